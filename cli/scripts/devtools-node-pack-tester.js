@@ -127,13 +127,14 @@ const QA = {
   async export(projectName = 'exported-state') {
     await this._init()
     const data = {
-      version: '1.0',
+      version: '1.1',
       exported_at: new Date().toISOString(),
       project_name: projectName,
       environment: {
         url: window.location.origin,
         user_agent: navigator.userAgent,
-        hanzo_studio_version: window.comfyAPI?.version || 'unknown'
+        hanzo_studio_version: window.comfyAPI?.version || null,
+        frontend_version: window.__COMFYUI_FRONTEND_VERSION__ || null
       },
       packs: Object.entries(this._byMod).map(([mod, nodes]) => ({
         name: mod,
@@ -0,0 +1,381 @@
+use crate::generators::NodePromptGenerator;
+use crate::models::{ApiPrompt, ApiTestStatus, NodeApiResult, ObjectInfo, PromptRun, ServerEvent};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+/// How long to wait for the last websocket events once `/history` has the
+/// result.
+const FINISH_GRACE: Duration = Duration::from_secs(1);
+
+pub struct ApiClient {
+    base_url: String,
+    agent: ureq::Agent,
+    client_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueResponse {
+    Queued { prompt_id: String },
+    Rejected { reason: String },
+}
+
+pub struct EventStream {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Execution {
+    Success,
+    Error { reason: String },
+}
+
+impl ApiClient {
+    pub fn new(base_url: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(Duration::from_secs(10))
+            .timeout_read(Duration::from_secs(120))
+            .build();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            agent,
+            client_id: format!("comfy-qa-{}", std::process::id()),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Connect to `/ws` as this client. Connect before queueing the prompts
+    /// whose events you want.
+    pub fn events(&self) -> Result<EventStream> {
+        let base = match self.base_url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some((_, rest)) => format!("ws://{}", rest),
+            None => format!("ws://{}", self.base_url),
+        };
+        let url = format!("{}/ws?clientId={}", base, self.client_id);
+        let (socket, _) =
+            tungstenite::connect(&url).with_context(|| format!("Failed to connect to {}", url))?;
+        Ok(EventStream { socket })
+    }
+
+    pub fn object_info(&self) -> Result<ObjectInfo> {
+        let url = format!("{}/object_info", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .call()
+            .with_context(|| format!("Failed to fetch {}", url))?;
+        response
+            .into_json()
+            .with_context(|| format!("Failed to parse {}", url))
+    }
+
+    pub fn queue_prompt(&self, prompt: &ApiPrompt) -> Result<QueueResponse> {
+        let url = format!("{}/prompt", self.base_url);
+        let body = prompt.to_request_body(Some(&self.client_id));
+
+        let value: Value = match self.agent.post(&url).send_json(body) {
+            Ok(response) => response.into_json()?,
+            Err(ureq::Error::Status(status, response)) => {
+                let text = response.into_string().unwrap_or_default();
+                let reason = serde_json::from_str(&text)
+                    .ok()
+                    .and_then(|value| rejection_reason(&value))
+                    .unwrap_or_else(|| format!("HTTP {}: {}", status, text.trim()));
+                return Ok(QueueResponse::Rejected { reason });
+            }
+            Err(err) => return Err(err).with_context(|| format!("Failed to post to {}", url)),
+        };
+
+        // Older servers answer 200 with node errors instead of 400
+        if let Some(reason) = rejection_reason(&value) {
+            return Ok(QueueResponse::Rejected { reason });
+        }
+        match value.get("prompt_id").and_then(Value::as_str) {
+            Some(prompt_id) => Ok(QueueResponse::Queued {
+                prompt_id: prompt_id.to_string(),
+            }),
+            None => anyhow::bail!("{} answered without a prompt_id", url),
+        }
+    }
+
+    /// `None` until the prompt has finished.
+    pub fn history(&self, prompt_id: &str) -> Result<Option<Execution>> {
+        let url = format!("{}/history/{}", self.base_url, prompt_id);
+        let value: Value = self
+            .agent
+            .get(&url)
+            .call()
+            .with_context(|| format!("Failed to fetch {}", url))?
+            .into_json()?;
+        let Some(entry) = value.get(prompt_id) else {
+            return Ok(None);
+        };
+        let status = entry.get("status");
+        let status_str = status
+            .and_then(|s| s.get("status_str"))
+            .and_then(Value::as_str);
+        let completed = status
+            .and_then(|s| s.get("completed"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        Ok(match status_str {
+            Some("error") => Some(Execution::Error {
+                reason: execution_error(entry).unwrap_or_else(|| "execution failed".to_string()),
+            }),
+            Some("success") if completed => Some(Execution::Success),
+            // Servers without status only add the entry once done
+            None => Some(Execution::Success),
+            _ => None,
+        })
+    }
+}
+
+impl EventStream {
+    pub fn next_before(&mut self, deadline: Instant) -> Result<Option<ServerEvent>> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            if let MaybeTlsStream::Plain(stream) = self.socket.get_mut() {
+                stream.set_read_timeout(Some(remaining))?;
+            }
+            match self.socket.read() {
+                Ok(Message::Text(text)) => {
+                    if let Some(event) = ServerEvent::from_json(&text) {
+                        return Ok(Some(event));
+                    }
+                }
+                // Binary messages are preview images
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err))
+                    if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                {
+                    return Ok(None)
+                }
+                Err(err) => return Err(err).context("Lost the websocket connection"),
+            }
+        }
+    }
+}
+
+pub struct ApiTestRunner<'a> {
+    pub client: &'a ApiClient,
+    pub object_info: &'a ObjectInfo,
+    pub timeout: Duration,
+    pub poll_interval: Duration,
+    pub validate_only: bool,
+    events: RefCell<Option<EventStream>>,
+}
+
+impl<'a> ApiTestRunner<'a> {
+    pub fn new(client: &'a ApiClient, object_info: &'a ObjectInfo) -> Self {
+        Self {
+            client,
+            object_info,
+            timeout: Duration::from_secs(120),
+            poll_interval: Duration::from_millis(500),
+            validate_only: false,
+            events: RefCell::new(None),
+        }
+    }
+
+    pub fn monitor(&mut self) -> Result<()> {
+        self.events = RefCell::new(Some(self.client.events()?));
+        Ok(())
+    }
+
+    /// Errors only when the server can't be reached; everything the server
+    /// says about the node is part of the result.
+    pub fn run_node(&self, class_type: &str) -> Result<NodeApiResult> {
+        let Some(conversion) = NodePromptGenerator::new(self.object_info).generate(class_type)
+        else {
+            return Ok(NodeApiResult::new(
+                class_type,
+                ApiTestStatus::Blocked,
+                Some("not in the server's /object_info".to_string()),
+            ));
+        };
+        // The server would reject it; say why instead
+        if !conversion.warnings.is_empty() {
+            return Ok(NodeApiResult::new(
+                class_type,
+                ApiTestStatus::Blocked,
+                Some(conversion.warnings.join("; ")),
+            ));
+        }
+
+        self.run_prompt(class_type, &conversion.prompt)
+    }
+
+    /// Queue a converted workflow; the result and its run go by `name`.
+    pub fn run_workflow(&self, name: &str, prompt: &ApiPrompt) -> Result<NodeApiResult> {
+        let mut result = self.run_prompt(name, prompt)?;
+        if let Some(run) = result.execution.as_mut() {
+            run.workflow = true;
+        }
+        Ok(result)
+    }
+
+    fn run_prompt(&self, label: &str, prompt: &ApiPrompt) -> Result<NodeApiResult> {
+        let prompt_id = match self.client.queue_prompt(prompt)? {
+            QueueResponse::Rejected { reason } => {
+                return Ok(NodeApiResult::new(
+                    label,
+                    ApiTestStatus::Failed,
+                    Some(reason),
+                ))
+            }
+            QueueResponse::Queued { prompt_id } => prompt_id,
+        };
+
+        let mut result = if self.validate_only {
+            NodeApiResult::new(label, ApiTestStatus::Validated, None)
+        } else {
+            self.wait_for(label, &prompt_id, prompt)?
+        };
+        result.prompt_id = Some(prompt_id);
+        Ok(result)
+    }
+
+    fn wait_for(
+        &self,
+        class_type: &str,
+        prompt_id: &str,
+        prompt: &ApiPrompt,
+    ) -> Result<NodeApiResult> {
+        let started = Instant::now();
+        let mut run = self
+            .events
+            .borrow()
+            .is_some()
+            .then(|| PromptRun::new(class_type, prompt_id));
+
+        loop {
+            let mut result = match self.client.history(prompt_id)? {
+                Some(Execution::Success) => {
+                    NodeApiResult::new(class_type, ApiTestStatus::Executed, None)
+                }
+                Some(Execution::Error { reason }) => {
+                    NodeApiResult::new(class_type, ApiTestStatus::Failed, Some(reason))
+                }
+                None if started.elapsed() >= self.timeout => NodeApiResult::new(
+                    class_type,
+                    ApiTestStatus::Blocked,
+                    Some(format!(
+                        "accepted but not finished after {}s",
+                        self.timeout.as_secs()
+                    )),
+                ),
+                None => {
+                    let deadline = Instant::now() + self.poll_interval;
+                    self.watch(run.as_mut(), prompt, deadline);
+                    thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                    continue;
+                }
+            };
+
+            if let Some(mut run) = run {
+                self.watch(Some(&mut run), prompt, Instant::now() + FINISH_GRACE);
+                run.finish(Utc::now());
+                result.execution = Some(run);
+            }
+            return Ok(result);
+        }
+    }
+
+    /// Record events into `run` until `deadline` or until it finishes. A
+    /// broken connection stops monitoring; `/history` still decides the
+    /// result.
+    fn watch(&self, run: Option<&mut PromptRun>, prompt: &ApiPrompt, deadline: Instant) {
+        let mut events = self.events.borrow_mut();
+        let mut lost = false;
+        if let (Some(run), Some(stream)) = (run, events.as_mut()) {
+            while !run.is_finished() {
+                match stream.next_before(deadline) {
+                    Ok(Some(event)) => run.record(&event, prompt, Utc::now()),
+                    Ok(None) => break,
+                    Err(_) => {
+                        lost = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if lost {
+            *events = None;
+        }
+    }
+}
+
+pub fn rejection_reason(response: &Value) -> Option<String> {
+    let error = response.get("error")?;
+    let mut parts = vec![match error {
+        Value::String(message) => message.clone(),
+        _ => error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("prompt rejected")
+            .to_string(),
+    }];
+
+    if let Some(node_errors) = response.get("node_errors").and_then(Value::as_object) {
+        for node in node_errors.values() {
+            let class_type = node
+                .get("class_type")
+                .and_then(Value::as_str)
+                .unwrap_or("?");
+            for err in node
+                .get("errors")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let message = err.get("message").and_then(Value::as_str).unwrap_or("");
+                match err.get("details").and_then(Value::as_str) {
+                    Some(details) if !details.is_empty() => {
+                        parts.push(format!("{}: {}: {}", class_type, message, details))
+                    }
+                    _ => parts.push(format!("{}: {}", class_type, message)),
+                }
+            }
+        }
+    }
+
+    Some(parts.join("; "))
+}
+
+fn execution_error(entry: &Value) -> Option<String> {
+    let messages = entry.get("status")?.get("messages")?.as_array()?;
+    messages.iter().find_map(|message| {
+        let (kind, data) = (message.get(0)?.as_str()?, message.get(1)?);
+        if kind != "execution_error" {
+            return None;
+        }
+        let exception = data
+            .get("exception_message")
+            .and_then(Value::as_str)
+            .unwrap_or("execution error")
+            .trim();
+        Some(match data.get("node_type").and_then(Value::as_str) {
+            Some(node_type) => format!("{}: {}", node_type, exception),
+            None => exception.to_string(),
+        })
+    })
+}
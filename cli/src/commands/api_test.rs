@@ -0,0 +1,292 @@
+use crate::api::{ApiClient, ApiTestRunner};
+use crate::commands::convert::convert_executable_workflows_with;
+use crate::commands::find_repo_root;
+use crate::models::{
+    ApiTestStatus, Catalog, Checklist, DetailedChecklist, Metadata, NodeApiResult, ObjectInfo,
+    PackApiResult, RunLog, TestDimension, WorkflowScope, DEPRECATED_SUFFIX,
+};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use std::path::Path;
+use std::time::Duration;
+
+pub fn run(
+    project: String,
+    pack: Option<String>,
+    url: String,
+    timeout: u64,
+    validate_only: bool,
+) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let project_dir = repo_root.join("checklists").join(&project);
+    let checklist_path = project_dir.join("checklist.md");
+    if !checklist_path.exists() {
+        anyhow::bail!("Project '{}' not found", project);
+    }
+    let mut checklist = Checklist::from_file(&checklist_path)?;
+
+    let client = ApiClient::new(&url);
+    let object_info = client
+        .object_info()
+        .with_context(|| format!("Could not reach Hanzo Studio at {}", client.base_url()))?;
+    let by_pack = object_info.by_pack();
+
+    let packs: Vec<String> = match pack {
+        Some(pack) => {
+            if !by_pack.contains_key(&pack) {
+                anyhow::bail!(
+                    "Pack '{}' has no nodes on {} (packs there: {})",
+                    pack,
+                    client.base_url(),
+                    by_pack.keys().cloned().collect::<Vec<_>>().join(", ")
+                );
+            }
+            vec![pack]
+        }
+        None => checklist
+            .packs
+            .iter()
+            .map(|p| p.name.clone())
+            .filter(|name| by_pack.contains_key(name))
+            .collect(),
+    };
+    if packs.is_empty() {
+        anyhow::bail!(
+            "None of the packs in {} are on {}",
+            checklist_path.display(),
+            client.base_url()
+        );
+    }
+
+    let mut runner = ApiTestRunner::new(&client, &object_info);
+    runner.timeout = Duration::from_secs(timeout);
+    runner.validate_only = validate_only;
+
+    println!(
+        "🔌 API testing {} against {}",
+        project.bold(),
+        client.base_url().cyan()
+    );
+
+    // Nothing executes with --validate-only, so there's nothing to watch
+    let mut run_log = None;
+    if !validate_only {
+        match runner.monitor() {
+            Ok(()) => run_log = Some(RunLog::new(client.base_url(), client.client_id())),
+            Err(err) => println!(
+                "   {} No execution events, only results: {:#}",
+                "⚠".yellow(),
+                err
+            ),
+        }
+    }
+
+    let scope = WorkflowScope::for_project(&repo_root, &project)?;
+    let mut results = Vec::new();
+    let mut interrupted = None;
+    for pack in &packs {
+        println!("\n{}", pack.bold());
+        let mut nodes = Vec::new();
+        for class_type in &by_pack[pack] {
+            // The browser script leaves deprecated nodes out too
+            if object_info.get(class_type).is_some_and(|d| d.deprecated) {
+                continue;
+            }
+            match runner.run_node(class_type) {
+                Ok(result) => {
+                    print_result(&result);
+                    nodes.push(result);
+                }
+                Err(err) => {
+                    interrupted =
+                        Some(err.context(format!("API testing stopped at {}", class_type)));
+                    break;
+                }
+            }
+        }
+
+        // Whole workflows show which node of a real graph fails
+        let mut workflows = Vec::new();
+        if interrupted.is_none() {
+            let prompts =
+                convert_executable_workflows_with(&project_dir, &scope, pack, &object_info)?;
+            if !prompts.is_empty() {
+                println!("  {}", "Executable workflows".dimmed());
+            }
+            for (name, prompt) in &prompts {
+                match runner.run_workflow(name, prompt) {
+                    Ok(result) => {
+                        print_result(&result);
+                        workflows.push(result);
+                    }
+                    Err(err) => {
+                        interrupted =
+                            Some(err.context(format!("API testing stopped at workflow {}", name)));
+                        break;
+                    }
+                }
+            }
+        }
+        results.push(PackApiResult {
+            pack: pack.clone(),
+            nodes,
+            workflows,
+        });
+        if interrupted.is_some() {
+            break;
+        }
+    }
+
+    // Keep what was tested before the server went away; the pack it stopped
+    // in only gets its node results
+    let incomplete = interrupted
+        .as_ref()
+        .and_then(|_| results.last())
+        .map(|r| r.pack.clone());
+    write_results(
+        &project_dir,
+        &mut checklist,
+        &object_info,
+        &results,
+        incomplete.as_deref(),
+    )?;
+
+    let mut run_log_path = None;
+    if let Some(mut run_log) = run_log {
+        run_log.prompts = results
+            .iter()
+            .flat_map(|r| r.nodes.iter().chain(&r.workflows))
+            .filter_map(|n| n.execution.clone())
+            .collect();
+        run_log.finished_at = Some(Utc::now());
+        let path = RunLog::path(&project_dir);
+        run_log.to_file(&path)?;
+        run_log_path = Some(path);
+    }
+    if let Some(err) = interrupted {
+        println!(
+            "\n   📄 Saved the results so far to {}",
+            checklist_path.display()
+        );
+        return Err(err);
+    }
+
+    let all: Vec<&NodeApiResult> = results
+        .iter()
+        .flat_map(|r| r.nodes.iter().chain(&r.workflows))
+        .collect();
+    let count = |status| all.iter().filter(|r| r.status == status).count();
+    println!(
+        "\n{} executed, {} validated, {} failed, {} blocked",
+        count(ApiTestStatus::Executed).to_string().green(),
+        count(ApiTestStatus::Validated).to_string().green(),
+        count(ApiTestStatus::Failed).to_string().red(),
+        count(ApiTestStatus::Blocked).to_string().yellow()
+    );
+    println!("   📄 Updated: {}", checklist_path.display());
+    if let Some(path) = run_log_path {
+        println!("   📄 Run log: {}", path.display());
+    }
+
+    let failed = count(ApiTestStatus::Failed);
+    if failed > 0 {
+        anyhow::bail!("{} node(s) or workflow(s) failed API testing", failed);
+    }
+    Ok(())
+}
+
+fn print_result(result: &NodeApiResult) {
+    let status = match result.status {
+        ApiTestStatus::Executed | ApiTestStatus::Validated => result.status.label().green(),
+        ApiTestStatus::Failed => result.status.label().red(),
+        ApiTestStatus::Blocked => result.status.label().yellow(),
+    };
+    let mut text = match &result.reason {
+        Some(reason) => format!("{}: {}", result.class_type, reason),
+        None => result.class_type.clone(),
+    };
+    if let Some(duration) = result.execution.as_ref().and_then(|run| run.duration_ms) {
+        text = format!("{} {}", text, format!("({} ms)", duration).dimmed());
+    }
+    println!(
+        "  {} {:<9} {}",
+        result.status.outcome().icon(),
+        status,
+        text
+    );
+}
+
+/// Record API results on the packs in checklist.md, their nodes in
+/// checklist-detailed.md and the packs in metadata.json.
+fn write_results(
+    project_dir: &Path,
+    checklist: &mut Checklist,
+    object_info: &ObjectInfo,
+    results: &[PackApiResult],
+    incomplete: Option<&str>,
+) -> Result<()> {
+    let complete = || {
+        results
+            .iter()
+            .filter(|r| Some(r.pack.as_str()) != incomplete)
+    };
+    for result in complete() {
+        if let Some(pack) = checklist.packs.iter_mut().find(|p| p.name == result.pack) {
+            pack.set_dimension_outcome(TestDimension::Api, result.outcome());
+        }
+    }
+    checklist.write()?;
+
+    let detailed_path = project_dir.join("checklist-detailed.md");
+    if detailed_path.exists() {
+        let detailed = DetailedChecklist::from_file(&detailed_path)?;
+        let catalog = Catalog::for_project(project_dir)?;
+        for result in results {
+            let Some(existing) = detailed.get_nodes(&result.pack) else {
+                continue;
+            };
+            let mut updated = Vec::new();
+            for node_result in &result.nodes {
+                // Classes the import didn't see go by the server's display name
+                let display_name = object_info
+                    .get(&node_result.class_type)
+                    .and_then(|d| d.display_name.as_deref());
+                let found = existing.iter().find(|n| {
+                    catalog.lists(&n.name, &node_result.class_type)
+                        || display_name
+                            .is_some_and(|d| n.name.trim_end_matches(DEPRECATED_SUFFIX) == d)
+                });
+                if let Some(node) = found {
+                    let mut node = node.clone();
+                    node.set_dimension_outcome(TestDimension::Api, node_result.status.outcome());
+                    updated.push(node);
+                }
+            }
+            DetailedChecklist::write_nodes(&detailed_path, &result.pack, &updated)?;
+        }
+    }
+
+    let metadata_path = project_dir.join("metadata.json");
+    if metadata_path.exists() {
+        let mut metadata = Metadata::from_file(&metadata_path)?;
+        for result in complete() {
+            let Some(pack_metadata) = metadata.packs.get_mut(&result.pack) else {
+                continue;
+            };
+            let checkbox = checklist
+                .packs
+                .iter()
+                .find(|p| p.name == result.pack)
+                .map_or_else(|| pack_metadata.outcome(), |p| p.outcome);
+            pack_metadata.record_api_result(checkbox, result);
+        }
+        metadata.last_updated = Some(Utc::now());
+        metadata.calculate_stats();
+        metadata
+            .to_file(&metadata_path)
+            .with_context(|| format!("Failed to write {}", metadata_path.display()))?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::ptr_arg)]
 #![allow(unused_assignments)]
 
+use crate::models::DEFAULT_HEADER;
 use anyhow::Result;
 use console::style;
 use dialoguer::{Confirm, Input, Select};
@@ -225,6 +226,7 @@ fn create_new_project() -> Result<String> {
 
         let metadata = format!(
             r#"{{
+  "schema_version": {},
   "project_name": "{}",
   "created_at": "{}",
   "environment": {{
@@ -234,6 +236,7 @@ fn create_new_project() -> Result<String> {
   "packs": {{}}
 }}
 "#,
+            crate::models::Metadata::current_schema_version(),
             project_name,
             chrono::Utc::now().to_rfc3339()
         );
@@ -241,11 +244,11 @@ fn create_new_project() -> Result<String> {
         fs::write(project_dir.join("metadata.json"), metadata)?;
         fs::write(
             project_dir.join("checklist.md"),
-            "# Node Pack Testing Checklist\n\n",
+            format!("{}\n\n", DEFAULT_HEADER),
         )?;
         fs::write(
             project_dir.join("checklist-detailed.md"),
-            "# Node Pack Testing Checklist\n\n",
+            format!("{}\n\n", DEFAULT_HEADER),
         )?;
 
         println!("\n{} Project created", style("✓").green());
@@ -267,7 +270,7 @@ fn show_dashboard(project: &str) -> Result<()> {
 
     if let Ok(checklist) = crate::models::Checklist::from_file(project_dir.join("checklist.md")) {
         let total = checklist.packs.len();
-        let tested = checklist.packs.iter().filter(|p| p.tested).count();
+        let tested = checklist.packs.iter().filter(|p| p.tested()).count();
 
         if total > 0 {
             let percent = (tested as f64 / total as f64) * 100.0;
@@ -358,7 +361,6 @@ fn show_browser_script(project: &str) -> Result<()> {
 fn show_pack_list(project: &str) -> Result<()> {
     let repo_root = find_repo_root()?;
     let project_dir = repo_root.join("checklists").join(project);
-    let workflows_dir = repo_root.join("workflows");
 
     loop {
         clear_screen();
@@ -377,7 +379,7 @@ fn show_pack_list(project: &str) -> Result<()> {
             }
         };
 
-        let workflows = crate::models::Workflow::load_all(&workflows_dir).unwrap_or_default();
+        let workflows = load_project_workflows(project);
 
         // Categorize packs
         let mut tested_ok = Vec::new();
@@ -385,7 +387,7 @@ fn show_pack_list(project: &str) -> Result<()> {
         let mut untested = Vec::new();
 
         for pack in &checklist.packs {
-            if pack.tested {
+            if pack.tested() {
                 if let Some(workflow) = workflows.get(&pack.name) {
                     if pack.node_count == workflow.node_count {
                         tested_ok.push(pack);
@@ -488,7 +490,7 @@ fn show_pack_category(
         let mut items: Vec<String> = packs
             .iter()
             .map(|p| {
-                let status = if p.tested {
+                let status = if p.tested() {
                     style("✓").green().to_string()
                 } else {
                     style("○").dim().to_string()
@@ -576,16 +578,25 @@ fn show_packs_with_issues(
         }
 
         let (pack, _) = packs_with_issues[selection];
-        let workflows_dir = find_repo_root()?.join("workflows");
-        let workflows = crate::models::Workflow::load_all(&workflows_dir).unwrap_or_default();
+        let workflows = load_project_workflows(project);
         show_pack_details(project, pack, &workflows)?;
     }
 
     Ok(())
 }
 
+/// All-nodes workflows in the project's scope; empty if they can't be loaded.
+fn load_project_workflows(
+    project: &str,
+) -> std::collections::HashMap<String, crate::models::Workflow> {
+    find_repo_root()
+        .and_then(|repo_root| crate::models::WorkflowScope::for_project(&repo_root, project))
+        .and_then(|scope| scope.load_all())
+        .unwrap_or_default()
+}
+
 fn show_pack_details(
-    _project: &str,
+    project: &str,
     pack: &crate::models::NodePack,
     workflows: &std::collections::HashMap<String, crate::models::Workflow>,
 ) -> Result<()> {
@@ -597,7 +608,7 @@ fn show_pack_details(
 
     println!(
         "Status: {}",
-        if pack.tested {
+        if pack.tested() {
             style("✓ Tested").green()
         } else {
             style("○ Not Tested").yellow()
@@ -620,11 +631,32 @@ fn show_pack_details(
                 delta
             );
         }
-    } else if pack.tested {
+    } else if pack.tested() {
         println!();
         println!("{} Workflow file missing", style("⚠").yellow());
     }
 
+    // Executable workflows that use this pack, whatever their filename
+    let repo_root = find_repo_root()?;
+    let catalog = crate::models::Catalog::for_project(&repo_root.join("checklists").join(project))
+        .unwrap_or_default()
+        .with_workflows(workflows.values());
+    let executable = crate::models::WorkflowScope::for_project(&repo_root, project)
+        .and_then(|scope| scope.load_executable(&catalog))
+        .unwrap_or_default();
+    if let Some(using) = catalog.index_by_pack(&executable).get(&pack.name) {
+        println!();
+        println!("Executable workflows using this pack:");
+        for (workflow, count) in using {
+            let file_name = workflow
+                .file_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("?");
+            println!("  {} {} ({} nodes)", style("→").dim(), file_name, count);
+        }
+    }
+
     println!();
     pause();
     Ok(())
@@ -765,7 +797,6 @@ fn run_diff_interactive(project: &str) -> Result<()> {
 
         let repo_root = find_repo_root()?;
         let checklists_dir = repo_root.join("checklists");
-        let workflows_dir = repo_root.join("workflows");
         let project_dir = checklists_dir.join(project);
 
         let checklist = match crate::models::Checklist::from_file(project_dir.join("checklist.md"))
@@ -778,7 +809,7 @@ fn run_diff_interactive(project: &str) -> Result<()> {
             }
         };
 
-        let workflows = crate::models::Workflow::load_all(&workflows_dir).unwrap_or_default();
+        let workflows = load_project_workflows(project);
 
         // Calculate diff
         let mut matches = Vec::new();
@@ -796,7 +827,7 @@ fn run_diff_interactive(project: &str) -> Result<()> {
                         workflow.node_count,
                     ));
                 }
-            } else if pack.tested {
+            } else if pack.tested() {
                 missing_workflows.push((pack.name.clone(), pack.node_count));
             }
         }
@@ -930,6 +961,8 @@ fn show_count_mismatches_interactive(
     } else {
         None
     };
+    let catalog =
+        crate::models::Catalog::for_project(project_dir)?.with_workflows(workflows.values());
 
     loop {
         clear_screen();
@@ -972,8 +1005,9 @@ fn show_count_mismatches_interactive(
 
         if let Some(workflow) = workflows.get(pack_name) {
             if let Some(ref detailed) = detailed_checklist {
-                let node_diff =
-                    crate::commands::diff::calculate_node_diff(pack_name, workflow, detailed);
+                let node_diff = crate::commands::diff::calculate_node_diff(
+                    pack_name, workflow, detailed, &catalog,
+                );
                 show_node_diff_details(&node_diff, *checklist_count, *workflow_count)?;
             } else {
                 clear_screen();
@@ -1067,7 +1101,6 @@ fn run_validate_interactive(project: &str) -> Result<()> {
 
         let repo_root = find_repo_root()?;
         let checklists_dir = repo_root.join("checklists");
-        let workflows_dir = repo_root.join("workflows");
         let project_dir = checklists_dir.join(project);
 
         let checklist_path = project_dir.join("checklist.md");
@@ -1088,7 +1121,7 @@ fn run_validate_interactive(project: &str) -> Result<()> {
             None
         };
 
-        let workflows = crate::models::Workflow::load_all(&workflows_dir).unwrap_or_default();
+        let workflows = load_project_workflows(project);
 
         let results = crate::validators::Validator::validate_project(
             &checklist,
@@ -0,0 +1,66 @@
+use crate::commands::find_repo_root;
+use crate::models::{Catalog, ObjectInfo};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+
+pub fn import(object_info_file: String, project: String) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let project_dir = repo_root.join("checklists").join(&project);
+    if !project_dir.exists() {
+        anyhow::bail!("Project '{}' not found", project);
+    }
+
+    // Parse before copying so a bad file never replaces a good snapshot
+    let object_info = ObjectInfo::from_file(&object_info_file)?;
+    if object_info.nodes.is_empty() {
+        anyhow::bail!("{} has no node definitions", object_info_file);
+    }
+
+    let snapshot_path = project_dir.join(ObjectInfo::PROJECT_FILE);
+    fs::copy(&object_info_file, &snapshot_path)
+        .with_context(|| format!("Failed to write {}", snapshot_path.display()))?;
+
+    let catalog_path = Catalog::path(&project_dir);
+    let mut catalog = Catalog::for_project(&project_dir)?;
+    let newer = Catalog::from_object_info(&object_info);
+    let added = newer
+        .nodes
+        .keys()
+        .filter(|class| catalog.get(class).is_none_or(|node| node.removed))
+        .count();
+    let removed: Vec<String> = catalog
+        .nodes
+        .iter()
+        .filter(|(class, node)| !node.removed && !newer.nodes.contains_key(*class))
+        .map(|(class, _)| class.clone())
+        .collect();
+    catalog.update(newer);
+    catalog.to_file(&catalog_path)?;
+
+    let packs = object_info.by_pack();
+    let api_nodes = object_info.nodes.values().filter(|d| d.api_node).count();
+    let deprecated = object_info.nodes.values().filter(|d| d.deprecated).count();
+
+    println!(
+        "📚 Imported {} node definitions in {} packs into {}",
+        object_info.nodes.len(),
+        packs.len(),
+        project.bold()
+    );
+    println!(
+        "   {} deprecated, {} API nodes",
+        deprecated.to_string().yellow(),
+        api_nodes.to_string().cyan()
+    );
+    if added > 0 {
+        println!("   {} {} new to the catalog", "+".green(), added);
+    }
+    for class in &removed {
+        println!("   {} {} (kept as removed)", "-".red(), class);
+    }
+    println!("   📄 {}", snapshot_path.display());
+    println!("   📄 {}", catalog_path.display());
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use crate::models::{Checklist, Workflow};
+use crate::models::{
+    Catalog, Checklist, DetailedChecklist, NodePack, TestDimension, TestOutcome, WorkflowScope,
+};
 use anyhow::Result;
 use console::style;
 use dialoguer::Select;
@@ -8,7 +10,6 @@ use std::process::Command;
 pub fn run(project: String) -> Result<()> {
     let repo_root = find_repo_root()?;
     let checklists_dir = repo_root.join("checklists");
-    let workflows_dir = repo_root.join("workflows");
     let project_dir = checklists_dir.join(&project);
 
     if !project_dir.exists() {
@@ -21,7 +22,14 @@ pub fn run(project: String) -> Result<()> {
 
     let checklist_path = project_dir.join("checklist.md");
     let checklist = Checklist::from_file(checklist_path)?;
-    let workflows = Workflow::load_all(workflows_dir)?;
+    let detailed_path = project_dir.join("checklist-detailed.md");
+    let detailed = if detailed_path.exists() {
+        Some(DetailedChecklist::from_file(&detailed_path)?)
+    } else {
+        None
+    };
+    let scope = WorkflowScope::for_project(&repo_root, &project)?;
+    let workflows = scope.load_all()?;
 
     println!();
     println!("{}", style(format!("Testing Progress: {}", project)).bold());
@@ -29,7 +37,7 @@ pub fn run(project: String) -> Result<()> {
     println!();
 
     let total = checklist.packs.len();
-    let tested = checklist.packs.iter().filter(|p| p.tested).count();
+    let tested = checklist.packs.iter().filter(|p| p.tested()).count();
     let percent = if total > 0 {
         (tested as f64 / total as f64) * 100.0
     } else {
@@ -49,10 +57,74 @@ pub fn run(project: String) -> Result<()> {
     println!("  {}", style(bar).cyan());
     println!();
     println!("  Tested: {} out of {} packs", style(tested).green(), total);
+    let summary = TestOutcome::summarize(&checklist.outcome_counts());
+    if !summary.is_empty() {
+        println!("          {}", style(summary).dim());
+    }
+    let (ui_packs, _) = checklist.dimension_progress(TestDimension::Ui);
+    let (api_packs, _) = checklist.dimension_progress(TestDimension::Api);
+    println!(
+        "          UI: {} · API: {}",
+        style(ui_packs).green(),
+        style(api_packs).green()
+    );
+
+    // Packs range from a handful of nodes to 100+, so show node progress too
+    if let Some(detailed) = &detailed {
+        let (tested_nodes, total_nodes) = detailed.progress();
+        if total_nodes > 0 {
+            println!(
+                "  Nodes:  {} out of {} nodes ({:.0}%)",
+                style(tested_nodes).green(),
+                total_nodes,
+                tested_nodes as f64 / total_nodes as f64 * 100.0
+            );
+            println!(
+                "          UI: {} · API: {}",
+                style(detailed.dimension_progress(TestDimension::Ui).0).green(),
+                style(detailed.dimension_progress(TestDimension::Api).0).green()
+            );
+        }
+    }
     println!();
 
+    // Executable workflows count toward every pack whose nodes they use
+    let catalog = Catalog::for_project(&project_dir)?.with_workflows(workflows.values());
+    let executable = scope.load_executable(&catalog)?;
+    if !executable.is_empty() {
+        let coverage = catalog.index_by_pack(&executable);
+        let covered = checklist
+            .packs
+            .iter()
+            .filter(|p| coverage.contains_key(&p.name))
+            .count();
+        println!(
+            "  Executable workflows: {} (covering {} of {} packs)",
+            executable.len(),
+            covered,
+            total
+        );
+        println!();
+    }
+
+    // Tested but not fine
+    let attention: Vec<_> = checklist
+        .packs
+        .iter()
+        .filter(|p| matches!(p.outcome, TestOutcome::Fail | TestOutcome::Blocked))
+        .collect();
+    if !attention.is_empty() {
+        println!("{}", style("Needs attention:").bold());
+        println!();
+        for pack in &attention {
+            let outcome = pack.outcome;
+            println!("  {} {} ({})", outcome.icon(), pack.name, outcome);
+        }
+        println!();
+    }
+
     // Show what needs testing
-    let untested: Vec<_> = checklist.packs.iter().filter(|p| !p.tested).collect();
+    let untested: Vec<_> = checklist.packs.iter().filter(|p| !p.tested()).collect();
 
     if !untested.is_empty() {
         println!("{}", style("Packs to test:").bold());
@@ -62,11 +134,11 @@ pub fn run(project: String) -> Result<()> {
 
         if show_all {
             for (i, pack) in untested.iter().enumerate() {
-                println!("  {}. {} ({} nodes)", i + 1, pack.name, pack.node_count);
+                println!("  {}. {}", i + 1, describe_pack(pack, detailed.as_ref()));
             }
         } else {
             for (i, pack) in untested.iter().take(10).enumerate() {
-                println!("  {}. {} ({} nodes)", i + 1, pack.name, pack.node_count);
+                println!("  {}. {}", i + 1, describe_pack(pack, detailed.as_ref()));
             }
             println!("  ... and {} more", untested.len() - 10);
             println!();
@@ -83,7 +155,7 @@ pub fn run(project: String) -> Result<()> {
                 println!("{}", style("All packs to test:").bold());
                 println!();
                 for (i, pack) in untested.iter().enumerate() {
-                    println!("  {}. {} ({} nodes)", i + 1, pack.name, pack.node_count);
+                    println!("  {}. {}", i + 1, describe_pack(pack, detailed.as_ref()));
                 }
             }
         }
@@ -110,7 +182,7 @@ pub fn run(project: String) -> Result<()> {
                     pack.name, pack.node_count, workflow.node_count
                 ));
             }
-        } else if pack.tested {
+        } else if pack.tested() {
             issues.push(format!("{}: workflow file missing", pack.name));
         }
     }
@@ -207,6 +279,16 @@ pub fn run(project: String) -> Result<()> {
     Ok(())
 }
 
+fn describe_pack(pack: &NodePack, detailed: Option<&DetailedChecklist>) -> String {
+    match detailed.map(|d| d.pack_progress(&pack.name)) {
+        Some((tested, total)) if tested > 0 => format!(
+            "{} ({} nodes, {}/{} tested)",
+            pack.name, pack.node_count, tested, total
+        ),
+        _ => format!("{} ({} nodes)", pack.name, pack.node_count),
+    }
+}
+
 fn pause() {
     println!("Press Enter to continue...");
     let mut input = String::new();
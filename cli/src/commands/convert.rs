@@ -0,0 +1,117 @@
+use crate::commands::find_repo_root;
+use crate::generators::ApiPromptGenerator;
+use crate::models::{ApiPrompt, Catalog, ObjectInfo, Workflow, WorkflowScope};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+pub fn run(
+    workflow_file: String,
+    object_info: Option<String>,
+    project: Option<String>,
+    output: Option<String>,
+) -> Result<()> {
+    let object_info_path = match (object_info, project) {
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(project)) => find_repo_root()?
+            .join("checklists")
+            .join(&project)
+            .join(ObjectInfo::PROJECT_FILE),
+        (None, None) => anyhow::bail!("Pass --object-info <file> or --project <name>"),
+    };
+
+    let object_info = ObjectInfo::from_file(&object_info_path)?;
+    let workflow = Workflow::from_file(&workflow_file)?;
+
+    // API files only need re-serializing
+    let (prompt, warnings) = match &workflow.api_prompt {
+        Some(prompt) => (prompt.clone(), Vec::new()),
+        None => {
+            let conversion = ApiPromptGenerator::new(&object_info).convert(&workflow.graph);
+            (conversion.prompt, conversion.warnings)
+        }
+    };
+
+    // Warnings go to stderr so stdout stays valid JSON
+    for warning in &warnings {
+        eprintln!("{} {}", "⚠".yellow(), warning);
+    }
+
+    match output {
+        Some(path) => {
+            prompt
+                .to_file(&path)
+                .with_context(|| format!("Failed to write {}", path))?;
+            eprintln!(
+                "{} Converted {} ({} nodes) -> {}",
+                "✓".green(),
+                workflow_file,
+                prompt.len(),
+                path
+            );
+        }
+        None => println!("{}", serde_json::to_string_pretty(&prompt)?),
+    }
+
+    Ok(())
+}
+
+/// Convert every executable workflow that uses `pack_name`. Returns nothing
+/// when the project has no `object_info.json` snapshot.
+pub fn convert_executable_workflows(
+    project_dir: &Path,
+    scope: &WorkflowScope,
+    pack_name: &str,
+) -> Result<Vec<(String, ApiPrompt)>> {
+    let object_info_path = project_dir.join(ObjectInfo::PROJECT_FILE);
+    if !object_info_path.exists() {
+        return Ok(Vec::new());
+    }
+    let object_info = ObjectInfo::from_file(&object_info_path)?;
+    convert_executable_workflows_with(project_dir, scope, pack_name, &object_info)
+}
+
+/// Like [`convert_executable_workflows`], with node definitions from
+/// somewhere other than the snapshot, e.g. a live server.
+pub fn convert_executable_workflows_with(
+    project_dir: &Path,
+    scope: &WorkflowScope,
+    pack_name: &str,
+    object_info: &ObjectInfo,
+) -> Result<Vec<(String, ApiPrompt)>> {
+    if !scope.roots.iter().any(|root| root.exists()) {
+        return Ok(Vec::new());
+    }
+
+    let all_nodes = scope.load_all()?;
+    let catalog = Catalog::for_project(project_dir)?.with_workflows(all_nodes.values());
+    let executable = scope.load_executable(&catalog)?;
+    let index = catalog.index_by_pack(&executable);
+
+    let mut prompts = Vec::new();
+    for (workflow, _) in index.get(pack_name).into_iter().flatten() {
+        let name = workflow
+            .file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("workflow")
+            .to_string();
+
+        let prompt = match &workflow.api_prompt {
+            Some(prompt) => prompt.clone(),
+            None => {
+                let conversion = ApiPromptGenerator::new(object_info).convert(&workflow.graph);
+                for warning in &conversion.warnings {
+                    eprintln!("{} {}: {}", "⚠".yellow(), name, warning);
+                }
+                conversion.prompt
+            }
+        };
+
+        if !prompt.is_empty() {
+            prompts.push((name, prompt));
+        }
+    }
+
+    Ok(prompts)
+}
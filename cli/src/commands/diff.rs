@@ -1,15 +1,14 @@
-use crate::models::{Checklist, DetailedChecklist, Workflow};
+use crate::commands::find_repo_root;
+use crate::models::{Catalog, Checklist, DetailedChecklist, TestOutcome, Workflow, WorkflowScope};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color as TableColor, Table};
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
 
 pub fn run(project: String, json: bool) -> Result<()> {
     let repo_root = find_repo_root()?;
     let checklists_dir = repo_root.join("checklists");
-    let workflows_dir = repo_root.join("workflows");
 
     let project_dir = checklists_dir.join(&project);
     if !project_dir.exists() {
@@ -20,14 +19,28 @@ pub fn run(project: String, json: bool) -> Result<()> {
     let checklist = Checklist::from_file(checklist_path)
         .with_context(|| format!("Failed to load checklist for '{}'", project))?;
 
-    let workflows = Workflow::load_all(workflows_dir)?;
+    let detailed_path = project_dir.join("checklist-detailed.md");
+    let detailed = if detailed_path.exists() {
+        Some(DetailedChecklist::from_file(&detailed_path)?)
+    } else {
+        None
+    };
+
+    let workflows = WorkflowScope::for_project(&repo_root, &project)?.load_all()?;
 
-    let diff = calculate_diff(&checklist, &workflows);
+    let mut diff = calculate_diff(&checklist, &workflows);
+    if let Some(detailed) = &detailed {
+        let catalog = Catalog::for_project(&project_dir)?.with_workflows(workflows.values());
+        for (pack, _, _) in &diff.count_mismatches {
+            let node_diff = calculate_node_diff(pack, &workflows[pack], detailed, &catalog);
+            diff.node_diffs.insert(pack.clone(), node_diff);
+        }
+    }
 
     if json {
-        print_json_diff(&project, &diff);
+        print_json_diff(&project, &diff, detailed.as_ref());
     } else {
-        print_text_diff(&project, &diff);
+        print_text_diff(&project, &diff, detailed.as_ref());
     }
 
     Ok(())
@@ -39,6 +52,9 @@ struct DiffResult {
     missing_workflows: Vec<(String, usize)>,
     new_packs: Vec<(String, usize)>,
     untested: Vec<(String, usize)>,
+    outcomes: HashMap<String, TestOutcome>,
+    /// For count mismatches, when there is a detailed checklist.
+    node_diffs: HashMap<String, NodeDiff>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,29 +64,34 @@ pub struct NodeDiff {
     pub extra_in_checklist: Vec<String>,     // In checklist but not in workflow
 }
 
+/// Workflows hold class names and checklists display names; `catalog`
+/// matches one to the other.
 pub fn calculate_node_diff(
     pack_name: &str,
     workflow: &Workflow,
     detailed_checklist: &DetailedChecklist,
+    catalog: &Catalog,
 ) -> NodeDiff {
     let workflow_nodes = workflow.get_unique_node_types();
-    let checklist_nodes = detailed_checklist
-        .get_nodes(pack_name)
-        .cloned()
-        .unwrap_or_default();
-
-    let workflow_set: HashSet<_> = workflow_nodes.iter().collect();
-    let checklist_set: HashSet<_> = checklist_nodes.iter().collect();
+    let checklist_nodes = detailed_checklist.node_names(pack_name);
 
     let missing_from_checklist: Vec<String> = workflow_nodes
         .iter()
-        .filter(|n| !checklist_set.contains(n))
-        .cloned()
+        .filter(|class| {
+            !checklist_nodes
+                .iter()
+                .any(|item| catalog.lists(item, class))
+        })
+        .map(|class| catalog.checklist_name(class))
         .collect();
 
     let extra_in_checklist: Vec<String> = checklist_nodes
         .iter()
-        .filter(|n| !workflow_set.contains(n))
+        .filter(|item| {
+            !workflow_nodes
+                .iter()
+                .any(|class| catalog.lists(item, class))
+        })
         .cloned()
         .collect();
 
@@ -88,6 +109,12 @@ fn calculate_diff(checklist: &Checklist, workflows: &HashMap<String, Workflow>)
         missing_workflows: Vec::new(),
         new_packs: Vec::new(),
         untested: Vec::new(),
+        outcomes: checklist
+            .packs
+            .iter()
+            .map(|p| (p.name.clone(), p.outcome))
+            .collect(),
+        node_diffs: HashMap::new(),
     };
 
     let checklist_packs: HashMap<_, _> = checklist
@@ -102,7 +129,7 @@ fn calculate_diff(checklist: &Checklist, workflows: &HashMap<String, Workflow>)
     for pack in &checklist.packs {
         if let Some(workflow) = workflows.get(&pack.name) {
             if pack.node_count == workflow.node_count {
-                if pack.tested {
+                if pack.tested() {
                     result.matches.push((pack.name.clone(), pack.node_count));
                 } else {
                     result.untested.push((pack.name.clone(), pack.node_count));
@@ -114,7 +141,7 @@ fn calculate_diff(checklist: &Checklist, workflows: &HashMap<String, Workflow>)
                     workflow.node_count,
                 ));
             }
-        } else if pack.tested {
+        } else if pack.tested() {
             result
                 .missing_workflows
                 .push((pack.name.clone(), pack.node_count));
@@ -135,7 +162,22 @@ fn calculate_diff(checklist: &Checklist, workflows: &HashMap<String, Workflow>)
     result
 }
 
-fn print_text_diff(project: &str, diff: &DiffResult) {
+/// "tested/total" nodes for a pack, or "-" without a detailed checklist.
+fn nodes_cell(pack: &str, detailed: Option<&DetailedChecklist>) -> Cell {
+    match detailed.map(|d| d.pack_progress(pack)) {
+        Some((tested, total)) if total > 0 => {
+            let cell = Cell::new(format!("{}/{}", tested, total));
+            if tested == total {
+                cell.fg(TableColor::Green)
+            } else {
+                cell
+            }
+        }
+        _ => Cell::new("-").fg(TableColor::DarkGrey),
+    }
+}
+
+fn print_text_diff(project: &str, diff: &DiffResult, detailed: Option<&DetailedChecklist>) {
     println!(
         "\n📊 Comparing {} checklist vs workflow files\n",
         project.bold()
@@ -160,16 +202,25 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
         Cell::new("Delta")
             .add_attribute(Attribute::Bold)
             .fg(TableColor::Cyan),
+        Cell::new("Nodes Tested")
+            .add_attribute(Attribute::Bold)
+            .fg(TableColor::Cyan),
     ]);
 
     // Add matches
     for (pack, count) in &diff.matches {
+        let status = match diff.outcomes.get(pack) {
+            Some(TestOutcome::Pass) | None => Cell::new("✓ Match").fg(TableColor::Green),
+            Some(TestOutcome::Fail) => Cell::new("✓ Match (fail)").fg(TableColor::Red),
+            Some(outcome) => Cell::new(format!("✓ Match ({})", outcome)).fg(TableColor::Yellow),
+        };
         table.add_row(vec![
             Cell::new(pack),
-            Cell::new("✓ Match").fg(TableColor::Green),
+            status,
             Cell::new(count.to_string()),
             Cell::new(count.to_string()),
             Cell::new("±0").fg(TableColor::DarkGrey),
+            nodes_cell(pack, detailed),
         ]);
     }
 
@@ -189,6 +240,7 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
             } else {
                 TableColor::Red
             }),
+            nodes_cell(pack, detailed),
         ]);
     }
 
@@ -200,6 +252,7 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
             Cell::new(count.to_string()),
             Cell::new("-"),
             Cell::new("-"),
+            nodes_cell(pack, detailed),
         ]);
     }
 
@@ -211,6 +264,7 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
             Cell::new("-"),
             Cell::new(count.to_string()),
             Cell::new("-"),
+            nodes_cell(pack, detailed),
         ]);
     }
 
@@ -222,12 +276,27 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
             Cell::new(count.to_string()),
             Cell::new("-"),
             Cell::new("-"),
+            nodes_cell(pack, detailed),
         ]);
     }
 
     println!("{}", table);
     println!();
 
+    for (pack, _, _) in &diff.count_mismatches {
+        let Some(node_diff) = diff.node_diffs.get(pack) else {
+            continue;
+        };
+        println!("{}:", pack.yellow());
+        for node in &node_diff.missing_from_checklist {
+            println!("   {} {}", "+".green(), node);
+        }
+        for node in &node_diff.extra_in_checklist {
+            println!("   {} {}", "-".red(), node);
+        }
+        println!();
+    }
+
     // Summary
     let total_packs = diff.matches.len() + diff.count_mismatches.len() + diff.untested.len();
     let completion = if total_packs > 0 {
@@ -243,6 +312,16 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
         diff.matches.len(),
         total_packs
     );
+    if let Some((tested_nodes, total_nodes)) = detailed.map(|d| d.progress()) {
+        if total_nodes > 0 {
+            println!(
+                "   Nodes: {:.0}% ({}/{})",
+                tested_nodes as f64 / total_nodes as f64 * 100.0,
+                tested_nodes,
+                total_nodes
+            );
+        }
+    }
     println!(
         "   Drift: {} count mismatches, {} new packs",
         diff.count_mismatches.len(),
@@ -257,10 +336,27 @@ fn print_text_diff(project: &str, diff: &DiffResult) {
     }
 }
 
-fn print_json_diff(project: &str, diff: &DiffResult) {
+fn print_json_diff(project: &str, diff: &DiffResult, detailed: Option<&DetailedChecklist>) {
+    let (tested_nodes, total_nodes) = detailed.map(|d| d.progress()).unwrap_or((0, 0));
+    let node_progress: serde_json::Map<String, serde_json::Value> = detailed
+        .map(|d| {
+            let mut packs: Vec<_> = d.packs.keys().collect();
+            packs.sort();
+            packs
+                .into_iter()
+                .map(|pack| {
+                    let (tested, total) = d.pack_progress(pack);
+                    (pack.clone(), json!({"tested": tested, "total": total}))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
     let json_output = json!({
         "project": project,
         "summary": {
+            "tested_nodes": tested_nodes,
+            "total_nodes": total_nodes,
             "matches": diff.matches.len(),
             "count_mismatches": diff.count_mismatches.len(),
             "missing_workflows": diff.missing_workflows.len(),
@@ -269,14 +365,18 @@ fn print_json_diff(project: &str, diff: &DiffResult) {
         },
         "details": {
             "matches": diff.matches.iter().map(|(name, count)| {
-                json!({"pack": name, "node_count": count})
+                json!({"pack": name, "node_count": count, "outcome": diff.outcomes.get(name)})
             }).collect::<Vec<_>>(),
             "count_mismatches": diff.count_mismatches.iter().map(|(name, checklist, workflow)| {
+                let node_diff = diff.node_diffs.get(name);
                 json!({
                     "pack": name,
+                    "outcome": diff.outcomes.get(name),
                     "checklist_count": checklist,
                     "workflow_count": workflow,
-                    "delta": *workflow as i64 - *checklist as i64
+                    "delta": *workflow as i64 - *checklist as i64,
+                    "added_nodes": node_diff.map(|d| &d.missing_from_checklist),
+                    "removed_nodes": node_diff.map(|d| &d.extra_in_checklist),
                 })
             }).collect::<Vec<_>>(),
             "missing_workflows": diff.missing_workflows.iter().map(|(name, count)| {
@@ -288,20 +388,9 @@ fn print_json_diff(project: &str, diff: &DiffResult) {
             "untested": diff.untested.iter().map(|(name, count)| {
                 json!({"pack": name, "node_count": count})
             }).collect::<Vec<_>>(),
-        }
+        },
+        "node_progress": node_progress,
     });
 
     println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
 }
-
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
-        }
-    }
-
-    anyhow::bail!("Could not find repository root")
-}
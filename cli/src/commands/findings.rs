@@ -0,0 +1,327 @@
+use crate::commands::find_repo_root;
+use crate::generators::FindingsReport;
+use crate::git;
+use crate::models::{Catalog, Checklist, DetailedChecklist, Finding, FindingsFile};
+use crate::validators::{FindingValidator, Severity};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color as TableColor, Table};
+use std::fs;
+use std::path::Path;
+
+pub fn add(
+    project: String,
+    pack: String,
+    node: Option<String>,
+    description: String,
+    round: Option<u32>,
+    force: bool,
+) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let findings_dir = repo_root.join("findings").join(&project);
+
+    // Checklists list display names; accept class names too
+    let catalog = Catalog::for_project(&repo_root.join("checklists").join(&project))?;
+    let node = node.map(|node| catalog.display_name(&node).to_string());
+
+    let mut finding = Finding::new(Some(pack), node, description.trim().to_string());
+    if !force {
+        let (checklist, detailed) = load_checklists(&repo_root, &project)?;
+        check_finding(&finding, &checklist, detailed.as_ref(), &catalog)?;
+        // Use the checklist's spelling of the pack
+        finding.pack = finding
+            .pack
+            .as_deref()
+            .and_then(|p| FindingValidator::find_pack(&checklist, p))
+            .map(str::to_string);
+    }
+
+    let mut files = FindingsFile::load_dir(&findings_dir)?;
+    let mut file = match round {
+        Some(round) => match files.iter().position(|f| f.round == Some(round)) {
+            Some(i) => files.remove(i),
+            None => FindingsFile::new_round(&findings_dir, round),
+        },
+        None => match files.pop() {
+            Some(latest) => latest,
+            None => FindingsFile::new_round(&findings_dir, 1),
+        },
+    };
+
+    let index = file.add(finding);
+    fs::create_dir_all(&findings_dir)?;
+    file.write()?;
+
+    println!(
+        "{} Added {} to {}",
+        "✓".green(),
+        file.id(index).bold(),
+        file.file_path.display()
+    );
+    Ok(())
+}
+
+pub fn list(
+    project: String,
+    open: bool,
+    resolved: bool,
+    pack: Option<String>,
+    round: Option<u32>,
+    json: bool,
+) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let files = load_findings(&repo_root, &project)?;
+
+    let matches: Vec<(String, &FindingsFile, &Finding)> = files
+        .iter()
+        .filter(|file| round.is_none() || file.round == round)
+        .flat_map(|file| {
+            file.findings
+                .iter()
+                .enumerate()
+                .map(move |(i, finding)| (file.id(i), file, finding))
+        })
+        .filter(|(_, _, f)| !open || f.is_open())
+        .filter(|(_, _, f)| !resolved || f.resolved)
+        .filter(|(_, _, f)| match (&pack, &f.pack) {
+            (Some(wanted), Some(pack)) => wanted.eq_ignore_ascii_case(pack),
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .collect();
+
+    if json {
+        let output: Vec<_> = matches
+            .iter()
+            .map(|(id, file, finding)| {
+                let mut value = serde_json::to_value(finding).unwrap_or_default();
+                if let Some(object) = value.as_object_mut() {
+                    object.insert("id".to_string(), id.clone().into());
+                    object.insert("round".to_string(), file.round.into());
+                    object.insert(
+                        "file".to_string(),
+                        file.file_path.display().to_string().into(),
+                    );
+                }
+                value
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        println!("No findings match");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(
+        ["ID", "Pack", "Finding", "Status", "Responder", "PRs"]
+            .iter()
+            .map(|h| {
+                Cell::new(h)
+                    .add_attribute(Attribute::Bold)
+                    .fg(TableColor::Cyan)
+            }),
+    );
+
+    for (id, _, finding) in &matches {
+        table.add_row(vec![
+            Cell::new(id),
+            Cell::new(finding.pack.as_deref().unwrap_or("-")),
+            Cell::new(truncate(&describe(finding), 60)),
+            if finding.resolved {
+                Cell::new("resolved").fg(TableColor::Green)
+            } else {
+                Cell::new("open").fg(TableColor::Yellow)
+            },
+            Cell::new(finding.responder.as_deref().unwrap_or("-")),
+            Cell::new(finding.pr_urls.len()),
+        ]);
+    }
+
+    let open_count = matches.iter().filter(|(_, _, f)| f.is_open()).count();
+    println!("{}", table);
+    println!(
+        "\n{} findings: {} open, {} resolved\n",
+        matches.len(),
+        open_count,
+        matches.len() - open_count
+    );
+    Ok(())
+}
+
+pub fn resolve(
+    project: String,
+    id: String,
+    note: String,
+    pr: Option<String>,
+    by: Option<String>,
+) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let mut files = load_findings(&repo_root, &project)?;
+
+    let Some((file, index)) = files.iter_mut().find_map(|file| {
+        let index = (0..file.findings.len()).find(|&i| file.id(i) == id)?;
+        Some((file, index))
+    }) else {
+        anyhow::bail!(
+            "Finding '{}' not found (run `comfy-qa findings list {}`)",
+            id,
+            project
+        );
+    };
+
+    let responder = by
+        .or_else(git::get_user_name)
+        .context("Pass --by <name> (no git user.name configured)")?;
+
+    if file.findings[index].resolved {
+        println!(
+            "{} {} was already resolved, adding the note",
+            "!".yellow(),
+            id
+        );
+    }
+    file.resolve(index, &responder, &note, pr.as_deref());
+    let summary = describe(&file.findings[index]);
+    file.write()?;
+
+    println!("{} Resolved {}: {}", "✓".green(), id.bold(), summary);
+    Ok(())
+}
+
+pub fn render(project: String, round: Option<u32>, write: bool) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let files = load_findings(&repo_root, &project)?;
+    let checklists = load_checklists(&repo_root, &project).ok();
+    let catalog = Catalog::for_project(&repo_root.join("checklists").join(&project))?;
+
+    for file in files
+        .iter()
+        .filter(|file| round.is_none() || file.round == round)
+    {
+        if let Some((checklist, detailed)) = &checklists {
+            for (i, finding) in file.findings.iter().enumerate() {
+                let results =
+                    FindingValidator::validate(finding, checklist, detailed.as_ref(), &catalog);
+                for result in results {
+                    eprintln!("{} {}: {}", "⚠".yellow(), file.id(i), result.message);
+                }
+            }
+        }
+
+        let markdown = file.to_markdown();
+        if write {
+            let current = fs::read_to_string(&file.file_path).unwrap_or_default();
+            if current == markdown {
+                println!("   {} (unchanged)", file.file_path.display());
+            } else {
+                fs::write(&file.file_path, &markdown)
+                    .with_context(|| format!("Failed to write {}", file.file_path.display()))?;
+                println!("{} {}", "✓".green(), file.file_path.display());
+            }
+        } else {
+            print!("{}", markdown);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn report(project: String, format: String, round: Option<u32>) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let files: Vec<_> = load_findings(&repo_root, &project)?
+        .into_iter()
+        .filter(|file| round.is_none() || file.round == round)
+        .collect();
+    let report = FindingsReport::new(&project, &files);
+
+    match format.as_str() {
+        "markdown" | "md" => print!("{}", report.generate_markdown()),
+        "html" => {
+            let output_path = repo_root
+                .join("findings")
+                .join(&project)
+                .join("report.html");
+            fs::write(&output_path, report.generate_html())?;
+            println!("✅ Generated HTML report: {}", output_path.display());
+        }
+        _ => println!("{}", report.generate_text()),
+    }
+    Ok(())
+}
+
+fn load_findings(repo_root: &Path, project: &str) -> Result<Vec<FindingsFile>> {
+    let findings_dir = repo_root.join("findings").join(project);
+    if !findings_dir.exists() {
+        anyhow::bail!(
+            "No findings for '{}' in {}",
+            project,
+            findings_dir.display()
+        );
+    }
+    FindingsFile::load_dir(&findings_dir)
+}
+
+fn load_checklists(
+    repo_root: &Path,
+    project: &str,
+) -> Result<(Checklist, Option<DetailedChecklist>)> {
+    let project_dir = repo_root.join("checklists").join(project);
+    if !project_dir.exists() {
+        anyhow::bail!(
+            "Project '{}' not found (use --force to skip checklist validation)",
+            project
+        );
+    }
+
+    let checklist = Checklist::from_file(project_dir.join("checklist.md"))
+        .with_context(|| format!("Failed to load checklist for '{}'", project))?;
+    let detailed_path = project_dir.join("checklist-detailed.md");
+    let detailed = if detailed_path.exists() {
+        Some(DetailedChecklist::from_file(&detailed_path)?)
+    } else {
+        None
+    };
+    Ok((checklist, detailed))
+}
+
+fn check_finding(
+    finding: &Finding,
+    checklist: &Checklist,
+    detailed: Option<&DetailedChecklist>,
+    catalog: &Catalog,
+) -> Result<()> {
+    let results = FindingValidator::validate(finding, checklist, detailed, catalog);
+    for result in results.iter().filter(|r| r.severity == Severity::Warning) {
+        result.print();
+    }
+
+    let errors: Vec<_> = results
+        .iter()
+        .filter(|r| r.severity == Severity::Error)
+        .map(|r| r.message.as_str())
+        .collect();
+    if !errors.is_empty() {
+        anyhow::bail!("{} (use --force to add anyway)", errors.join("; "));
+    }
+    Ok(())
+}
+
+fn describe(finding: &Finding) -> String {
+    match &finding.node {
+        Some(node) => format!("{}: {}", node, finding.description),
+        None => finding.description.clone(),
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max - 1).collect::<String>())
+    }
+}
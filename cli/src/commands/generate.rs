@@ -29,13 +29,13 @@ pub fn run(search_query: Option<String>) -> Result<()> {
     );
     println!();
 
-    // Load all workflows and find matching nodes
-    let workflows = Workflow::load_all(&workflows_dir)?;
+    // Search every discovered workflow, whatever its kind
+    let workflows = Workflow::discover(&workflows_dir)?;
     let mut matching_nodes = Vec::new();
     let query_lower = query.to_lowercase();
 
-    for workflow in workflows.values() {
-        for node in &workflow.nodes {
+    for workflow in &workflows {
+        for node in workflow.nodes() {
             if node.node_type.to_lowercase().contains(&query_lower)
                 && !matching_nodes.contains(&node.node_type)
             {
@@ -179,7 +179,9 @@ fn show_load_instructions(file_path: &PathBuf) -> Result<()> {
     println!();
     println!(
         "{}",
-        style("How to Load This Workflow in Hanzo Studio:").bold().cyan()
+        style("How to Load This Workflow in Hanzo Studio:")
+            .bold()
+            .cyan()
     );
     println!();
     println!("{}", style("Method 1: Copy-Paste (Easiest)").bold());
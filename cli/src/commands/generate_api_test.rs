@@ -1,7 +1,9 @@
 #![allow(clippy::ptr_arg)]
 #![allow(clippy::format_in_format_args)]
 
-use crate::models::{Checklist, Workflow};
+use crate::commands::convert::convert_executable_workflows;
+use crate::generators::NodePromptGenerator;
+use crate::models::{ApiPrompt, Checklist, ObjectInfo, WorkflowScope};
 use anyhow::Result;
 use console::style;
 use dialoguer::{Input, MultiSelect, Select};
@@ -61,12 +63,26 @@ pub fn run(project: Option<String>) -> Result<()> {
 
     let selected_pack = &checklist.packs[pack_selection];
 
+    // Node definitions let each node get a prompt that passes validation
+    let object_info_path = project_dir.join(ObjectInfo::PROJECT_FILE);
+    let object_info = if object_info_path.exists() {
+        Some(ObjectInfo::from_file(&object_info_path)?)
+    } else {
+        None
+    };
+
     // Get workflow file to extract node names
-    let workflows_dir = repo_root.join("workflows");
-    let workflows = Workflow::load_all(workflows_dir)?;
+    let scope = WorkflowScope::for_project(&repo_root, &project_name)?;
+    let workflows = scope.load_all()?;
+    let pack_definitions = object_info
+        .as_ref()
+        .and_then(|info| info.by_pack().remove(&selected_pack.name))
+        .map(|nodes| nodes.into_iter().map(str::to_string).collect::<Vec<_>>());
 
     let node_types = if let Some(workflow) = workflows.get(&selected_pack.name) {
         workflow.get_unique_node_types()
+    } else if let Some(nodes) = pack_definitions {
+        nodes
     } else {
         println!();
         println!(
@@ -94,6 +110,33 @@ pub fn run(project: Option<String>) -> Result<()> {
         return Ok(());
     }
 
+    let node_tests = match &object_info {
+        Some(object_info) => node_test_prompts(object_info, &selected_nodes),
+        None => {
+            println!(
+                "{} No {} for this project; node tests will need their inputs filled in",
+                style("⚠").yellow(),
+                ObjectInfo::PROJECT_FILE
+            );
+            println!(
+                "  Run: comfy-qa catalog import <object_info.json> {}",
+                project_name
+            );
+            selected_nodes.iter().map(|n| (n.clone(), None)).collect()
+        }
+    };
+
+    // Executable workflows using this pack, converted with the project's
+    // object_info.json snapshot
+    let workflow_prompts = convert_executable_workflows(&project_dir, &scope, &selected_pack.name)?;
+    if !workflow_prompts.is_empty() {
+        println!(
+            "{} Including {} executable workflow(s) as full-prompt tests",
+            style("✓").green(),
+            workflow_prompts.len()
+        );
+    }
+
     // Get server URL
     println!();
     let server_url = Input::<String>::new()
@@ -127,8 +170,12 @@ pub fn run(project: Option<String>) -> Result<()> {
     let output_path = output_dir.join(filename);
 
     // Generate Python script
-    let script_content =
-        generate_api_test_script(&selected_pack.name, &selected_nodes, &server_url);
+    let script_content = generate_api_test_script(
+        &selected_pack.name,
+        &node_tests,
+        &workflow_prompts,
+        &server_url,
+    );
 
     fs::write(&output_path, script_content)?;
 
@@ -145,7 +192,40 @@ pub fn run(project: Option<String>) -> Result<()> {
     Ok(())
 }
 
-fn generate_api_test_script(pack_name: &str, node_types: &[String], server_url: &str) -> String {
+fn node_test_prompts(
+    object_info: &ObjectInfo,
+    node_types: &[String],
+) -> Vec<(String, Option<ApiPrompt>)> {
+    let generator = NodePromptGenerator::new(object_info);
+    node_types
+        .iter()
+        .map(|node_type| match generator.generate(node_type) {
+            Some(conversion) => {
+                for warning in &conversion.warnings {
+                    eprintln!("{} {}", style("⚠").yellow(), warning);
+                }
+                (node_type.clone(), Some(conversion.prompt))
+            }
+            None => {
+                println!(
+                    "{} {} is not in {}; its test needs inputs filled in",
+                    style("⚠").yellow(),
+                    node_type,
+                    ObjectInfo::PROJECT_FILE
+                );
+                (node_type.clone(), None)
+            }
+        })
+        .collect()
+}
+
+/// Nodes with no prompt get a placeholder to fill in by hand.
+fn generate_api_test_script(
+    pack_name: &str,
+    node_tests: &[(String, Option<ApiPrompt>)],
+    workflow_prompts: &[(String, ApiPrompt)],
+    server_url: &str,
+) -> String {
     let mut script = format!(
         r#"#!/usr/bin/env python3
 """
@@ -184,13 +264,16 @@ SERVER_URL = "{}"
     );
 
     // Generate test functions for each node
-    for node_type in node_types {
+    for (node_type, prompt) in node_tests {
         let test_fn_name = format!("test_{}", node_type.to_lowercase().replace([' ', '-'], "_"));
-        script.push_str(&format!(
-            r#"
-def {}():
-    """Test {} functionality"""
-    prompt = {{
+        let prompt_code = match prompt {
+            Some(prompt) => format!(
+                "prompt = json.loads({})",
+                serde_json::to_string(&serde_json::to_string(prompt).unwrap_or_default())
+                    .unwrap_or_default()
+            ),
+            None => format!(
+                r#"prompt = {{
         "1": {{
             "class_type": "{}",
             "inputs": {{
@@ -198,7 +281,15 @@ def {}():
                 # Check node definition for required inputs
             }}
         }}
-    }}
+    }}"#,
+                node_type
+            ),
+        };
+        script.push_str(&format!(
+            r#"
+def {}():
+    """Test {} functionality"""
+    {}
 
     try:
         response = requests.post(f"{{SERVER_URL}}/prompt", json={{"prompt": prompt}})
@@ -223,7 +314,42 @@ def {}():
         return False
 
 "#,
-            test_fn_name, node_type, node_type, node_type, node_type, node_type, node_type
+            test_fn_name, node_type, prompt_code, node_type, node_type, node_type, node_type
+        ));
+    }
+
+    // Generate test functions for each converted workflow
+    for (name, prompt) in workflow_prompts {
+        let prompt_json = serde_json::to_string(prompt).unwrap_or_default();
+        script.push_str(&format!(
+            r#"
+def {}():
+    """Run workflow {}"""
+    prompt = json.loads({})
+
+    try:
+        response = requests.post(f"{{SERVER_URL}}/prompt", json={{"prompt": prompt}})
+
+        if response.status_code != 200:
+            print(f"✗ {} failed: {{response.status_code}}")
+            print(f"  Response: {{response.text}}")
+            return False
+
+        prompt_id = response.json().get("prompt_id")
+        print(f"✓ {} queued (prompt_id: {{prompt_id}})")
+        return True
+
+    except Exception as e:
+        print(f"✗ {} failed with exception: {{e}}")
+        return False
+
+"#,
+            workflow_test_name(name),
+            name,
+            serde_json::to_string(&prompt_json).unwrap_or_default(),
+            name,
+            name,
+            name
         ));
     }
 
@@ -232,13 +358,16 @@ def {}():
     script.push_str(&format!(
         "    print(\"Testing {} - {} nodes\\n\")\n\n",
         pack_name,
-        node_types.len()
+        node_tests.len()
     ));
     script.push_str("    tests = [\n");
-    for node_type in node_types {
+    for (node_type, _) in node_tests {
         let test_fn_name = format!("test_{}", node_type.to_lowercase().replace([' ', '-'], "_"));
         script.push_str(&format!("        {},\n", test_fn_name));
     }
+    for (name, _) in workflow_prompts {
+        script.push_str(&format!("        {},\n", workflow_test_name(name)));
+    }
     script.push_str("    ]\n\n");
     script.push_str("    passed = sum(1 for test in tests if test())\n");
     script.push_str("    total = len(tests)\n\n");
@@ -255,6 +384,15 @@ def {}():
     script
 }
 
+fn workflow_test_name(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("test_workflow_{}", slug)
+}
+
 fn show_instructions(file_path: &PathBuf, pack_name: &str) -> Result<()> {
     println!("{}", style("═".repeat(70)).cyan());
     println!();
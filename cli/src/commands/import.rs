@@ -1,12 +1,16 @@
-use crate::models::{Environment, Export, Metadata, PackMetadata};
+use crate::commands::find_repo_root;
+use crate::models::{
+    Catalog, Checklist, ChecklistMerge, DetailedChecklist, Environment, Export, ExportPack,
+    Metadata, PackChange, PackMetadata, DEFAULT_HEADER,
+};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
-pub fn run(export_file: String, project: String) -> Result<()> {
+pub fn run(export_file: String, project: String, merge: bool) -> Result<()> {
     let repo_root = find_repo_root()?;
     let checklists_dir = repo_root.join("checklists");
 
@@ -20,45 +24,29 @@ pub fn run(export_file: String, project: String) -> Result<()> {
         export_file.bold()
     );
 
-    // Create project directory
     let project_dir = checklists_dir.join(&project);
-    fs::create_dir_all(&project_dir)?;
-
-    // Generate metadata.json
-    let mut packs_metadata = HashMap::new();
-
-    for pack in &export.packs {
-        packs_metadata.insert(
-            pack.name.clone(),
-            PackMetadata {
-                node_count: pack.node_count,
-                tested: false,
-                workflow_file: Some(format!("workflows/all-nodes-{}.json", pack.name)),
-                notes: None,
-                issues: None,
-            },
+    if merge {
+        return merge_into_project(&export, &project, &project_dir);
+    }
+    if project_dir.join("checklist.md").exists() {
+        println!(
+            "   {} Replacing the existing checklists (use {} to keep results)",
+            "!".yellow(),
+            "--merge".cyan()
         );
     }
 
-    let metadata = Metadata {
-        project_name: project.clone(),
-        created_at: Utc::now(),
-        last_updated: None,
-        environment: Environment {
-            env_type: "unknown".to_string(),
-            url: export.environment.url.clone(),
-            hanzo_studio_version: export.environment.hanzo_studio_version.clone(),
-            frontend_version: None,
-        },
-        packs: packs_metadata,
-        stats: None,
-    };
+    // Create project directory
+    fs::create_dir_all(&project_dir)?;
 
+    let metadata = new_metadata(&export, &project);
     let metadata_path = project_dir.join("metadata.json");
     metadata.to_file(metadata_path)?;
+    let catalog = Catalog::from_export(&export);
+    catalog.to_file(Catalog::path(&project_dir))?;
 
     // Generate checklists
-    let mut checklist_lines = vec!["# Node Pack QA Checklist".to_string(), String::new()];
+    let mut checklist_lines = vec![DEFAULT_HEADER.to_string(), String::new()];
 
     let mut sorted_packs = export.packs.clone();
     sorted_packs.sort_by(|a, b| a.name.cmp(&b.name));
@@ -71,20 +59,14 @@ pub fn run(export_file: String, project: String) -> Result<()> {
     fs::write(project_dir.join("checklist.md"), checklist_md)?;
 
     // Generate detailed checklist
-    let mut detailed_lines = vec!["# Node Pack QA Checklist".to_string(), String::new()];
+    let mut detailed_lines = vec![DEFAULT_HEADER.to_string(), String::new()];
 
     for pack in &sorted_packs {
         detailed_lines.push(format!("## {}", pack.name));
         detailed_lines.push(String::new());
 
         for node in &pack.nodes {
-            let name = node.display_name.as_ref().unwrap_or(&node.name);
-            let deprecated = if node.deprecated.unwrap_or(false) {
-                " ~~DEPRECATED~~"
-            } else {
-                ""
-            };
-            detailed_lines.push(format!("- [ ] {}{}", name, deprecated));
+            detailed_lines.push(format!("- [ ] {}", catalog.checklist_name(&node.name)));
         }
 
         detailed_lines.push(String::new());
@@ -98,6 +80,7 @@ pub fn run(export_file: String, project: String) -> Result<()> {
 
     println!("✅ Created project: {}", project.bold());
     println!("   📄 {}", project_dir.join("metadata.json").display());
+    println!("   📄 {}", Catalog::path(&project_dir).display());
     println!("   📄 {}", project_dir.join("checklist.md").display());
     println!(
         "   📄 {}",
@@ -112,14 +95,154 @@ pub fn run(export_file: String, project: String) -> Result<()> {
     Ok(())
 }
 
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
+/// Re-import into an existing project, keeping results, notes and issues.
+fn merge_into_project(export: &Export, project: &str, project_dir: &Path) -> Result<()> {
+    let checklist_path = project_dir.join("checklist.md");
+    let detailed_path = project_dir.join("checklist-detailed.md");
+    let metadata_path = project_dir.join("metadata.json");
+
+    if !checklist_path.exists() {
+        anyhow::bail!(
+            "Project '{}' has no checklist to merge into (import without --merge)",
+            project
+        );
+    }
 
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
+    let checklist = Checklist::from_file(&checklist_path)?;
+    let detailed = if detailed_path.exists() {
+        Some(
+            fs::read_to_string(&detailed_path)
+                .with_context(|| format!("Failed to read {}", detailed_path.display()))?,
+        )
+    } else {
+        None
+    };
+    let existing_metadata = if metadata_path.exists() {
+        Some(Metadata::from_file(&metadata_path)?)
+    } else {
+        None
+    };
+
+    let packs: HashMap<String, ExportPack> = export
+        .packs
+        .iter()
+        .map(|pack| (pack.name.clone(), pack.clone()))
+        .collect();
+
+    let mut merge = ChecklistMerge::reconcile(&checklist, &packs, existing_metadata.as_ref());
+    merge.mark_removed = true;
+    merge.catalog = Catalog::from_export(export);
+    let node_changes = detailed
+        .as_ref()
+        .map(|d| merge.node_changes(&DetailedChecklist::parse(d), &packs))
+        .unwrap_or_default();
+
+    fs::write(&checklist_path, checklist.document.render(&merge.packs))
+        .with_context(|| format!("Failed to write {}", checklist_path.display()))?;
+    fs::write(
+        &detailed_path,
+        merge.render_detailed(detailed.as_deref(), &packs),
+    )
+    .with_context(|| format!("Failed to write {}", detailed_path.display()))?;
+
+    let mut metadata = existing_metadata.unwrap_or_else(|| new_metadata(export, project));
+    metadata.environment.url = export.environment.url.clone();
+    if export.environment.hanzo_studio_version.is_some() {
+        metadata.environment.hanzo_studio_version = export.environment.hanzo_studio_version.clone();
+    }
+    if export.environment.frontend_version.is_some() {
+        metadata.environment.frontend_version = export.environment.frontend_version.clone();
+    }
+    merge.apply_to_metadata(&mut metadata);
+    metadata
+        .to_file(&metadata_path)
+        .with_context(|| format!("Failed to write {}", metadata_path.display()))?;
+
+    let catalog_path = Catalog::path(project_dir);
+    let mut catalog = Catalog::for_project(project_dir)?;
+    catalog.update(Catalog::from_export(export));
+    catalog.to_file(&catalog_path)?;
+
+    println!();
+    if merge.is_unchanged() && node_changes.is_empty() {
+        println!("Packs and nodes already match the export");
+    }
+    for sync in &merge.changes {
+        match &sync.change {
+            PackChange::Unchanged => {}
+            PackChange::CountChanged { from, to } => {
+                println!("  {} {}: {} → {} nodes", "~".yellow(), sync.name, from, to)
+            }
+            PackChange::Added {
+                restored: Some(outcome),
+            } => println!(
+                "  {} {} (new, {} restored from metadata)",
+                "+".green(),
+                sync.name,
+                outcome
+            ),
+            PackChange::Added { restored: None } => {
+                println!("  {} {} (new)", "+".green(), sync.name)
+            }
+            PackChange::Missing => println!(
+                "  {} {}: not in the export, kept with its nodes marked removed",
+                "-".red(),
+                sync.name
+            ),
         }
     }
+    for changes in &node_changes {
+        println!("  {} nodes:", changes.pack.bold());
+        for node in &changes.added {
+            println!("      {} {}", "+".green(), node);
+        }
+        for node in &changes.removed {
+            println!("      {} {} (marked removed)", "-".red(), node);
+        }
+    }
+    for conflict in &merge.conflicts {
+        println!("  {} {}", "!".yellow(), conflict);
+    }
+
+    println!("\n✅ Merged export into {}", project.bold());
+    println!("   📄 Updated: {}", checklist_path.display());
+    println!("   📄 Updated: {}", detailed_path.display());
+    println!("   📄 Updated: {}", metadata_path.display());
+    println!("   📄 Updated: {}", catalog_path.display());
+
+    Ok(())
+}
 
-    anyhow::bail!("Could not find repository root")
+fn new_metadata(export: &Export, project: &str) -> Metadata {
+    let mut packs_metadata = HashMap::new();
+    for pack in &export.packs {
+        packs_metadata.insert(
+            pack.name.clone(),
+            PackMetadata {
+                node_count: pack.node_count,
+                tested: false,
+                outcome: None,
+                api_outcome: None,
+                workflow_file: Some(format!("workflows/all-nodes-{}.json", pack.name)),
+                notes: None,
+                issues: None,
+            },
+        );
+    }
+
+    Metadata {
+        schema_version: Metadata::current_schema_version(),
+        project_name: project.to_string(),
+        created_at: Utc::now(),
+        last_updated: None,
+        environment: Environment {
+            env_type: "unknown".to_string(),
+            url: export.environment.url.clone(),
+            hanzo_studio_version: export.environment.hanzo_studio_version.clone(),
+            frontend_version: export.environment.frontend_version.clone(),
+        },
+        packs: packs_metadata,
+        stats: None,
+        workflow_roots: Vec::new(),
+    }
 }
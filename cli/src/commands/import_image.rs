@@ -0,0 +1,90 @@
+use crate::commands::find_repo_root;
+use crate::models::{Catalog, Workflow, WorkflowDiscovery, WorkflowKind};
+use crate::parsers::ImageMetadataParser;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn run(image_file: String, pack: Option<String>) -> Result<()> {
+    let repo_root = find_repo_root()?;
+    let workflows_dir = repo_root.join("workflows");
+    let image_path = PathBuf::from(&image_file);
+
+    let embedded = ImageMetadataParser::from_file(&image_path)?;
+
+    // The UI graph has layout and widget state; fall back to the API prompt
+    let (value, format) = match (embedded.workflow, embedded.prompt) {
+        (Some(workflow), _) => (workflow, "workflow"),
+        (None, Some(prompt)) => (prompt, "API prompt"),
+        (None, None) => anyhow::bail!("No workflow or prompt embedded in {}", image_file),
+    };
+
+    let workflow = Workflow::from_value(&image_path, value.clone())
+        .with_context(|| format!("Embedded {} is not valid", format))?;
+
+    let catalog = Catalog::default().with_workflows(Workflow::load_all(&workflows_dir)?.values());
+    let attribution = catalog.attribute(&workflow);
+
+    let pack_name = pack
+        .or_else(|| attribution.primary_pack().map(str::to_string))
+        .unwrap_or_else(|| {
+            image_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("workflow")
+                .to_string()
+        });
+
+    let discovery = WorkflowDiscovery::load(&workflows_dir)?;
+    let target_dir = workflows_dir.join(
+        discovery
+            .folder_for(WorkflowKind::Executable)
+            .unwrap_or("executable"),
+    );
+    fs::create_dir_all(&target_dir)?;
+
+    let output_path = next_free_path(&target_dir, &pack_name);
+    fs::write(&output_path, serde_json::to_string_pretty(&value)?)
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
+
+    // Confirm discovery picks the file up as an executable workflow
+    let indexed = Workflow::load_kind(&workflows_dir, WorkflowKind::Executable)?
+        .into_iter()
+        .any(|w| w.file_path == output_path);
+
+    println!("{} Imported {} from {}", "✓".green(), format, image_file);
+    println!("  File: {}", output_path.display().to_string().yellow());
+    println!("  Nodes: {}", workflow.node_count);
+    if !attribution.pack_counts.is_empty() {
+        let packs: Vec<_> = attribution
+            .pack_counts
+            .iter()
+            .map(|(pack, count)| format!("{} ({})", pack, count))
+            .collect();
+        println!("  Packs: {}", packs.join(", "));
+    }
+    if !indexed {
+        println!(
+            "{} {} is outside the executable workflow folders (see workflows/discovery.json)",
+            "⚠".yellow(),
+            output_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// `<pack>.json`, or `<pack> (n).json` if taken, matching how browsers name
+/// repeated downloads.
+fn next_free_path(dir: &Path, pack_name: &str) -> PathBuf {
+    let first = dir.join(format!("{}.json", pack_name));
+    if !first.exists() {
+        return first;
+    }
+
+    (1..)
+        .map(|n| dir.join(format!("{} ({}).json", pack_name, n)))
+        .find(|path| !path.exists())
+        .unwrap_or(first)
+}
@@ -1,8 +1,8 @@
+use crate::commands::find_repo_root;
 use crate::models::Checklist;
 use anyhow::Result;
 use colored::Colorize;
 use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color as TableColor, Table};
-use std::path::PathBuf;
 use walkdir::WalkDir;
 
 pub fn run() -> Result<()> {
@@ -61,7 +61,7 @@ pub fn run() -> Result<()> {
 
         let (pack_count, status) = match Checklist::from_file(&checklist_path) {
             Ok(checklist) => {
-                let tested = checklist.packs.iter().filter(|p| p.tested).count();
+                let tested = checklist.packs.iter().filter(|p| p.tested()).count();
                 let total = checklist.packs.len();
                 let status = if tested == total && total > 0 {
                     format!("✓ Complete ({}/{})", tested, total)
@@ -98,15 +98,3 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
-
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
-        }
-    }
-
-    anyhow::bail!("Could not find repository root")
-}
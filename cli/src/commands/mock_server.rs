@@ -0,0 +1,44 @@
+use crate::mock_server::MockServer;
+use crate::models::ObjectInfo;
+use anyhow::Result;
+use colored::Colorize;
+use std::time::Duration;
+
+pub fn run(
+    object_info_file: Option<String>,
+    port: u16,
+    fail: Vec<String>,
+    delay: u64,
+) -> Result<()> {
+    let object_info = match &object_info_file {
+        Some(path) => ObjectInfo::from_file(path)?,
+        None => MockServer::fixture_object_info(),
+    };
+    let node_count = object_info.nodes.len();
+
+    let server = MockServer::bind(
+        &format!("127.0.0.1:{}", port),
+        object_info,
+        Duration::from_millis(delay),
+    )?;
+    for spec in &fail {
+        let (class_type, message) = spec
+            .split_once('=')
+            .unwrap_or((spec.as_str(), "Mock failure"));
+        server.fail_node(class_type, message);
+    }
+
+    println!("🧪 Mock Hanzo Studio server on {}", server.url().cyan());
+    println!(
+        "   {} nodes from {}",
+        node_count,
+        object_info_file.as_deref().unwrap_or("the bundled fixture")
+    );
+    if !fail.is_empty() {
+        println!("   Failing when executed: {}", fail.join(", ").yellow());
+    }
+    println!("   Press Ctrl+C to stop");
+
+    server.wait();
+    Ok(())
+}
@@ -1,11 +1,36 @@
+pub mod api_test;
 pub mod app;
+pub mod catalog;
 pub mod check;
+pub mod convert;
 pub mod diff;
+pub mod findings;
 pub mod generate;
 pub mod generate_api_test;
 pub mod import;
+pub mod import_image;
 pub mod list;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod new_project;
+pub mod round;
 pub mod status;
 pub mod sync;
 pub mod validate;
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// The nearest ancestor of the working directory with `.git` or
+/// `checklists/`.
+pub fn find_repo_root() -> Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+
+    for ancestor in current_dir.ancestors() {
+        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
+            return Ok(ancestor.to_path_buf());
+        }
+    }
+
+    anyhow::bail!("Could not find repository root (looking for .git or checklists/ directory)")
+}
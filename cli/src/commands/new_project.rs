@@ -1,8 +1,9 @@
+use crate::commands::find_repo_root;
+use crate::models::{Metadata, DEFAULT_HEADER};
 use crate::validators::NamingValidator;
 use anyhow::Result;
 use colored::Colorize;
 use std::fs;
-use std::path::PathBuf;
 
 pub fn run(project_name: String) -> Result<()> {
     if !NamingValidator::is_valid_project_name(&project_name) {
@@ -41,7 +42,7 @@ pub fn run(project_name: String) -> Result<()> {
         // Create empty checklist
         fs::write(
             project_dir.join("checklist.md"),
-            "# Node Pack QA Checklist\n\n",
+            format!("{}\n\n", DEFAULT_HEADER),
         )?;
     }
 
@@ -53,13 +54,14 @@ pub fn run(project_name: String) -> Result<()> {
     } else {
         fs::write(
             project_dir.join("checklist-detailed.md"),
-            "# Node Pack QA Checklist\n\n",
+            format!("{}\n\n", DEFAULT_HEADER),
         )?;
     }
 
     // Create placeholder metadata.json
     let metadata_json = format!(
         r#"{{
+  "schema_version": {},
   "project_name": "{}",
   "created_at": "{}",
   "environment": {{
@@ -69,6 +71,7 @@ pub fn run(project_name: String) -> Result<()> {
   "packs": {{}}
 }}
 "#,
+        Metadata::current_schema_version(),
         project_name,
         chrono::Utc::now().to_rfc3339()
     );
@@ -90,15 +93,3 @@ pub fn run(project_name: String) -> Result<()> {
 
     Ok(())
 }
-
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
-        }
-    }
-
-    anyhow::bail!("Could not find repository root")
-}
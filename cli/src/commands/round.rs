@@ -0,0 +1,229 @@
+use crate::commands::find_repo_root;
+use crate::models::{
+    FindingChange, FindingsFile, Round, RoundComparison, RoundSnapshot, StatusChange, TestDimension,
+};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+pub fn start(project: String, round: Option<u32>) -> Result<()> {
+    let (project_dir, findings_dir) = project_dirs(&project)?;
+    let rounds = Round::list(&project_dir)?;
+
+    if let Some(open) = rounds.iter().find(|r| r.is_open()) {
+        anyhow::bail!(
+            "Round {} is still open (close it with `comfy-qa round close {}`)",
+            open.round,
+            project
+        );
+    }
+
+    // Continue the numbering of rounds and findings files kept before rounds
+    // were tracked
+    let findings = FindingsFile::load_dir(&findings_dir)?;
+    let number = round.unwrap_or_else(|| {
+        rounds
+            .iter()
+            .map(|r| r.round)
+            .chain(findings.iter().filter_map(|f| f.round))
+            .max()
+            .unwrap_or(0)
+            + 1
+    });
+    if rounds.iter().any(|r| r.round == number) {
+        anyhow::bail!("Round {} already exists", number);
+    }
+
+    // The findings file first, so a failed write can't leave an open round
+    // without one
+    if !findings.iter().any(|f| f.round == Some(number)) {
+        std::fs::create_dir_all(&findings_dir)?;
+        FindingsFile::new_round(&findings_dir, number).write()?;
+    }
+    let round = Round::start(&project_dir, &findings_dir, number)?;
+
+    println!("✅ Started round {} for {}", number, project.bold());
+    println!("   📸 Snapshot: {}", round.start_dir().display());
+    println!(
+        "   📝 New findings go to {}",
+        findings_dir
+            .join(format!("findings - round{}.md", number))
+            .display()
+    );
+    Ok(())
+}
+
+pub fn close(project: String) -> Result<()> {
+    let (project_dir, findings_dir) = project_dirs(&project)?;
+    let Some(mut round) = Round::list(&project_dir)?.into_iter().find(|r| r.is_open()) else {
+        anyhow::bail!("No open round for '{}'", project);
+    };
+
+    round.close(&project_dir, &findings_dir)?;
+
+    let snapshot = RoundSnapshot::load_dir(&round.results_dir())?;
+    let findings: Vec<_> = snapshot
+        .findings
+        .iter()
+        .filter(|f| f.round == Some(round.round))
+        .flat_map(|f| f.findings.iter())
+        .collect();
+    let open = findings.iter().filter(|f| f.is_open()).count();
+
+    println!("✅ Closed round {} for {}", round.round, project.bold());
+    println!("   📸 Results: {}", round.results_dir().display());
+    println!(
+        "   {} findings this round: {} open, {} resolved",
+        findings.len(),
+        open,
+        findings.len() - open
+    );
+    Ok(())
+}
+
+pub fn compare(project: String, from: Option<u32>, to: Option<u32>, json: bool) -> Result<()> {
+    let (project_dir, findings_dir) = project_dirs(&project)?;
+    let rounds = Round::list(&project_dir)?;
+    if rounds.is_empty() {
+        anyhow::bail!(
+            "No rounds for '{}' (start one with `comfy-qa round start {}`)",
+            project,
+            project
+        );
+    }
+
+    let find = |number: u32| {
+        rounds
+            .iter()
+            .find(|r| r.round == number)
+            .ok_or_else(|| anyhow::anyhow!("Round {} not found", number))
+    };
+
+    let after = match to {
+        Some(number) => find(number)?,
+        None => rounds.last().unwrap(),
+    };
+    let before = match from {
+        Some(number) => Some(find(number)?),
+        None => rounds.iter().rev().find(|r| r.round < after.round),
+    };
+
+    // With no earlier round, compare against where this one started
+    let (before_label, before_snapshot) = match before {
+        Some(round) => (
+            format!("round {}", round.round),
+            results(round, &project_dir, &findings_dir)?,
+        ),
+        None => (
+            format!("start of round {}", after.round),
+            RoundSnapshot::load_dir(&after.start_dir())?,
+        ),
+    };
+    let after_label = if after.is_open() {
+        format!("round {} (open)", after.round)
+    } else {
+        format!("round {}", after.round)
+    };
+    let after_snapshot = results(after, &project_dir, &findings_dir)?;
+
+    let comparison = RoundComparison::compare(&before_snapshot, &after_snapshot);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&comparison)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n🔁 {}: {} → {}\n",
+        project.bold(),
+        before_label,
+        after_label
+    );
+    if comparison.is_empty() {
+        println!("No changes\n");
+        return Ok(());
+    }
+
+    print_status_changes("Packs", &comparison.packs);
+    print_status_changes("Nodes", &comparison.nodes);
+
+    for (change, label) in [
+        (FindingChange::Fixed, "Fixed findings".green()),
+        (FindingChange::Reopened, "Reopened findings".red()),
+        (FindingChange::New, "New findings".yellow()),
+    ] {
+        let findings: Vec<_> = comparison.findings_with(change).collect();
+        if findings.is_empty() {
+            continue;
+        }
+        println!("{} ({})", label.bold(), findings.len());
+        for finding in findings {
+            println!(
+                "  • [{}] {}",
+                finding.pack.as_deref().unwrap_or("-"),
+                match &finding.node {
+                    Some(node) => format!("{}: {}", node, finding.description),
+                    None => finding.description.clone(),
+                }
+            );
+        }
+        println!();
+    }
+
+    let regressions = comparison.regressions().count()
+        + comparison.findings_with(FindingChange::Reopened).count();
+    if regressions > 0 {
+        println!("{} {} regressions\n", "⚠️".yellow(), regressions);
+    }
+    Ok(())
+}
+
+/// A round's frozen results, or the live files while it is open.
+fn results(round: &Round, project_dir: &Path, findings_dir: &Path) -> Result<RoundSnapshot> {
+    if round.is_open() {
+        RoundSnapshot::load(project_dir, findings_dir)
+    } else {
+        RoundSnapshot::load_dir(&round.results_dir())
+    }
+}
+
+fn print_status_changes(title: &str, changes: &[StatusChange]) {
+    if changes.is_empty() {
+        return;
+    }
+
+    println!("{} ({})", title.bold(), changes.len());
+    for change in changes {
+        let mut name = match &change.node {
+            Some(node) => format!("{} / {}", change.pack, node),
+            None => change.pack.clone(),
+        };
+        match change.dimension {
+            Some(TestDimension::Ui) => name.push_str(" (UI)"),
+            Some(TestDimension::Api) => name.push_str(" (API)"),
+            None => {}
+        }
+        let line = format!(
+            "  {} {} → {} {}",
+            change.from.icon(),
+            change.from,
+            change.to.icon(),
+            change.to
+        );
+        if change.is_regression() {
+            println!("{}{}", line.red(), format!("  {} (regressed)", name).red());
+        } else {
+            println!("{}  {}", line, name);
+        }
+    }
+    println!();
+}
+
+fn project_dirs(project: &str) -> Result<(PathBuf, PathBuf)> {
+    let repo_root = find_repo_root()?;
+    let project_dir = repo_root.join("checklists").join(project);
+    if !project_dir.exists() {
+        anyhow::bail!("Project '{}' not found", project);
+    }
+    Ok((project_dir, repo_root.join("findings").join(project)))
+}
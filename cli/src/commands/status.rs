@@ -1,5 +1,8 @@
+use crate::commands::find_repo_root;
 use crate::generators::ReportGenerator;
-use crate::models::{Checklist, Metadata};
+use crate::models::{
+    Catalog, Checklist, DetailedChecklist, Metadata, TestDimension, WorkflowScope,
+};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color as TableColor, Table};
@@ -12,14 +15,14 @@ pub fn run(project: Option<String>, format: String, _all: bool) -> Result<()> {
     let checklists_dir = repo_root.join("checklists");
 
     if let Some(project_name) = project {
-        show_single_project(&checklists_dir, &project_name, &format)
+        show_single_project(&repo_root, &project_name, &format)
     } else {
-        show_all_projects(&checklists_dir, &format)
+        show_all_projects(&repo_root, &checklists_dir, &format)
     }
 }
 
-fn show_single_project(checklists_dir: &Path, project_name: &str, format: &str) -> Result<()> {
-    let project_dir = checklists_dir.join(project_name);
+fn show_single_project(repo_root: &Path, project_name: &str, format: &str) -> Result<()> {
+    let project_dir = repo_root.join("checklists").join(project_name);
 
     if !project_dir.exists() {
         anyhow::bail!("Project '{}' not found", project_name);
@@ -36,28 +39,42 @@ fn show_single_project(checklists_dir: &Path, project_name: &str, format: &str)
     } else {
         None
     };
+    let detailed = load_detailed(&project_dir);
+    let catalog = load_catalog(repo_root, project_name)?;
 
     match format {
         "json" => {
-            let json_output = generate_json_status(&checklist, metadata.as_ref());
+            let json_output =
+                generate_json_status(&checklist, detailed.as_ref(), metadata.as_ref(), &catalog);
             println!("{}", serde_json::to_string_pretty(&json_output)?);
         }
         "html" => {
-            let html = ReportGenerator::generate_html(&checklist, metadata.as_ref());
+            let html =
+                ReportGenerator::generate_html(&checklist, detailed.as_ref(), metadata.as_ref());
             let output_path = project_dir.join("report.html");
             fs::write(&output_path, html)?;
             println!("✅ Generated HTML report: {}", output_path.display());
         }
         _ => {
-            let report = ReportGenerator::generate_text(&checklist, metadata.as_ref());
+            let report =
+                ReportGenerator::generate_text(&checklist, detailed.as_ref(), metadata.as_ref());
             println!("{}", report);
+            let (deprecated, untested) = deprecated_nodes(detailed.as_ref(), &catalog);
+            if deprecated > 0 {
+                println!(
+                    "{} {} deprecated nodes in the checklist ({} untested)",
+                    "!".yellow(),
+                    deprecated,
+                    untested
+                );
+            }
         }
     }
 
     Ok(())
 }
 
-fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
+fn show_all_projects(repo_root: &Path, checklists_dir: &PathBuf, format: &str) -> Result<()> {
     let mut projects = Vec::new();
 
     for entry in WalkDir::new(checklists_dir)
@@ -97,7 +114,14 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
             };
 
             if let Some(checklist) = checklist {
-                all_statuses.push(generate_json_status(&checklist, metadata.as_ref()));
+                let detailed = load_detailed(&project_dir);
+                let catalog = load_catalog(repo_root, project_name).unwrap_or_default();
+                all_statuses.push(generate_json_status(
+                    &checklist,
+                    detailed.as_ref(),
+                    metadata.as_ref(),
+                    &catalog,
+                ));
             }
         }
 
@@ -120,6 +144,12 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
             Cell::new("Progress")
                 .add_attribute(Attribute::Bold)
                 .fg(TableColor::Cyan),
+            Cell::new("Nodes")
+                .add_attribute(Attribute::Bold)
+                .fg(TableColor::Cyan),
+            Cell::new("UI / API")
+                .add_attribute(Attribute::Bold)
+                .fg(TableColor::Cyan),
             Cell::new("Hanzo Studio Version")
                 .add_attribute(Attribute::Bold)
                 .fg(TableColor::Cyan),
@@ -139,6 +169,8 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
                         Cell::new("-"),
                         Cell::new("-"),
                         Cell::new("-"),
+                        Cell::new("-"),
+                        Cell::new("-"),
                     ]);
                     continue;
                 }
@@ -150,7 +182,7 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
                 None
             };
 
-            let tested = checklist.packs.iter().filter(|p| p.tested).count();
+            let tested = checklist.packs.iter().filter(|p| p.tested()).count();
             let total = checklist.packs.len();
             let percent = if total > 0 {
                 (tested as f64 / total as f64) * 100.0
@@ -167,6 +199,19 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
                 Cell::new(&progress_bar).fg(TableColor::Red)
             };
 
+            let nodes_str = match load_detailed(&project_dir).map(|d| d.progress()) {
+                Some((tested_nodes, total_nodes)) if total_nodes > 0 => {
+                    format!("{}/{}", tested_nodes, total_nodes)
+                }
+                _ => "-".to_string(),
+            };
+
+            let coverage_str = format!(
+                "{} / {}",
+                checklist.dimension_progress(TestDimension::Ui).0,
+                checklist.dimension_progress(TestDimension::Api).0
+            );
+
             let version_str = metadata
                 .as_ref()
                 .and_then(|m| m.environment.hanzo_studio_version.as_deref())
@@ -181,6 +226,8 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
                 }),
                 Cell::new(total.to_string()),
                 progress_cell,
+                Cell::new(nodes_str),
+                Cell::new(coverage_str),
                 Cell::new(version_str).fg(TableColor::DarkGrey),
             ]);
         }
@@ -195,14 +242,51 @@ fn show_all_projects(checklists_dir: &PathBuf, format: &str) -> Result<()> {
     Ok(())
 }
 
-fn generate_json_status(checklist: &Checklist, metadata: Option<&Metadata>) -> serde_json::Value {
-    let tested = checklist.packs.iter().filter(|p| p.tested).count();
+fn load_detailed(project_dir: &Path) -> Option<DetailedChecklist> {
+    DetailedChecklist::from_file(project_dir.join("checklist-detailed.md")).ok()
+}
+
+fn load_catalog(repo_root: &Path, project: &str) -> Result<Catalog> {
+    let workflows = WorkflowScope::for_project(repo_root, project)?.load_all()?;
+    let project_dir = repo_root.join("checklists").join(project);
+    Ok(Catalog::for_project(&project_dir)?.with_workflows(workflows.values()))
+}
+
+/// (deprecated, untested deprecated) nodes of the detailed checklist.
+fn deprecated_nodes(detailed: Option<&DetailedChecklist>, catalog: &Catalog) -> (usize, usize) {
+    let deprecated: Vec<_> = detailed
+        .into_iter()
+        .flat_map(|d| d.packs.values().flatten())
+        .filter(|node| {
+            catalog
+                .resolve(&node.name)
+                .is_some_and(|(_, n)| n.deprecated)
+        })
+        .collect();
+    let untested = deprecated.iter().filter(|node| !node.tested()).count();
+    (deprecated.len(), untested)
+}
+
+fn generate_json_status(
+    checklist: &Checklist,
+    detailed: Option<&DetailedChecklist>,
+    metadata: Option<&Metadata>,
+    catalog: &Catalog,
+) -> serde_json::Value {
+    let tested = checklist.packs.iter().filter(|p| p.tested()).count();
     let total = checklist.packs.len();
     let percent = if total > 0 {
         (tested as f64 / total as f64) * 100.0
     } else {
         0.0
     };
+    let (tested_nodes, total_nodes) = detailed.map(|d| d.progress()).unwrap_or((0, 0));
+    let (deprecated, untested_deprecated) = deprecated_nodes(detailed, catalog);
+    let node_percent = if total_nodes > 0 {
+        (tested_nodes as f64 / total_nodes as f64) * 100.0
+    } else {
+        0.0
+    };
 
     serde_json::json!({
         "project": checklist.project_name,
@@ -210,6 +294,17 @@ fn generate_json_status(checklist: &Checklist, metadata: Option<&Metadata>) -> s
             "total_packs": total,
             "tested_packs": tested,
             "completion_percent": percent,
+            "total_nodes": total_nodes,
+            "tested_nodes": tested_nodes,
+            "node_completion_percent": node_percent,
+            "outcomes": checklist.outcome_counts(),
+            "node_outcomes": detailed.map(|d| d.outcome_counts()),
+            "ui_tested_packs": checklist.dimension_progress(TestDimension::Ui).0,
+            "api_tested_packs": checklist.dimension_progress(TestDimension::Api).0,
+            "ui_tested_nodes": detailed.map(|d| d.dimension_progress(TestDimension::Ui).0),
+            "api_tested_nodes": detailed.map(|d| d.dimension_progress(TestDimension::Api).0),
+            "deprecated_nodes": deprecated,
+            "untested_deprecated_nodes": untested_deprecated,
         },
         "environment": metadata.map(|m| serde_json::json!({
             "type": m.environment.env_type,
@@ -219,7 +314,11 @@ fn generate_json_status(checklist: &Checklist, metadata: Option<&Metadata>) -> s
         "packs": checklist.packs.iter().map(|p| serde_json::json!({
             "name": p.name,
             "node_count": p.node_count,
-            "tested": p.tested,
+            "tested": p.tested(),
+            "outcome": p.outcome,
+            "ui_outcome": p.dimension_outcome(TestDimension::Ui),
+            "api_outcome": p.dimension_outcome(TestDimension::Api),
+            "tested_nodes": detailed.map(|d| d.pack_progress(&p.name).0),
         })).collect::<Vec<_>>()
     })
 }
@@ -234,15 +333,3 @@ fn create_mini_progress_bar(percent: f64) -> String {
         percent
     )
 }
-
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
-        }
-    }
-
-    anyhow::bail!("Could not find repository root")
-}
@@ -1,46 +1,85 @@
-use crate::generators::ChecklistGenerator;
-use crate::models::Workflow;
+use crate::commands::find_repo_root;
+use crate::models::{
+    Catalog, Checklist, ChecklistDocument, ChecklistMerge, DetailedChecklist, Metadata,
+    NodeChanges, PackChange, WorkflowScope,
+};
 use anyhow::{Context, Result};
 use colored::Colorize;
+use similar::{ChangeTag, TextDiff};
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 pub fn run(project: String, dry_run: bool) -> Result<()> {
     let repo_root = find_repo_root()?;
     let checklists_dir = repo_root.join("checklists");
-    let workflows_dir = repo_root.join("workflows");
 
     let project_dir = checklists_dir.join(&project);
     if !project_dir.exists() {
         anyhow::bail!("Project '{}' not found", project);
     }
 
-    let workflows = Workflow::load_all(workflows_dir)?;
+    let scope = WorkflowScope::for_project(&repo_root, &project)?;
+    let workflows = scope.load_all()?;
 
     if workflows.is_empty() {
-        anyhow::bail!("No workflow files found in workflows/");
+        anyhow::bail!(
+            "No workflow files found in {}",
+            scope
+                .roots
+                .iter()
+                .map(|r| r.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
     }
 
-    // Generate new checklists
-    let checklist_md = ChecklistGenerator::generate_from_workflows(&workflows);
-    let checklist_detailed_md = ChecklistGenerator::generate_detailed_from_workflows(&workflows);
-
     let checklist_path = project_dir.join("checklist.md");
     let checklist_detailed_path = project_dir.join("checklist-detailed.md");
+    let metadata_path = project_dir.join("metadata.json");
+
+    let checklist = if checklist_path.exists() {
+        Checklist::from_file(&checklist_path)?
+    } else {
+        Checklist {
+            file_path: checklist_path.clone(),
+            project_name: project.clone(),
+            packs: Vec::new(),
+            document: ChecklistDocument::default(),
+        }
+    };
+    let detailed = if checklist_detailed_path.exists() {
+        Some(
+            fs::read_to_string(&checklist_detailed_path)
+                .with_context(|| format!("Failed to read {}", checklist_detailed_path.display()))?,
+        )
+    } else {
+        None
+    };
+    let mut metadata = if metadata_path.exists() {
+        Some(Metadata::from_file(&metadata_path)?)
+    } else {
+        None
+    };
+
+    let mut merge = ChecklistMerge::reconcile(&checklist, &workflows, metadata.as_ref());
+    merge.mark_removed = true;
+    merge.catalog = Catalog::for_project(&project_dir)?.with_workflows(workflows.values());
+    let node_changes = detailed
+        .as_ref()
+        .map(|d| merge.node_changes(&DetailedChecklist::parse(d), &workflows))
+        .unwrap_or_default();
+    let checklist_md = checklist.document.render(&merge.packs);
+    let checklist_detailed_md = merge.render_detailed(detailed.as_deref(), &workflows);
+
+    print_summary(&merge, &node_changes);
 
     if dry_run {
         println!("🔍 {} - would update:\n", "Dry run".yellow());
-        println!("📄 {}", checklist_path.display());
-        println!("📄 {}\n", checklist_detailed_path.display());
-        println!("Preview of checklist.md:\n{}", "─".repeat(60));
-        println!(
-            "{}",
-            checklist_md.lines().take(20).collect::<Vec<_>>().join("\n")
-        );
-        if checklist_md.lines().count() > 20 {
-            println!("... ({} more lines)", checklist_md.lines().count() - 20);
+        print_file_diff(&checklist_path, &checklist_md);
+        print_file_diff(&checklist_detailed_path, &checklist_detailed_md);
+        if metadata.is_some() {
+            println!("📄 {} (counts and results)\n", metadata_path.display());
         }
-        println!("{}\n", "─".repeat(60));
         println!("💡 Run without {} to apply changes", "--dry-run".cyan());
     } else {
         fs::write(&checklist_path, checklist_md)
@@ -49,6 +88,13 @@ pub fn run(project: String, dry_run: bool) -> Result<()> {
         fs::write(&checklist_detailed_path, checklist_detailed_md)
             .with_context(|| format!("Failed to write {}", checklist_detailed_path.display()))?;
 
+        if let Some(metadata) = &mut metadata {
+            merge.apply_to_metadata(metadata);
+            metadata
+                .to_file(&metadata_path)
+                .with_context(|| format!("Failed to write {}", metadata_path.display()))?;
+        }
+
         println!(
             "✅ Synced {} from {} workflow files",
             project.bold(),
@@ -56,19 +102,106 @@ pub fn run(project: String, dry_run: bool) -> Result<()> {
         );
         println!("   📄 Updated: {}", checklist_path.display());
         println!("   📄 Updated: {}", checklist_detailed_path.display());
+        if metadata.is_some() {
+            println!("   📄 Updated: {}", metadata_path.display());
+        }
     }
 
     Ok(())
 }
 
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
+fn print_summary(merge: &ChecklistMerge, node_changes: &[NodeChanges]) {
+    if merge.is_unchanged() && node_changes.is_empty() {
+        println!("Packs and nodes already match the workflow files\n");
+    }
 
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
+    for sync in &merge.changes {
+        match &sync.change {
+            PackChange::Unchanged => {}
+            PackChange::CountChanged { from, to } => {
+                println!("  {} {}: {} → {} nodes", "~".yellow(), sync.name, from, to)
+            }
+            PackChange::Added {
+                restored: Some(outcome),
+            } => println!(
+                "  {} {} (new, {} restored from metadata)",
+                "+".green(),
+                sync.name,
+                outcome
+            ),
+            PackChange::Added { restored: None } => {
+                println!("  {} {} (new)", "+".green(), sync.name)
+            }
+            PackChange::Missing => println!(
+                "  {} {}: no workflow file, kept with its nodes marked removed",
+                "?".red(),
+                sync.name
+            ),
         }
     }
 
-    anyhow::bail!("Could not find repository root")
+    for changes in node_changes {
+        println!("  {} nodes:", changes.pack.bold());
+        for node in &changes.added {
+            println!("      {} {}", "+".green(), node);
+        }
+        for node in &changes.removed {
+            println!("      {} {} (marked removed)", "-".red(), node);
+        }
+    }
+
+    if merge.missing().next().is_some() {
+        println!(
+            "\n  {} Remove missing packs by hand if they were dropped on purpose",
+            "💡".dimmed()
+        );
+    }
+    for conflict in &merge.conflicts {
+        println!("  {} {}", "!".yellow(), conflict);
+    }
+    println!();
+}
+
+fn print_file_diff(path: &Path, new_contents: &str) {
+    let old_contents = fs::read_to_string(path).unwrap_or_default();
+    let diff = TextDiff::from_lines(old_contents.as_str(), new_contents);
+
+    println!("📄 {}", path.display());
+    if old_contents == new_contents {
+        println!("   (no changes)\n");
+        return;
+    }
+
+    println!("{}", "─".repeat(60));
+    for (i, group) in diff.grouped_ops(1).iter().enumerate() {
+        if i > 0 {
+            println!("{}", "   ...".dimmed());
+        }
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let line = change.value().trim_end_matches(['\r', '\n']);
+                match change.tag() {
+                    ChangeTag::Delete => println!(
+                        "{}",
+                        format!("{:>4}      - {}", change.old_index().unwrap() + 1, line).red()
+                    ),
+                    ChangeTag::Insert => println!(
+                        "{}",
+                        format!("     {:>4} + {}", change.new_index().unwrap() + 1, line).green()
+                    ),
+                    ChangeTag::Equal => println!(
+                        "{}",
+                        format!(
+                            "{:>4} {:>4}   {}",
+                            change.old_index().unwrap() + 1,
+                            change.new_index().unwrap() + 1,
+                            line
+                        )
+                        .dimmed()
+                    ),
+                }
+            }
+        }
+    }
+    println!("{}\n", "─".repeat(60));
 }
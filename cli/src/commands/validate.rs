@@ -1,41 +1,33 @@
 #![allow(clippy::ptr_arg)]
 
-use crate::models::{Checklist, Metadata, Workflow};
-use crate::validators::{Severity, Validator};
+use crate::commands::find_repo_root;
+use crate::models::{Checklist, Metadata, WorkflowKind, WorkflowScope};
+use crate::validators::{Severity, Validator, WorkflowValidator};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use serde_json::json;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::Path;
 use walkdir::WalkDir;
 
 pub fn run(project: Option<String>, json: bool, fix: bool, verbose: bool) -> Result<()> {
     let repo_root = find_repo_root()?;
-    let checklists_dir = repo_root.join("checklists");
-    let workflows_dir = repo_root.join("workflows");
 
     if let Some(project_name) = project {
-        validate_single_project(
-            &checklists_dir,
-            &workflows_dir,
-            &project_name,
-            json,
-            fix,
-            verbose,
-        )
+        validate_single_project(&repo_root, &project_name, json, fix, verbose)
     } else {
-        validate_all_projects(&checklists_dir, &workflows_dir, json, fix, verbose)
+        validate_all_projects(&repo_root, json, fix, verbose)
     }
 }
 
 fn validate_single_project(
-    checklists_dir: &PathBuf,
-    workflows_dir: &PathBuf,
+    repo_root: &Path,
     project_name: &str,
     json: bool,
     fix: bool,
     verbose: bool,
 ) -> Result<()> {
-    let project_dir = checklists_dir.join(project_name);
+    let project_dir = repo_root.join("checklists").join(project_name);
 
     if !project_dir.exists() {
         anyhow::bail!("Project '{}' not found", project_name);
@@ -53,9 +45,12 @@ fn validate_single_project(
         None
     };
 
-    let workflows = Workflow::load_all(workflows_dir)?;
+    let scope = WorkflowScope::for_project(repo_root, project_name)?;
+    let workflows = scope.load_all()?;
+    let executable = scope.load_kind(WorkflowKind::Executable)?;
 
-    let results = Validator::validate_project(&checklist, &workflows, metadata.as_ref());
+    let mut results = Validator::validate_project(&checklist, &workflows, metadata.as_ref());
+    results.extend(WorkflowValidator::validate_files(&executable));
 
     if json {
         print_json_results(project_name, &results);
@@ -76,17 +71,12 @@ fn validate_single_project(
     Ok(())
 }
 
-fn validate_all_projects(
-    checklists_dir: &PathBuf,
-    workflows_dir: &PathBuf,
-    json: bool,
-    _fix: bool,
-    verbose: bool,
-) -> Result<()> {
+fn validate_all_projects(repo_root: &Path, json: bool, _fix: bool, verbose: bool) -> Result<()> {
     let mut all_results = Vec::new();
     let mut projects = Vec::new();
+    let checklists_dir = repo_root.join("checklists");
 
-    for entry in WalkDir::new(checklists_dir)
+    for entry in WalkDir::new(&checklists_dir)
         .max_depth(1)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -103,8 +93,8 @@ fn validate_all_projects(
         }
     }
 
-    let workflows = Workflow::load_all(workflows_dir)?;
-
+    // Projects without their own roots share the executable workflows
+    let mut validated = HashSet::new();
     for project_name in &projects {
         let project_dir = checklists_dir.join(project_name);
         let checklist_path = project_dir.join("checklist.md");
@@ -124,7 +114,16 @@ fn validate_all_projects(
             None
         };
 
-        let results = Validator::validate_project(&checklist, &workflows, metadata.as_ref());
+        let scope = WorkflowScope::for_project(repo_root, project_name)?;
+        let workflows = scope.load_all()?;
+        let executable: Vec<_> = scope
+            .load_kind(WorkflowKind::Executable)?
+            .into_iter()
+            .filter(|w| validated.insert(w.file_path.clone()))
+            .collect();
+
+        let mut results = Validator::validate_project(&checklist, &workflows, metadata.as_ref());
+        results.extend(WorkflowValidator::validate_files(&executable));
         all_results.push((project_name.clone(), results));
     }
 
@@ -256,15 +255,3 @@ fn print_json_results(project_name: &str, results: &[crate::validators::Validati
 
     println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
 }
-
-fn find_repo_root() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join("checklists").exists() {
-            return Ok(ancestor.to_path_buf());
-        }
-    }
-
-    anyhow::bail!("Could not find repository root (looking for .git or checklists/ directory)")
-}
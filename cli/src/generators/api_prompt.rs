@@ -0,0 +1,206 @@
+use crate::models::{
+    ApiInput, ApiNode, ApiNodeMeta, ApiPrompt, NodeInput, ObjectInfo, WidgetValues, WorkflowGraph,
+    WorkflowNode,
+};
+use serde_json::Value;
+
+/// Frontend-only node types that never reach the server.
+const VIRTUAL_NODES: [&str; 4] = ["Reroute", "PrimitiveNode", "Note", "MarkdownNote"];
+
+/// Guards against reroute/bypass chains that loop back on themselves.
+const MAX_RESOLVE_DEPTH: usize = 64;
+
+/// Converts UI workflows to API prompts offline, using a saved
+/// `/object_info` snapshot to name widget values.
+pub struct ApiPromptGenerator<'a> {
+    object_info: &'a ObjectInfo,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Conversion {
+    pub prompt: ApiPrompt,
+    /// Things the server is likely to reject, e.g. unknown node types or
+    /// unconnected required inputs.
+    pub warnings: Vec<String>,
+}
+
+enum Resolved {
+    Link(String, u32),
+    Value(Value),
+    /// Comes from a node the conversion leaves out.
+    Skipped(String),
+    Nothing,
+}
+
+impl<'a> ApiPromptGenerator<'a> {
+    pub fn new(object_info: &'a ObjectInfo) -> Self {
+        Self { object_info }
+    }
+
+    pub fn convert(&self, graph: &WorkflowGraph) -> Conversion {
+        let mut conversion = Conversion::default();
+
+        for node in &graph.nodes {
+            if VIRTUAL_NODES.contains(&node.node_type.as_str()) || !node.is_active() {
+                continue;
+            }
+
+            if let Some(subgraph) = graph.get_subgraph(&node.node_type) {
+                conversion.warnings.push(format!(
+                    "node {} is subgraph '{}'; unpack it in the editor to convert",
+                    node.id, subgraph.name
+                ));
+                continue;
+            }
+
+            let Some(definition) = self.object_info.get(&node.node_type) else {
+                conversion.warnings.push(format!(
+                    "node {} ({}): not in object_info, skipped",
+                    node.id, node.node_type
+                ));
+                continue;
+            };
+
+            let mut api_node = ApiNode::new(&node.node_type);
+            api_node.meta = Some(ApiNodeMeta {
+                title: node.display_title().to_string(),
+            });
+
+            // Widget values, positional or keyed by name
+            match &node.widgets_values {
+                Some(WidgetValues::List(values)) => {
+                    let mut index = 0;
+                    for slot in definition.widget_slots() {
+                        let Some(value) = values.get(index) else {
+                            break;
+                        };
+                        api_node
+                            .inputs
+                            .insert(slot.name.clone(), ApiInput::Value(value.clone()));
+                        index += 1 + slot.extra_widget_values;
+                    }
+                }
+                Some(WidgetValues::Map(values)) => {
+                    for slot in definition.widget_slots() {
+                        if let Some(value) = values.get(&slot.name) {
+                            api_node
+                                .inputs
+                                .insert(slot.name.clone(), ApiInput::Value(value.clone()));
+                        }
+                    }
+                }
+                None => {}
+            }
+
+            // Connected inputs override widget values
+            for input in &node.inputs {
+                let Some(link_id) = input.link else {
+                    continue;
+                };
+                match self.resolve(graph, link_id, 0) {
+                    Resolved::Link(origin, slot) => {
+                        api_node
+                            .inputs
+                            .insert(input.name.clone(), ApiInput::Link(origin, slot));
+                    }
+                    Resolved::Value(value) => {
+                        api_node
+                            .inputs
+                            .insert(input.name.clone(), ApiInput::Value(value));
+                    }
+                    Resolved::Skipped(origin) => {
+                        conversion.warnings.push(format!(
+                            "node {} ({}): input '{}' comes from skipped node {}, dropped",
+                            node.id, node.node_type, input.name, origin
+                        ));
+                        if input.widget.is_none() {
+                            api_node.inputs.remove(&input.name);
+                        }
+                    }
+                    Resolved::Nothing => {
+                        if input.widget.is_none() {
+                            api_node.inputs.remove(&input.name);
+                        }
+                    }
+                }
+            }
+
+            for slot in definition.input_slots() {
+                if slot.required && !api_node.inputs.contains_key(&slot.name) {
+                    conversion.warnings.push(format!(
+                        "node {} ({}): required input '{}' has no value",
+                        node.id, node.node_type, slot.name
+                    ));
+                }
+            }
+
+            conversion
+                .prompt
+                .nodes
+                .insert(node.id.to_string(), api_node);
+        }
+
+        conversion
+    }
+
+    /// Follow a link back to the node that actually produces the value,
+    /// skipping reroutes and bypassed nodes and inlining primitive nodes.
+    fn resolve(&self, graph: &WorkflowGraph, link_id: u64, depth: usize) -> Resolved {
+        if depth > MAX_RESOLVE_DEPTH {
+            return Resolved::Nothing;
+        }
+        let Some(link) = graph.get_link(link_id) else {
+            return Resolved::Nothing;
+        };
+        let Some(origin) = graph.get_node(&link.origin_id) else {
+            return Resolved::Nothing;
+        };
+
+        match origin.node_type.as_str() {
+            "Reroute" => self.follow_input(graph, origin.inputs.first(), depth),
+            "PrimitiveNode" => origin
+                .widget_list()
+                .first()
+                .cloned()
+                .map(Resolved::Value)
+                .unwrap_or(Resolved::Nothing),
+            "Note" | "MarkdownNote" => Resolved::Nothing,
+            _ if origin.is_muted() => Resolved::Nothing,
+            _ if origin.is_bypassed() => {
+                let input = Self::bypass_input(origin, link.origin_slot);
+                self.follow_input(graph, input, depth)
+            }
+            _ if graph.get_subgraph(&origin.node_type).is_some()
+                || self.object_info.get(&origin.node_type).is_none() =>
+            {
+                Resolved::Skipped(origin.id.to_string())
+            }
+            _ => Resolved::Link(origin.id.to_string(), link.origin_slot),
+        }
+    }
+
+    fn follow_input(
+        &self,
+        graph: &WorkflowGraph,
+        input: Option<&NodeInput>,
+        depth: usize,
+    ) -> Resolved {
+        match input.and_then(|i| i.link) {
+            Some(link_id) => self.resolve(graph, link_id, depth + 1),
+            None => Resolved::Nothing,
+        }
+    }
+
+    /// The input a bypassed node passes through to `output_slot`: the same
+    /// slot index if the types match, otherwise the first connected input of
+    /// that type.
+    fn bypass_input(node: &WorkflowNode, output_slot: u32) -> Option<&NodeInput> {
+        let output_type = node.outputs.get(output_slot as usize)?.output_type.as_str();
+        let matches = |i: &&NodeInput| i.link.is_some() && i.input_type == output_type;
+
+        node.inputs
+            .get(output_slot as usize)
+            .filter(|i| matches(i))
+            .or_else(|| node.inputs.iter().find(|i| matches(i)))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::{NodePack, Workflow};
+use crate::models::{NodePack, Workflow, DEFAULT_HEADER};
 use std::collections::HashMap;
 
 pub struct ChecklistGenerator;
@@ -12,7 +12,7 @@ impl ChecklistGenerator {
 
         packs.sort_by(|a, b| a.name.cmp(&b.name));
 
-        let mut lines = vec!["# Node Pack QA Checklist".to_string(), String::new()];
+        let mut lines = vec![DEFAULT_HEADER.to_string(), String::new()];
 
         for pack in packs {
             lines.push(format!("- [ ] {} ({})", pack.name, pack.node_count));
@@ -25,13 +25,13 @@ impl ChecklistGenerator {
         let mut packs: Vec<_> = workflows.values().collect();
         packs.sort_by(|a, b| a.pack_name.cmp(&b.pack_name));
 
-        let mut lines = vec!["# Node Pack QA Checklist".to_string(), String::new()];
+        let mut lines = vec![DEFAULT_HEADER.to_string(), String::new()];
 
         for workflow in packs {
             lines.push(format!("## {}", workflow.pack_name));
             lines.push(String::new());
 
-            for node in &workflow.nodes {
+            for node in workflow.nodes() {
                 lines.push(format!("- [ ] {}", node.node_type));
             }
 
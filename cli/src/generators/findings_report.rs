@@ -0,0 +1,314 @@
+use crate::models::{Finding, FindingsFile};
+use colored::Colorize;
+use regex::Regex;
+
+pub struct LinkGroup<'a> {
+    pub url: String,
+    /// `(id, finding)` in file order.
+    pub findings: Vec<(String, &'a Finding)>,
+}
+
+impl LinkGroup<'_> {
+    pub fn resolved(&self) -> usize {
+        self.findings.iter().filter(|(_, f)| f.resolved).count()
+    }
+
+    /// `owner/repo#123`
+    pub fn label(&self) -> String {
+        let re = Regex::new(r"github\.com/([\w.-]+/[\w.-]+)/(?:pull|issues)/(\d+)").unwrap();
+        match re.captures(&self.url) {
+            Some(caps) => format!("{}#{}", &caps[1], &caps[2]),
+            None => self.url.clone(),
+        }
+    }
+}
+
+/// Findings grouped by the pull request or issue they link to, for triage.
+///
+/// A finding linking several PRs is listed under each of them.
+pub struct FindingsReport<'a> {
+    pub project: String,
+    /// Most findings first.
+    pub groups: Vec<LinkGroup<'a>>,
+    pub unlinked: Vec<(String, &'a Finding)>,
+    pub total: usize,
+}
+
+impl<'a> FindingsReport<'a> {
+    pub fn new(project: &str, files: &'a [FindingsFile]) -> Self {
+        let mut groups: Vec<LinkGroup<'a>> = Vec::new();
+        let mut unlinked = Vec::new();
+        let mut total = 0;
+
+        for file in files {
+            for (i, finding) in file.findings.iter().enumerate() {
+                total += 1;
+                let links = finding.links();
+                if links.is_empty() {
+                    unlinked.push((file.id(i), finding));
+                    continue;
+                }
+                for url in links {
+                    match groups.iter_mut().find(|g| g.url == url) {
+                        Some(group) => group.findings.push((file.id(i), finding)),
+                        None => groups.push(LinkGroup {
+                            url,
+                            findings: vec![(file.id(i), finding)],
+                        }),
+                    }
+                }
+            }
+        }
+
+        groups.sort_by(|a, b| {
+            b.findings
+                .len()
+                .cmp(&a.findings.len())
+                .then_with(|| a.url.cmp(&b.url))
+        });
+
+        FindingsReport {
+            project: project.to_string(),
+            groups,
+            unlinked,
+            total,
+        }
+    }
+
+    pub fn generate_text(&self) -> String {
+        let mut lines = vec![
+            format!("🔗 Findings by fix: {}", self.project.bold()),
+            String::new(),
+            self.summary(),
+            String::new(),
+        ];
+
+        for group in &self.groups {
+            lines.push(format!(
+                "{} - {} ({})",
+                group.label().bold(),
+                count(group.findings.len(), "finding"),
+                resolved_summary(group)
+            ));
+            lines.push(format!("   {}", group.url.dimmed()));
+            for (id, finding) in &group.findings {
+                lines.push(format!(
+                    "   {} {} {}",
+                    status_icon(finding),
+                    id,
+                    describe(finding)
+                ));
+            }
+            lines.push(String::new());
+        }
+
+        if !self.unlinked.is_empty() {
+            lines.push(
+                format!("No fix linked ({})", self.unlinked.len())
+                    .yellow()
+                    .bold()
+                    .to_string(),
+            );
+            for (id, finding) in &self.unlinked {
+                lines.push(format!(
+                    "   {} {} {}",
+                    status_icon(finding),
+                    id,
+                    describe(finding)
+                ));
+            }
+        } else if lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn generate_markdown(&self) -> String {
+        let mut lines = vec![
+            format!("# Findings by fix: {}", self.project),
+            String::new(),
+            self.summary(),
+            String::new(),
+            "| Fix | Findings | Resolved |".to_string(),
+            "|-----|----------|----------|".to_string(),
+        ];
+        for group in &self.groups {
+            lines.push(format!(
+                "| [{}]({}) | {} | {} |",
+                group.label(),
+                group.url,
+                group.findings.len(),
+                group.resolved()
+            ));
+        }
+        lines.push(String::new());
+
+        for group in &self.groups {
+            lines.push(format!("## [{}]({})", group.label(), group.url));
+            lines.push(String::new());
+            for (id, finding) in &group.findings {
+                lines.push(markdown_item(id, finding));
+            }
+            lines.push(String::new());
+        }
+
+        if !self.unlinked.is_empty() {
+            lines.push("## No fix linked".to_string());
+            lines.push(String::new());
+            for (id, finding) in &self.unlinked {
+                lines.push(markdown_item(id, finding));
+            }
+            lines.push(String::new());
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn generate_html(&self) -> String {
+        let rows: Vec<String> = self
+            .groups
+            .iter()
+            .map(|group| {
+                format!(
+                    r#"<tr><td><a href="{}">{}</a></td><td>{}</td><td>{}</td><td><ul>{}</ul></td></tr>"#,
+                    escape_html(&group.url),
+                    escape_html(&group.label()),
+                    group.findings.len(),
+                    group.resolved(),
+                    html_items(&group.findings)
+                )
+            })
+            .collect();
+
+        let unlinked = if self.unlinked.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<h2>No fix linked ({})</h2>\n    <ul>{}</ul>",
+                self.unlinked.len(),
+                html_items(&self.unlinked)
+            )
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+    <title>Findings by Fix - {}</title>
+    <style>
+        body {{ font-family: system-ui, -apple-system, sans-serif; padding: 2rem; max-width: 1100px; margin: 0 auto; }}
+        h1 {{ color: #333; }}
+        table {{ width: 100%; border-collapse: collapse; margin-top: 2rem; }}
+        th, td {{ padding: 0.75rem; text-align: left; border-bottom: 1px solid #ddd; vertical-align: top; }}
+        th {{ background: #f5f5f5; font-weight: 600; }}
+        ul {{ margin: 0; padding-left: 1.2rem; }}
+        .resolved {{ color: #4caf50; }}
+        .open {{ color: #ff9800; }}
+        .id {{ color: #888; font-family: monospace; }}
+    </style>
+</head>
+<body>
+    <h1>Findings by Fix: {}</h1>
+    <p>{}</p>
+    <table>
+        <thead>
+            <tr>
+                <th>Fix</th>
+                <th>Findings</th>
+                <th>Resolved</th>
+                <th>Details</th>
+            </tr>
+        </thead>
+        <tbody>
+            {}
+        </tbody>
+    </table>
+    {}
+</body>
+</html>"#,
+            escape_html(&self.project),
+            escape_html(&self.project),
+            escape_html(&self.summary()),
+            rows.join("\n            "),
+            unlinked
+        )
+    }
+
+    /// "12 findings, 5 fixes linked, 3 with no fix"
+    fn summary(&self) -> String {
+        format!(
+            "{}, {} linked, {} with no fix",
+            count(self.total, "finding"),
+            count(self.groups.len(), "fix"),
+            self.unlinked.len()
+        )
+    }
+}
+
+fn resolved_summary(group: &LinkGroup) -> String {
+    let resolved = group.resolved();
+    if resolved == group.findings.len() {
+        "all resolved".green().to_string()
+    } else {
+        format!("{} resolved", resolved).yellow().to_string()
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    match (n, noun) {
+        (1, _) => format!("1 {}", noun),
+        (_, "fix") => format!("{} fixes", n),
+        _ => format!("{} {}s", n, noun),
+    }
+}
+
+fn describe(finding: &Finding) -> String {
+    let text = match &finding.node {
+        Some(node) => format!("{}: {}", node, finding.description),
+        None => finding.description.clone(),
+    };
+    match &finding.pack {
+        Some(pack) => format!("[{}] {}", pack, text),
+        None => text,
+    }
+}
+
+fn status_icon(finding: &Finding) -> &'static str {
+    if finding.resolved {
+        "✅"
+    } else {
+        "⬜"
+    }
+}
+
+fn markdown_item(id: &str, finding: &Finding) -> String {
+    format!(
+        "- [{}] `{}` {}",
+        if finding.resolved { "x" } else { " " },
+        id,
+        describe(finding)
+    )
+}
+
+fn html_items(findings: &[(String, &Finding)]) -> String {
+    findings
+        .iter()
+        .map(|(id, finding)| {
+            let status = if finding.resolved { "resolved" } else { "open" };
+            format!(
+                r#"<li class="{}"><span class="id">{}</span> {}</li>"#,
+                status,
+                escape_html(id),
+                escape_html(&describe(finding))
+            )
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,5 +1,11 @@
+mod api_prompt;
 mod checklist;
+mod findings_report;
+mod node_prompt;
 mod report;
 
+pub use api_prompt::{ApiPromptGenerator, Conversion};
 pub use checklist::ChecklistGenerator;
+pub use findings_report::{FindingsReport, LinkGroup};
+pub use node_prompt::NodePromptGenerator;
 pub use report::ReportGenerator;
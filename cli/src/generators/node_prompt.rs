@@ -0,0 +1,289 @@
+use super::Conversion;
+use crate::models::{ApiInput, ApiNode, ApiNodeMeta, InputSpec, NodeDefinition, ObjectInfo};
+use serde_json::{json, Value};
+
+/// Standard nodes that produce each upstream type, tried in order.
+const PROVIDERS: &[(&str, &[&str])] = &[
+    ("MODEL", &["CheckpointLoaderSimple", "UNETLoader"]),
+    ("CLIP", &["CheckpointLoaderSimple", "CLIPLoader"]),
+    ("VAE", &["CheckpointLoaderSimple", "VAELoader"]),
+    ("CONDITIONING", &["CLIPTextEncode"]),
+    ("LATENT", &["EmptyLatentImage"]),
+    ("IMAGE", &["LoadImage"]),
+    ("MASK", &["LoadImage", "SolidMask"]),
+    ("CLIP_VISION", &["CLIPVisionLoader"]),
+    ("CLIP_VISION_OUTPUT", &["CLIPVisionEncode"]),
+    ("CONTROL_NET", &["ControlNetLoader"]),
+    ("STYLE_MODEL", &["StyleModelLoader"]),
+    ("UPSCALE_MODEL", &["UpscaleModelLoader"]),
+    ("GLIGEN", &["GLIGENLoader"]),
+    ("AUDIO", &["LoadAudio"]),
+    ("SAMPLER", &["KSamplerSelect"]),
+    ("SIGMAS", &["BasicScheduler"]),
+    ("NOISE", &["RandomNoise"]),
+    ("GUIDER", &["BasicGuider"]),
+];
+
+/// Output nodes that accept each type, so the prompt has an output and
+/// `/prompt` doesn't reject it. Entries that aren't output nodes are
+/// followed until one is.
+const SINKS: &[(&str, &str, &str)] = &[
+    ("IMAGE", "PreviewImage", "images"),
+    ("LATENT", "VAEDecode", "samples"),
+    ("MODEL", "KSampler", "model"),
+    ("CONDITIONING", "KSampler", "positive"),
+    ("CLIP", "CLIPTextEncode", "clip"),
+    ("VAE", "VAEDecode", "vae"),
+    ("MASK", "MaskToImage", "mask"),
+    ("AUDIO", "PreviewAudio", "audio"),
+];
+
+/// Guards against providers that (indirectly) need themselves.
+const MAX_PROVIDER_DEPTH: usize = 8;
+
+/// Builds a runnable API prompt for a single node, wiring its typed inputs
+/// to standard provider nodes.
+pub struct NodePromptGenerator<'a> {
+    object_info: &'a ObjectInfo,
+}
+
+struct Builder<'a> {
+    object_info: &'a ObjectInfo,
+    conversion: Conversion,
+    next_id: u32,
+}
+
+impl<'a> NodePromptGenerator<'a> {
+    pub const TARGET_ID: &'static str = "1";
+
+    pub fn new(object_info: &'a ObjectInfo) -> Self {
+        Self { object_info }
+    }
+
+    /// `None` when `class_type` isn't in the snapshot.
+    pub fn generate(&self, class_type: &str) -> Option<Conversion> {
+        let definition = self.object_info.get(class_type)?;
+        let mut builder = Builder {
+            object_info: self.object_info,
+            conversion: Conversion::default(),
+            next_id: 1,
+        };
+
+        let target = builder.add_node(class_type, definition, 0);
+        if !definition.output_node {
+            builder.add_sink(&target, definition);
+        }
+        Some(builder.conversion)
+    }
+
+    /// The value a widget input gets when the node is tested on its own.
+    pub fn widget_value(input: &InputSpec) -> Option<Value> {
+        if input.input_type == "COMBO" {
+            return match &input.default {
+                Some(default) if input.options.contains(default) => Some(default.clone()),
+                _ => input.options.first().cloned(),
+            };
+        }
+
+        match input.input_type.as_str() {
+            "INT" | "FLOAT" => {
+                let value = input
+                    .default
+                    .as_ref()
+                    .and_then(Value::as_f64)
+                    .unwrap_or(0.0);
+                let value = match (input.min, input.max) {
+                    (Some(min), _) if value < min => min,
+                    (_, Some(max)) if value > max => max,
+                    _ => value,
+                };
+                if input.input_type == "INT" {
+                    // Keep large integer defaults (seeds) exact
+                    match input.default.as_ref() {
+                        Some(default) if default.as_f64() == Some(value) => Some(default.clone()),
+                        _ => Some(json!(value as i64)),
+                    }
+                } else {
+                    Some(json!(value))
+                }
+            }
+            "STRING" => Some(input.default.clone().unwrap_or_else(|| json!(""))),
+            "BOOLEAN" => Some(input.default.clone().unwrap_or(json!(false))),
+            _ => None,
+        }
+    }
+}
+
+impl Builder<'_> {
+    fn add_node(&mut self, class_type: &str, definition: &NodeDefinition, depth: usize) -> String {
+        let id = self.next_id.to_string();
+        self.next_id += 1;
+        self.conversion
+            .prompt
+            .nodes
+            .insert(id.clone(), ApiNode::new(class_type));
+
+        let mut inputs = Vec::new();
+        for input in definition.inputs().into_iter().filter(|i| i.required) {
+            let value = if input.is_widget() {
+                match NodePromptGenerator::widget_value(&input) {
+                    Some(value) => Some(ApiInput::Value(value)),
+                    None => {
+                        self.warn(class_type, &input, "has no options on this server");
+                        None
+                    }
+                }
+            } else {
+                match self.provide(&input.input_type, depth) {
+                    Some((origin, slot)) => Some(ApiInput::Link(origin, slot)),
+                    None => {
+                        self.warn(class_type, &input, "has no standard provider node");
+                        None
+                    }
+                }
+            };
+            if let Some(value) = value {
+                inputs.push((input.name, value));
+            }
+        }
+
+        let node = self.conversion.prompt.nodes.get_mut(&id).unwrap();
+        node.inputs.extend(inputs);
+        if id != NodePromptGenerator::TARGET_ID {
+            node.meta = Some(ApiNodeMeta {
+                title: format!("{} (provider)", class_type),
+            });
+        }
+        id
+    }
+
+    /// An output of type `input_type`, reusing a provider already in the
+    /// prompt (one checkpoint loader feeds MODEL, CLIP and VAE).
+    fn provide(&mut self, input_type: &str, depth: usize) -> Option<(String, u32)> {
+        if depth >= MAX_PROVIDER_DEPTH {
+            return None;
+        }
+        let (_, candidates) = PROVIDERS.iter().find(|(t, _)| *t == input_type)?;
+
+        for class_type in candidates.iter() {
+            let Some(definition) = self.object_info.get(class_type) else {
+                continue;
+            };
+            let Some(slot) = output_slot(definition, input_type) else {
+                continue;
+            };
+
+            let existing = self
+                .conversion
+                .prompt
+                .nodes
+                .iter()
+                .find(|(id, node)| {
+                    id.as_str() != NodePromptGenerator::TARGET_ID && node.class_type == *class_type
+                })
+                .map(|(id, _)| id.clone());
+            let id = match existing {
+                Some(id) => id,
+                None => self.add_node(class_type, definition, depth + 1),
+            };
+            return Some((id, slot));
+        }
+        None
+    }
+
+    fn add_sink(&mut self, origin: &str, definition: &NodeDefinition) {
+        for (slot, output) in definition.outputs().iter().enumerate() {
+            if self.sink(origin, slot as u32, &output.output_type, 0) {
+                return;
+            }
+        }
+        self.conversion.warnings.push(format!(
+            "{}: no output node accepts its outputs; the server may reject the prompt",
+            self.conversion.prompt.nodes[origin].class_type
+        ));
+    }
+
+    /// Feed `origin`'s output into a chain ending at an output node. A chain
+    /// that dead-ends is taken out again, providers and warnings included.
+    fn sink(&mut self, origin: &str, slot: u32, output_type: &str, depth: usize) -> bool {
+        let (next_id, warnings) = (self.next_id, self.conversion.warnings.len());
+        if self.try_sink(origin, slot, output_type, depth) {
+            return true;
+        }
+        self.conversion
+            .prompt
+            .nodes
+            .retain(|id, _| id.parse::<u32>().is_ok_and(|id| id < next_id));
+        self.next_id = next_id;
+        self.conversion.warnings.truncate(warnings);
+        false
+    }
+
+    fn try_sink(&mut self, origin: &str, slot: u32, output_type: &str, depth: usize) -> bool {
+        if depth >= MAX_PROVIDER_DEPTH {
+            return false;
+        }
+        let Some((_, class_type, input)) = SINKS.iter().find(|(t, _, _)| *t == output_type) else {
+            return false;
+        };
+        let Some(definition) = self.object_info.get(class_type) else {
+            return false;
+        };
+
+        let id = self.next_id.to_string();
+        let mut node = ApiNode::new(class_type);
+        node.inputs
+            .insert(input.to_string(), ApiInput::Link(origin.to_string(), slot));
+        node.meta = Some(ApiNodeMeta {
+            title: format!("{} (output)", class_type),
+        });
+        self.next_id += 1;
+        self.conversion.prompt.nodes.insert(id.clone(), node);
+
+        // Remaining inputs, e.g. the VAE of VAEDecode
+        for spec in definition.inputs().into_iter().filter(|i| i.required) {
+            if spec.name == *input {
+                continue;
+            }
+            let value = if spec.is_widget() {
+                NodePromptGenerator::widget_value(&spec).map(ApiInput::Value)
+            } else {
+                self.provide(&spec.input_type, depth)
+                    .map(|(origin, slot)| ApiInput::Link(origin, slot))
+            };
+            if let Some(value) = value {
+                self.conversion
+                    .prompt
+                    .nodes
+                    .get_mut(&id)
+                    .unwrap()
+                    .inputs
+                    .insert(spec.name, value);
+            }
+        }
+
+        if definition.output_node {
+            return true;
+        }
+        definition
+            .outputs()
+            .iter()
+            .enumerate()
+            .any(|(slot, output)| self.sink(&id, slot as u32, &output.output_type, depth + 1))
+    }
+
+    fn warn(&mut self, class_type: &str, input: &InputSpec, problem: &str) {
+        self.conversion.warnings.push(format!(
+            "{}: required input '{}' ({}) {}",
+            class_type, input.name, input.input_type, problem
+        ));
+    }
+}
+
+fn output_slot(definition: &NodeDefinition, output_type: &str) -> Option<u32> {
+    definition
+        .outputs()
+        .iter()
+        .position(|o| o.output_type == output_type)
+        .map(|slot| slot as u32)
+}
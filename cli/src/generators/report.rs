@@ -1,10 +1,14 @@
-use crate::models::{Checklist, Metadata};
+use crate::models::{Checklist, DetailedChecklist, Metadata, NodePack, TestDimension, TestOutcome};
 use colored::Colorize;
 
 pub struct ReportGenerator;
 
 impl ReportGenerator {
-    pub fn generate_text(checklist: &Checklist, metadata: Option<&Metadata>) -> String {
+    pub fn generate_text(
+        checklist: &Checklist,
+        detailed: Option<&DetailedChecklist>,
+        metadata: Option<&Metadata>,
+    ) -> String {
         let mut lines = Vec::new();
 
         lines.push(format!("📊 Project: {}", checklist.project_name.bold()));
@@ -16,6 +20,9 @@ impl ReportGenerator {
                     "✅ Tested: {}/{}",
                     stats.tested_packs, stats.total_packs
                 ));
+                if !stats.outcomes.is_empty() {
+                    lines.push(format!("   {}", TestOutcome::summarize(&stats.outcomes)));
+                }
                 lines.push(format!("📈 Progress: {:.1}%", stats.completion_percent));
                 lines.push(format!("🔢 Total Nodes: {}", stats.total_nodes));
                 lines.push(String::new());
@@ -28,42 +35,109 @@ impl ReportGenerator {
             lines.push(String::new());
         }
 
-        let tested = checklist.packs.iter().filter(|p| p.tested).count();
+        let tested = checklist.packs.iter().filter(|p| p.tested()).count();
         let total = checklist.packs.len();
 
-        lines.push(format!("Packs: {}/{} tested", tested, total));
+        let outcome_counts = checklist.outcome_counts();
+        let summary = TestOutcome::summarize(&outcome_counts);
+        if summary.is_empty() {
+            lines.push(format!("Packs: {}/{} tested", tested, total));
+        } else {
+            lines.push(format!("Packs: {}/{} tested ({})", tested, total, summary));
+        }
+        if let Some((tested_nodes, total_nodes)) = node_progress(detailed) {
+            let node_summary = detailed
+                .map(|d| TestOutcome::summarize(&d.outcome_counts()))
+                .unwrap_or_default();
+            if node_summary.is_empty() {
+                lines.push(format!("Nodes: {}/{} tested", tested_nodes, total_nodes));
+            } else {
+                lines.push(format!(
+                    "Nodes: {}/{} tested ({})",
+                    tested_nodes, total_nodes, node_summary
+                ));
+            }
+        }
+        lines.push(format!(
+            "Coverage: {}",
+            coverage_summary(
+                checklist.dimension_progress(TestDimension::Ui),
+                checklist.dimension_progress(TestDimension::Api),
+                "packs"
+            )
+        ));
+        if let Some(detailed) = detailed.filter(|_| node_progress(detailed).is_some()) {
+            lines.push(format!(
+                "          {}",
+                coverage_summary(
+                    detailed.dimension_progress(TestDimension::Ui),
+                    detailed.dimension_progress(TestDimension::Api),
+                    "nodes"
+                )
+            ));
+        }
         lines.push(String::new());
 
-        // Group by tested status
-        let tested_packs: Vec<_> = checklist.packs.iter().filter(|p| p.tested).collect();
-        let untested_packs: Vec<_> = checklist.packs.iter().filter(|p| !p.tested).collect();
+        // Group by outcome, problems first, untested last
+        for outcome in [
+            TestOutcome::Fail,
+            TestOutcome::Blocked,
+            TestOutcome::Pass,
+            TestOutcome::Skipped,
+            TestOutcome::NotApplicable,
+            TestOutcome::Untested,
+        ] {
+            let packs: Vec<_> = checklist
+                .packs
+                .iter()
+                .filter(|p| p.outcome == outcome)
+                .collect();
+            if packs.is_empty() {
+                continue;
+            }
 
-        if !tested_packs.is_empty() {
-            lines.push("✅ Tested:".green().to_string());
-            for pack in tested_packs {
-                lines.push(format!("   • {} ({})", pack.name, pack.node_count));
+            let heading = format!("{} {}:", outcome.icon(), outcome_heading(outcome));
+            lines.push(match outcome {
+                TestOutcome::Pass => heading.green().to_string(),
+                TestOutcome::Fail => heading.red().to_string(),
+                TestOutcome::Untested | TestOutcome::Blocked => heading.yellow().to_string(),
+                _ => heading.dimmed().to_string(),
+            });
+            for pack in packs {
+                lines.push(format!("   • {}", describe_pack(pack, detailed)));
             }
             lines.push(String::new());
         }
-
-        if !untested_packs.is_empty() {
-            lines.push("⏳ Untested:".yellow().to_string());
-            for pack in untested_packs {
-                lines.push(format!("   • {} ({})", pack.name, pack.node_count));
-            }
+        if lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
         }
 
         lines.join("\n")
     }
 
-    pub fn generate_html(checklist: &Checklist, _metadata: Option<&Metadata>) -> String {
-        let tested = checklist.packs.iter().filter(|p| p.tested).count();
+    pub fn generate_html(
+        checklist: &Checklist,
+        detailed: Option<&DetailedChecklist>,
+        _metadata: Option<&Metadata>,
+    ) -> String {
+        let (tested_nodes, total_nodes) = node_progress(detailed).unwrap_or((0, 0));
+        let tested = checklist.packs.iter().filter(|p| p.tested()).count();
         let total = checklist.packs.len();
         let percent = if total > 0 {
             (tested as f64 / total as f64) * 100.0
         } else {
             0.0
         };
+        let (ui_tested, _) = checklist.dimension_progress(TestDimension::Ui);
+        let (api_tested, _) = checklist.dimension_progress(TestDimension::Api);
+        let node_coverage = match detailed.filter(|_| total_nodes > 0) {
+            Some(d) => format!(
+                "{} / {}",
+                d.dimension_progress(TestDimension::Ui).0,
+                d.dimension_progress(TestDimension::Api).0
+            ),
+            None => "-".to_string(),
+        };
 
         format!(
             r#"<!DOCTYPE html>
@@ -82,8 +156,10 @@ impl ReportGenerator {
         table {{ width: 100%; border-collapse: collapse; margin-top: 2rem; }}
         th, td {{ padding: 0.75rem; text-align: left; border-bottom: 1px solid #ddd; }}
         th {{ background: #f5f5f5; font-weight: 600; }}
-        .tested {{ color: #4caf50; }}
-        .untested {{ color: #ff9800; }}
+        .pass {{ color: #4caf50; }}
+        .fail {{ color: #e53935; }}
+        .blocked, .untested {{ color: #ff9800; }}
+        .skipped, .not_applicable {{ color: #888; }}
     </style>
 </head>
 <body>
@@ -97,6 +173,22 @@ impl ReportGenerator {
             <div class="stat-value">{:.1}%</div>
             <div class="stat-label">Progress</div>
         </div>
+        <div class="stat">
+            <div class="stat-value">{}/{}</div>
+            <div class="stat-label">Nodes Tested</div>
+        </div>
+        <div class="stat">
+            <div class="stat-value">{}/{}</div>
+            <div class="stat-label">UI Tested</div>
+        </div>
+        <div class="stat">
+            <div class="stat-value">{}/{}</div>
+            <div class="stat-label">API Tested</div>
+        </div>
+        <div class="stat">
+            <div class="stat-value">{}</div>
+            <div class="stat-label">UI / API Nodes</div>
+        </div>
     </div>
     <div class="progress">
         <div class="progress-bar" style="width: {:.1}%"></div>
@@ -106,6 +198,9 @@ impl ReportGenerator {
             <tr>
                 <th>Pack Name</th>
                 <th>Node Count</th>
+                <th>Nodes Tested</th>
+                <th>UI</th>
+                <th>API</th>
                 <th>Status</th>
             </tr>
         </thead>
@@ -120,20 +215,38 @@ impl ReportGenerator {
             tested,
             total,
             percent,
+            tested_nodes,
+            total_nodes,
+            ui_tested,
+            total,
+            api_tested,
+            total,
+            node_coverage,
             percent,
             checklist
                 .packs
                 .iter()
                 .map(|pack| {
-                    let status_class = if pack.tested { "tested" } else { "untested" };
-                    let status_text = if pack.tested {
-                        "✅ Tested"
-                    } else {
-                        "⏳ Untested"
+                    let outcome = pack.outcome;
+                    let status_class = outcome.key();
+                    let status_text = format!("{} {}", outcome.icon(), outcome_heading(outcome));
+                    let nodes_tested = match detailed.map(|d| d.pack_progress(&pack.name)) {
+                        Some((tested, total)) if total > 0 => format!("{}/{}", tested, total),
+                        _ => "-".to_string(),
                     };
+                    let ui = pack.dimension_outcome(TestDimension::Ui);
+                    let api = pack.dimension_outcome(TestDimension::Api);
                     format!(
-                        r#"<tr><td>{}</td><td>{}</td><td class="{}">{}</td></tr>"#,
-                        pack.name, pack.node_count, status_class, status_text
+                        r#"<tr><td>{}</td><td>{}</td><td>{}</td><td class="{}">{}</td><td class="{}">{}</td><td class="{}">{}</td></tr>"#,
+                        pack.name,
+                        pack.node_count,
+                        nodes_tested,
+                        ui.key(),
+                        ui.icon(),
+                        api.key(),
+                        api.icon(),
+                        status_class,
+                        status_text
                     )
                 })
                 .collect::<Vec<_>>()
@@ -141,3 +254,38 @@ impl ReportGenerator {
         )
     }
 }
+
+fn node_progress(detailed: Option<&DetailedChecklist>) -> Option<(usize, usize)> {
+    detailed
+        .map(|d| d.progress())
+        .filter(|(_, total)| *total > 0)
+}
+
+/// "UI 3/10 packs · API 1/10 packs"
+fn coverage_summary(ui: (usize, usize), api: (usize, usize), unit: &str) -> String {
+    format!(
+        "UI {}/{} {} · API {}/{} {}",
+        ui.0, ui.1, unit, api.0, api.1, unit
+    )
+}
+
+fn describe_pack(pack: &NodePack, detailed: Option<&DetailedChecklist>) -> String {
+    match detailed.map(|d| d.pack_progress(&pack.name)) {
+        Some((tested, total)) if total > 0 => format!(
+            "{} ({}) - {}/{} nodes tested",
+            pack.name, pack.node_count, tested, total
+        ),
+        _ => format!("{} ({})", pack.name, pack.node_count),
+    }
+}
+
+fn outcome_heading(outcome: TestOutcome) -> &'static str {
+    match outcome {
+        TestOutcome::Untested => "Untested",
+        TestOutcome::Pass => "Tested",
+        TestOutcome::Fail => "Failed",
+        TestOutcome::Blocked => "Blocked",
+        TestOutcome::Skipped => "Skipped",
+        TestOutcome::NotApplicable => "Not applicable",
+    }
+}
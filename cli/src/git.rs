@@ -228,3 +228,17 @@ pub fn get_repo_status_summary() -> Option<String> {
         Some(parts.join(", "))
     }
 }
+
+pub fn get_user_name() -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()?;
+
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !name.is_empty() {
+        Some(name)
+    } else {
+        None
+    }
+}
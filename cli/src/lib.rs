@@ -1,6 +1,9 @@
+pub mod api;
 pub mod commands;
 pub mod generators;
 pub mod git;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod models;
 pub mod parsers;
 pub mod validators;
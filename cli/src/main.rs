@@ -71,6 +71,24 @@ enum Commands {
         project: Option<String>,
     },
 
+    /// Convert a UI workflow to API prompt format offline
+    Convert {
+        /// Path to workflow JSON file
+        workflow: String,
+
+        /// Saved /object_info response (defaults to the project's object_info.json)
+        #[arg(long)]
+        object_info: Option<String>,
+
+        /// Project whose object_info.json to use
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Write the prompt to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
     /// Import browser export JSON and create/update project
     Import {
         /// Path to export JSON file
@@ -78,6 +96,20 @@ enum Commands {
 
         /// Project name
         project: String,
+
+        /// Keep results, notes and issues of an existing project
+        #[arg(long)]
+        merge: bool,
+    },
+
+    /// Import the workflow embedded in a PNG/WebP output image
+    ImportImage {
+        /// Path to the image file
+        image_file: String,
+
+        /// Pack name for the saved file (detected from the nodes if omitted)
+        #[arg(long)]
+        pack: Option<String>,
     },
 
     /// Create new QA project from template
@@ -102,6 +134,224 @@ enum Commands {
 
     /// List all QA projects
     List,
+
+    /// Add, list and resolve findings
+    Findings {
+        #[command(subcommand)]
+        action: FindingsAction,
+    },
+
+    /// Start, close and compare testing rounds
+    Round {
+        #[command(subcommand)]
+        action: RoundAction,
+    },
+
+    /// Manage the project's node definitions
+    Catalog {
+        #[command(subcommand)]
+        action: CatalogAction,
+    },
+
+    /// Test nodes through a Hanzo Studio server's API
+    ApiTest {
+        #[command(subcommand)]
+        action: ApiTestAction,
+    },
+
+    /// Run a mock Hanzo Studio server for trying api-test without a GPU
+    #[cfg(feature = "mock-server")]
+    MockServer {
+        /// object_info JSON to serve (defaults to a bundled fixture)
+        #[arg(long)]
+        object_info: Option<String>,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8188)]
+        port: u16,
+
+        /// Make a node class raise when executed, as Class or Class=message
+        #[arg(long)]
+        fail: Vec<String>,
+
+        /// Milliseconds spent executing each node
+        #[arg(long, default_value_t = 0)]
+        delay: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApiTestAction {
+    /// Queue a prompt per node and record the results in the checklists
+    Run {
+        /// Project name
+        project: String,
+
+        /// Only test this pack (defaults to every pack in the checklist)
+        pack: Option<String>,
+
+        /// Server URL
+        #[arg(long, default_value = "http://127.0.0.1:8188")]
+        url: String,
+
+        /// Seconds to wait for each prompt to finish
+        #[arg(long, default_value_t = 120)]
+        timeout: u64,
+
+        /// Only check that /prompt accepts each prompt
+        #[arg(long)]
+        validate_only: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CatalogAction {
+    /// Import a saved /object_info response as the project's node definitions
+    Import {
+        /// Path to the object_info JSON file
+        object_info: String,
+
+        /// Project name
+        project: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RoundAction {
+    /// Start a round, snapshotting the checklists and findings
+    Start {
+        /// Project name
+        project: String,
+
+        /// Round number (defaults to the next one)
+        #[arg(long)]
+        round: Option<u32>,
+    },
+
+    /// Close the open round, freezing its results
+    Close {
+        /// Project name
+        project: String,
+    },
+
+    /// Show what changed between two rounds
+    Compare {
+        /// Project name
+        project: String,
+
+        /// Earlier round (defaults to the one before --to)
+        #[arg(long)]
+        from: Option<u32>,
+
+        /// Later round (defaults to the latest)
+        #[arg(long)]
+        to: Option<u32>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum FindingsAction {
+    /// Add a finding to the latest round
+    Add {
+        /// Project name
+        project: String,
+
+        /// Pack the finding is about
+        pack: String,
+
+        /// What went wrong
+        description: String,
+
+        /// Node the finding is about
+        #[arg(long)]
+        node: Option<String>,
+
+        /// Add to this round's file instead of the latest
+        #[arg(long)]
+        round: Option<u32>,
+
+        /// Skip checking the pack and node against the checklist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// List findings
+    List {
+        /// Project name
+        project: String,
+
+        /// Only open findings
+        #[arg(long, conflicts_with = "resolved")]
+        open: bool,
+
+        /// Only resolved findings
+        #[arg(long)]
+        resolved: bool,
+
+        /// Only findings for this pack
+        #[arg(long)]
+        pack: Option<String>,
+
+        /// Only findings from this round
+        #[arg(long)]
+        round: Option<u32>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Mark a finding resolved
+    Resolve {
+        /// Project name
+        project: String,
+
+        /// Finding ID from `findings list` (e.g. r2-5)
+        id: String,
+
+        /// Resolution note
+        note: String,
+
+        /// Pull request with the fix
+        #[arg(long)]
+        pr: Option<String>,
+
+        /// Who resolved it (defaults to git user.name)
+        #[arg(long)]
+        by: Option<String>,
+    },
+
+    /// Print findings files in the standard layout
+    Render {
+        /// Project name
+        project: String,
+
+        /// Only this round
+        #[arg(long)]
+        round: Option<u32>,
+
+        /// Rewrite the files instead of printing
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Group findings by the pull request or issue that fixes them
+    Report {
+        /// Project name
+        project: String,
+
+        /// Output format (text, markdown, html)
+        #[arg(long, default_value = "text")]
+        format: String,
+
+        /// Only this round
+        #[arg(long)]
+        round: Option<u32>,
+    },
 }
 
 fn main() -> Result<()> {
@@ -131,10 +381,20 @@ fn main() -> Result<()> {
 
         Commands::GenerateApiTest { project } => commands::generate_api_test::run(project),
 
+        Commands::Convert {
+            workflow,
+            object_info,
+            project,
+            output,
+        } => commands::convert::run(workflow, object_info, project, output),
+
         Commands::Import {
             export_file,
             project,
-        } => commands::import::run(export_file, project),
+            merge,
+        } => commands::import::run(export_file, project, merge),
+
+        Commands::ImportImage { image_file, pack } => commands::import_image::run(image_file, pack),
 
         Commands::New { project_name } => commands::new_project::run(project_name),
 
@@ -145,5 +405,77 @@ fn main() -> Result<()> {
         } => commands::status::run(project, format, all),
 
         Commands::List => commands::list::run(),
+
+        Commands::Findings { action } => match action {
+            FindingsAction::Add {
+                project,
+                pack,
+                description,
+                node,
+                round,
+                force,
+            } => commands::findings::add(project, pack, node, description, round, force),
+            FindingsAction::List {
+                project,
+                open,
+                resolved,
+                pack,
+                round,
+                json,
+            } => commands::findings::list(project, open, resolved, pack, round, json),
+            FindingsAction::Resolve {
+                project,
+                id,
+                note,
+                pr,
+                by,
+            } => commands::findings::resolve(project, id, note, pr, by),
+            FindingsAction::Render {
+                project,
+                round,
+                write,
+            } => commands::findings::render(project, round, write),
+            FindingsAction::Report {
+                project,
+                format,
+                round,
+            } => commands::findings::report(project, format, round),
+        },
+
+        Commands::Round { action } => match action {
+            RoundAction::Start { project, round } => commands::round::start(project, round),
+            RoundAction::Close { project } => commands::round::close(project),
+            RoundAction::Compare {
+                project,
+                from,
+                to,
+                json,
+            } => commands::round::compare(project, from, to, json),
+        },
+
+        Commands::Catalog { action } => match action {
+            CatalogAction::Import {
+                object_info,
+                project,
+            } => commands::catalog::import(object_info, project),
+        },
+
+        Commands::ApiTest { action } => match action {
+            ApiTestAction::Run {
+                project,
+                pack,
+                url,
+                timeout,
+                validate_only,
+            } => commands::api_test::run(project, pack, url, timeout, validate_only),
+        },
+
+        #[cfg(feature = "mock-server")]
+        Commands::MockServer {
+            object_info,
+            port,
+            fail,
+            delay,
+        } => commands::mock_server::run(object_info, port, fail, delay),
     }
 }
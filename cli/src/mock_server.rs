@@ -0,0 +1,782 @@
+use crate::models::{InputSpec, ObjectInfo};
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tiny_http::{Header, Method, ReadWrite, Request, Response, Server, StatusCode};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+/// Core nodes plus a small custom pack with a deprecated node.
+pub const FIXTURE_OBJECT_INFO: &str = include_str!("../fixtures/object_info.json");
+
+type Socket = WebSocket<Box<dyn ReadWrite + Send>>;
+
+/// A stand-in Hanzo Studio server. `/prompt` is validated with the real
+/// server's `node_errors`; accepted prompts "execute" on a worker thread.
+pub struct MockServer {
+    server: Arc<Server>,
+    shared: Arc<Shared>,
+    url: String,
+    handle: Option<JoinHandle<()>>,
+}
+
+struct Shared {
+    object_info: ObjectInfo,
+    state: Mutex<State>,
+    node_delay: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    number: u64,
+    queue_remaining: usize,
+    requests: Vec<Value>,
+    history: Map<String, Value>,
+    failures: HashMap<String, String>,
+    sockets: Vec<(String, Socket)>,
+}
+
+struct Job {
+    prompt_id: String,
+    number: u64,
+    prompt: Map<String, Value>,
+    extra_data: Value,
+    client_id: Option<String>,
+    outputs: Vec<String>,
+}
+
+impl MockServer {
+    pub fn start(object_info: ObjectInfo) -> Result<Self> {
+        Self::bind("127.0.0.1:0", object_info, Duration::ZERO)
+    }
+
+    pub fn start_fixture() -> Result<Self> {
+        Self::start(Self::fixture_object_info())
+    }
+
+    pub fn fixture_object_info() -> ObjectInfo {
+        serde_json::from_str(FIXTURE_OBJECT_INFO).expect("bundled object_info fixture is valid")
+    }
+
+    pub fn bind(addr: &str, object_info: ObjectInfo, node_delay: Duration) -> Result<Self> {
+        let server = Server::http(addr)
+            .map_err(|err| anyhow::anyhow!("Failed to listen on {}: {}", addr, err))?;
+        let local = server
+            .server_addr()
+            .to_ip()
+            .with_context(|| format!("{} is not a TCP address", addr))?;
+        let server = Arc::new(server);
+        let shared = Arc::new(Shared {
+            object_info,
+            state: Mutex::new(State::default()),
+            node_delay,
+        });
+
+        let (jobs, queue) = mpsc::channel();
+        let worker = Arc::clone(&shared);
+        thread::spawn(move || execute_jobs(&worker, queue));
+
+        let handle = {
+            let server = Arc::clone(&server);
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    shared.handle(request, &jobs);
+                }
+            })
+        };
+
+        Ok(Self {
+            server,
+            shared,
+            url: format!("http://{}", local),
+            handle: Some(handle),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn ws_url(&self, client_id: &str) -> String {
+        format!(
+            "{}/ws?clientId={}",
+            self.url.replacen("http://", "ws://", 1),
+            client_id
+        )
+    }
+
+    /// Make every node of `class_type` raise `message` when executed.
+    /// Validation is unaffected, as with a real node that crashes.
+    pub fn fail_node(&self, class_type: &str, message: &str) {
+        self.shared
+            .lock()
+            .failures
+            .insert(class_type.to_string(), message.to_string());
+    }
+
+    pub fn requests(&self) -> Vec<Value> {
+        self.shared.lock().requests.clone()
+    }
+
+    pub fn history(&self) -> Map<String, Value> {
+        self.shared.lock().history.clone()
+    }
+
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn handle(&self, mut request: Request, jobs: &Sender<Job>) {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        let path = path.trim_end_matches('/').to_string();
+
+        let method = request.method().clone();
+        let (status, body) = match (&method, path.as_str()) {
+            (Method::Get, "/ws") => {
+                let client_id = query_param(query, "clientId").map(str::to_string);
+                self.accept_socket(request, client_id);
+                return;
+            }
+            (Method::Get, "/object_info") => (200, json!(self.object_info)),
+            (Method::Get, p) if p.starts_with("/object_info/") => {
+                let class_type = &p["/object_info/".len()..];
+                match self.object_info.get(class_type) {
+                    Some(definition) => (200, json!({ class_type: definition })),
+                    None => (200, json!({})),
+                }
+            }
+            (Method::Get, "/prompt") => (200, self.queue_status()),
+            (Method::Post, "/prompt") => {
+                let mut text = String::new();
+                let _ = request.as_reader().read_to_string(&mut text);
+                self.queue_prompt(&text, jobs)
+            }
+            (Method::Get, "/history") => (200, Value::Object(self.lock().history.clone())),
+            (Method::Get, p) if p.starts_with("/history/") => {
+                let prompt_id = &p["/history/".len()..];
+                match self.lock().history.get(prompt_id) {
+                    Some(entry) => (200, json!({ prompt_id: entry })),
+                    None => (200, json!({})),
+                }
+            }
+            _ => (404, json!({ "error": "Not Found" })),
+        };
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(StatusCode(status))
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+        let _ = request.respond(response);
+    }
+
+    fn queue_status(&self) -> Value {
+        json!({ "exec_info": { "queue_remaining": self.lock().queue_remaining } })
+    }
+
+    fn queue_prompt(&self, text: &str, jobs: &Sender<Job>) -> (u16, Value) {
+        let body: Value = serde_json::from_str(text).unwrap_or(Value::Null);
+        self.lock().requests.push(body.clone());
+
+        let Some(prompt) = body.get("prompt").and_then(Value::as_object) else {
+            return (400, json!({ "error": "no prompt", "node_errors": [] }));
+        };
+        let (outputs, node_errors) = match validate_prompt(&self.object_info, prompt) {
+            Ok(validated) => validated,
+            Err(error) => return (400, json!({ "error": error, "node_errors": {} })),
+        };
+        if outputs.is_empty() {
+            return (
+                400,
+                json!({
+                    "error": prompt_error(
+                        "prompt_outputs_failed_validation",
+                        "Prompt outputs failed validation",
+                        ""
+                    ),
+                    "node_errors": node_errors,
+                }),
+            );
+        }
+
+        let (prompt_id, number) = {
+            let mut state = self.lock();
+            let number = state.number;
+            state.number += 1;
+            state.queue_remaining += 1;
+            (prompt_id(number), number)
+        };
+        let job = Job {
+            prompt_id: prompt_id.clone(),
+            number,
+            prompt: prompt.clone(),
+            extra_data: body.get("extra_data").cloned().unwrap_or_else(|| json!({})),
+            client_id: body
+                .get("client_id")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            outputs,
+        };
+        self.send(
+            None,
+            "status",
+            json!({ "status": self.queue_status()["exec_info"].clone() }),
+        );
+        let _ = jobs.send(job);
+
+        (
+            200,
+            json!({ "prompt_id": prompt_id, "number": number, "node_errors": node_errors }),
+        )
+    }
+
+    fn accept_socket(&self, request: Request, client_id: Option<String>) {
+        let key = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Sec-WebSocket-Key"))
+            .map(|h| h.value.as_str().to_string());
+        let Some(key) = key else {
+            let _ = request
+                .respond(Response::from_string("Expected a websocket").with_status_code(400));
+            return;
+        };
+        let accept = derive_accept_key(key.as_bytes());
+        let response = Response::empty(StatusCode(101))
+            .with_header(Header::from_bytes("Sec-WebSocket-Accept", accept).unwrap());
+        let stream = request.upgrade("websocket", response);
+        let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+        let sid = client_id.unwrap_or_else(|| prompt_id(u64::MAX));
+        let mut state = self.lock();
+        let status = json!({
+            "type": "status",
+            "data": {
+                "status": { "exec_info": { "queue_remaining": state.queue_remaining } },
+                "sid": sid,
+            },
+        });
+        if socket.send(Message::Text(status.to_string())).is_ok() {
+            state.sockets.push((sid, socket));
+        }
+    }
+
+    fn send(&self, client_id: Option<&str>, kind: &str, data: Value) {
+        let text = json!({ "type": kind, "data": data }).to_string();
+        self.lock().sockets.retain_mut(|(sid, socket)| {
+            if client_id.is_some_and(|id| id != sid) {
+                return true;
+            }
+            socket.send(Message::Text(text.clone())).is_ok()
+        });
+    }
+
+    /// Run a queued prompt node by node, sending the same events as the real
+    /// server, then add it to the history.
+    fn execute(&self, job: Job) {
+        let client = job.client_id.as_deref();
+        let prompt_id = job.prompt_id.as_str();
+        let mut messages = Vec::new();
+        let mut event = |kind: &str, data: Value, record: bool| {
+            if record {
+                messages.push(json!([kind, data]));
+            }
+            self.send(client, kind, data);
+        };
+
+        event(
+            "execution_start",
+            json!({ "prompt_id": prompt_id, "timestamp": timestamp() }),
+            true,
+        );
+        event(
+            "execution_cached",
+            json!({ "nodes": [], "prompt_id": prompt_id, "timestamp": timestamp() }),
+            true,
+        );
+
+        let mut outputs = Map::new();
+        let mut executed = Vec::new();
+        let mut error = None;
+        for id in execution_order(&job.prompt, &job.outputs) {
+            let class_type = job.prompt[&id]["class_type"].as_str().unwrap_or_default();
+            event(
+                "executing",
+                json!({ "node": id, "display_node": id, "prompt_id": prompt_id }),
+                false,
+            );
+            thread::sleep(self.node_delay);
+
+            if let Some(message) = self.lock().failures.get(class_type).cloned() {
+                error = Some(json!({
+                    "prompt_id": prompt_id,
+                    "node_id": id,
+                    "node_type": class_type,
+                    "executed": executed,
+                    "exception_message": message,
+                    "exception_type": "Exception",
+                    "traceback": [],
+                    "current_inputs": {},
+                    "current_outputs": {},
+                    "timestamp": timestamp(),
+                }));
+                break;
+            }
+
+            // Samplers report each step
+            let steps = job.prompt[&id]["inputs"]["steps"].as_u64().unwrap_or(0);
+            for value in 1..=steps {
+                event(
+                    "progress",
+                    json!({ "value": value, "max": steps, "prompt_id": prompt_id, "node": id }),
+                    false,
+                );
+            }
+
+            if let Some(output) = self.node_output(class_type, job.number) {
+                event(
+                    "executed",
+                    json!({
+                        "node": id,
+                        "display_node": id,
+                        "output": output,
+                        "prompt_id": prompt_id,
+                    }),
+                    false,
+                );
+                outputs.insert(id.clone(), output);
+            }
+            executed.push(id);
+        }
+
+        let status_str = match error {
+            Some(data) => {
+                event("execution_error", data, true);
+                "error"
+            }
+            None => {
+                event(
+                    "execution_success",
+                    json!({ "prompt_id": prompt_id, "timestamp": timestamp() }),
+                    true,
+                );
+                "success"
+            }
+        };
+        event(
+            "executing",
+            json!({ "node": null, "prompt_id": prompt_id }),
+            false,
+        );
+
+        let meta: Map<String, Value> = outputs
+            .keys()
+            .map(|id| {
+                let meta = json!({
+                    "node_id": id,
+                    "display_node": id,
+                    "parent_node": null,
+                    "real_node_id": id,
+                });
+                (id.clone(), meta)
+            })
+            .collect();
+        let entry = json!({
+            "prompt": [job.number, prompt_id, job.prompt, job.extra_data, job.outputs],
+            "outputs": outputs,
+            "status": {
+                "status_str": status_str,
+                "completed": status_str == "success",
+                "messages": messages,
+            },
+            "meta": meta,
+        });
+        {
+            let mut state = self.lock();
+            state.history.insert(prompt_id.to_string(), entry);
+            state.queue_remaining = state.queue_remaining.saturating_sub(1);
+        }
+        self.send(
+            None,
+            "status",
+            json!({ "status": self.queue_status()["exec_info"].clone() }),
+        );
+    }
+
+    fn node_output(&self, class_type: &str, number: u64) -> Option<Value> {
+        let definition = self.object_info.get(class_type)?;
+        let takes_images = definition.inputs().iter().any(|i| i.input_type == "IMAGE");
+        if !definition.output_node || !takes_images {
+            return None;
+        }
+        let (prefix, kind) = if class_type.contains("Preview") {
+            ("ComfyUI_temp", "temp")
+        } else {
+            ("ComfyUI", "output")
+        };
+        Some(json!({
+            "images": [{
+                "filename": format!("{}_{:05}_.png", prefix, number + 1),
+                "subfolder": "",
+                "type": kind,
+            }]
+        }))
+    }
+}
+
+fn execute_jobs(shared: &Shared, queue: Receiver<Job>) {
+    for job in queue {
+        shared.execute(job);
+    }
+}
+
+/// Check a prompt the way the server's `validate_prompt` does. Returns the
+/// output nodes that can run plus `node_errors` for the rest, or the
+/// prompt-level error when nothing can be checked.
+pub fn validate_prompt(
+    object_info: &ObjectInfo,
+    prompt: &Map<String, Value>,
+) -> Result<(Vec<String>, Map<String, Value>), Value> {
+    let mut output_ids = Vec::new();
+    for (id, node) in prompt {
+        let Some(class_type) = node.get("class_type").and_then(Value::as_str) else {
+            return Err(prompt_error(
+                "invalid_prompt",
+                "Cannot execute because a node is missing the class_type property.",
+                &format!("Node ID '#{}'", id),
+            ));
+        };
+        let Some(definition) = object_info.get(class_type) else {
+            return Err(prompt_error(
+                "invalid_prompt",
+                &format!("Cannot execute because node {} does not exist.", class_type),
+                &format!("Node ID '#{}'", id),
+            ));
+        };
+        if definition.output_node {
+            output_ids.push(id.clone());
+        }
+    }
+    if output_ids.is_empty() {
+        return Err(prompt_error(
+            "prompt_no_outputs",
+            "Prompt has no outputs",
+            "",
+        ));
+    }
+
+    let mut validator = PromptValidator {
+        object_info,
+        prompt,
+        errors: BTreeMap::new(),
+    };
+    let mut good = Vec::new();
+    let mut node_errors = Map::new();
+    for output in output_ids {
+        let mut upstream = Vec::new();
+        if validator.validate(&output, &mut upstream) {
+            good.push(output);
+            continue;
+        }
+        for id in upstream {
+            let errors = &validator.errors[&id];
+            if errors.is_empty() {
+                continue;
+            }
+            let entry = node_errors.entry(id.clone()).or_insert_with(|| {
+                json!({
+                    "errors": errors,
+                    "dependent_outputs": [],
+                    "class_type": prompt[&id]["class_type"],
+                })
+            });
+            let dependents = entry["dependent_outputs"].as_array_mut().unwrap();
+            if !dependents.contains(&json!(output)) {
+                dependents.push(json!(output));
+            }
+        }
+    }
+    Ok((good, node_errors))
+}
+
+struct PromptValidator<'a> {
+    object_info: &'a ObjectInfo,
+    prompt: &'a Map<String, Value>,
+    errors: BTreeMap<String, Vec<Value>>,
+}
+
+impl PromptValidator<'_> {
+    /// Whether `id` and everything it links to is valid; `upstream` collects
+    /// the ids visited.
+    fn validate(&mut self, id: &str, upstream: &mut Vec<String>) -> bool {
+        if upstream.iter().any(|visited| visited == id) {
+            return self.errors.get(id).is_none_or(Vec::is_empty);
+        }
+        upstream.push(id.to_string());
+
+        let node = &self.prompt[id];
+        let class_type = node["class_type"].as_str().unwrap_or_default();
+        let definition = self.object_info.get(class_type).unwrap();
+        let inputs = node.get("inputs").and_then(Value::as_object);
+
+        let mut errors = Vec::new();
+        let mut links = Vec::new();
+        for spec in definition.inputs() {
+            let value = inputs.and_then(|inputs| inputs.get(&spec.name));
+            match value {
+                None if spec.required => errors.push(input_error(
+                    "required_input_missing",
+                    "Required input is missing",
+                    &spec.name,
+                    &spec.name,
+                    None,
+                )),
+                None => {}
+                Some(Value::Array(link)) => match self.check_link(&spec, link) {
+                    Ok(origin) => links.push(origin),
+                    Err(error) => errors.push(error),
+                },
+                Some(value) => errors.extend(check_value(&spec, value)),
+            }
+        }
+
+        let mut valid = errors.is_empty();
+        self.errors.insert(id.to_string(), errors);
+        for origin in links {
+            valid &= self.validate(&origin, upstream);
+        }
+        valid
+    }
+
+    fn check_link(&self, spec: &InputSpec, link: &[Value]) -> Result<String, Value> {
+        let (origin, slot) = match link {
+            [Value::String(origin), slot] => (origin.clone(), slot.as_u64()),
+            [Value::Number(origin), slot] => (origin.to_string(), slot.as_u64()),
+            _ => (String::new(), None),
+        };
+        let (Some(origin_node), Some(slot)) = (self.prompt.get(&origin), slot) else {
+            return Err(input_error(
+                "bad_linked_input",
+                "Bad linked input, must be a length-2 list of [node_id, slot_index]",
+                &format!("{}, {}", spec.name, Value::Array(link.to_vec())),
+                &spec.name,
+                None,
+            ));
+        };
+
+        let origin_class = origin_node["class_type"].as_str().unwrap_or_default();
+        let received = self
+            .object_info
+            .get(origin_class)
+            .and_then(|d| {
+                d.outputs()
+                    .get(slot as usize)
+                    .map(|o| o.output_type.clone())
+            })
+            .unwrap_or_default();
+        if !types_match(&received, &spec.input_type) {
+            return Err(input_error(
+                "return_type_mismatch",
+                "Return type mismatch between linked nodes",
+                &format!(
+                    "{}, received_type({}) mismatch input_type({})",
+                    spec.name, received, spec.input_type
+                ),
+                &spec.name,
+                None,
+            ));
+        }
+        Ok(origin)
+    }
+}
+
+fn check_value(spec: &InputSpec, value: &Value) -> Vec<Value> {
+    let error = |kind: &str, message: String, details: String| {
+        input_error(kind, &message, &details, &spec.name, Some(value))
+    };
+
+    match spec.input_type.as_str() {
+        "INT" | "FLOAT" => {
+            let number = match value {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.trim().parse().ok(),
+                Value::Bool(b) => Some(f64::from(u8::from(*b))),
+                _ => None,
+            };
+            let Some(number) = number else {
+                return vec![error(
+                    "invalid_input_type",
+                    format!(
+                        "Failed to convert an input value to a {} value",
+                        spec.input_type
+                    ),
+                    format!("{}, {}", spec.name, display(value)),
+                )];
+            };
+            let mut errors = Vec::new();
+            if let Some(min) = spec.min.filter(|min| number < *min) {
+                errors.push(error(
+                    "value_smaller_than_min",
+                    format!("Value {} smaller than min of {}", display(value), min),
+                    spec.name.clone(),
+                ));
+            }
+            if let Some(max) = spec.max.filter(|max| number > *max) {
+                errors.push(error(
+                    "value_bigger_than_max",
+                    format!("Value {} bigger than max of {}", display(value), max),
+                    spec.name.clone(),
+                ));
+            }
+            errors
+        }
+        "COMBO" if !spec.options.contains(value) => {
+            let options: Vec<String> = spec
+                .options
+                .iter()
+                .map(|o| format!("'{}'", display(o)))
+                .collect();
+            vec![error(
+                "value_not_in_list",
+                "Value not in list".to_string(),
+                format!(
+                    "{}: '{}' not in [{}]",
+                    spec.name,
+                    display(value),
+                    options.join(", ")
+                ),
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// `*` accepts anything; otherwise every received type (comma separated)
+/// must be one of the accepted ones.
+fn types_match(received: &str, expected: &str) -> bool {
+    if received == "*" || expected == "*" {
+        return true;
+    }
+    let expected: Vec<&str> = expected.split(',').map(str::trim).collect();
+    received
+        .split(',')
+        .map(str::trim)
+        .all(|t| expected.contains(&t))
+}
+
+/// Ids of the nodes the outputs depend on, each after its inputs.
+fn execution_order(prompt: &Map<String, Value>, outputs: &[String]) -> Vec<String> {
+    fn visit(
+        prompt: &Map<String, Value>,
+        id: &str,
+        seen: &mut Vec<String>,
+        order: &mut Vec<String>,
+    ) {
+        if seen.iter().any(|s| s == id) {
+            return;
+        }
+        seen.push(id.to_string());
+        let Some(node) = prompt.get(id) else {
+            return;
+        };
+        let inputs = node.get("inputs").and_then(Value::as_object);
+        for origin in inputs
+            .into_iter()
+            .flat_map(|i| i.values())
+            .filter_map(link_origin)
+        {
+            visit(prompt, &origin, seen, order);
+        }
+        order.push(id.to_string());
+    }
+
+    let (mut seen, mut order) = (Vec::new(), Vec::new());
+    for output in outputs {
+        visit(prompt, output, &mut seen, &mut order);
+    }
+    order
+}
+
+fn link_origin(value: &Value) -> Option<String> {
+    match value.as_array()?.as_slice() {
+        [Value::String(origin), slot] if slot.is_u64() => Some(origin.clone()),
+        [Value::Number(origin), slot] if slot.is_u64() => Some(origin.to_string()),
+        _ => None,
+    }
+}
+
+fn prompt_error(kind: &str, message: &str, details: &str) -> Value {
+    json!({ "type": kind, "message": message, "details": details, "extra_info": {} })
+}
+
+fn input_error(
+    kind: &str,
+    message: &str,
+    details: &str,
+    input_name: &str,
+    received: Option<&Value>,
+) -> Value {
+    let mut extra_info = json!({ "input_name": input_name });
+    if let Some(received) = received {
+        extra_info["received_value"] = received.clone();
+    }
+    json!({ "type": kind, "message": message, "details": details, "extra_info": extra_info })
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn prompt_id(number: u64) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    format!(
+        "{:08x}-{:04x}-4{:03x}-8{:03x}-{:012x}",
+        nanos,
+        std::process::id() & 0xffff,
+        (number >> 12) & 0xfff,
+        number & 0xfff,
+        number
+    )
+}
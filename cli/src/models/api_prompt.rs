@@ -0,0 +1,197 @@
+use super::{NodeId, NodeInput, WidgetValues, WorkflowGraph, WorkflowLink, WorkflowNode};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// API ("prompt") format workflow, as posted to `/prompt`:
+/// `{"1": {"class_type": "...", "inputs": {...}}}`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiPrompt {
+    pub nodes: BTreeMap<String, ApiNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiNode {
+    pub class_type: String,
+    #[serde(default)]
+    pub inputs: BTreeMap<String, ApiInput>,
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ApiNodeMeta>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiNodeMeta {
+    pub title: String,
+}
+
+/// An input is either a link `["node_id", output_slot]` or a literal value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ApiInput {
+    Link(String, u32),
+    Value(Value),
+}
+
+impl ApiPrompt {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read prompt file: {}", path.display()))?;
+        let value: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse prompt JSON: {}", path.display()))?;
+        Self::from_value(value).with_context(|| format!("Not an API prompt: {}", path.display()))
+    }
+
+    /// Accepts a bare prompt or a `/prompt` request body (`{"prompt": {...}}`).
+    pub fn from_value(value: Value) -> Result<Self> {
+        let value = match value {
+            Value::Object(mut map)
+                if map.contains_key("prompt") && !map.contains_key("class_type") =>
+            {
+                map.remove("prompt").unwrap_or_default()
+            }
+            other => other,
+        };
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Whether a parsed JSON document looks like an API prompt rather than a
+    /// UI graph.
+    pub fn detect(value: &Value) -> bool {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return false,
+        };
+        let map = match map.get("prompt") {
+            Some(Value::Object(inner)) => inner,
+            _ => map,
+        };
+        !map.is_empty()
+            && !map.contains_key("nodes")
+            && map.values().all(|node| {
+                node.get("class_type")
+                    .map(Value::is_string)
+                    .unwrap_or(false)
+            })
+    }
+
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn to_request_body(&self, client_id: Option<&str>) -> Value {
+        let mut body = json!({ "prompt": self });
+        if let Some(client_id) = client_id {
+            body["client_id"] = json!(client_id);
+        }
+        body
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Node ids in numeric order where possible ("2" before "10", "10:2"
+    /// before "10:10").
+    pub fn sorted_ids(&self) -> Vec<&String> {
+        let mut ids: Vec<_> = self.nodes.keys().collect();
+        ids.sort_by(|a, b| compare_ids(a, b));
+        ids
+    }
+
+    /// Build a UI graph view so API files count toward node types and
+    /// coverage. Literal inputs become a name -> value widget map; layout is
+    /// left empty.
+    pub fn to_graph(&self) -> WorkflowGraph {
+        let mut graph = WorkflowGraph::default();
+        let mut next_link_id = 1;
+
+        for id in self.sorted_ids() {
+            let api_node = &self.nodes[id];
+            let node_id = parse_node_id(id);
+            let mut inputs = Vec::new();
+            let mut widgets = Map::new();
+
+            for (name, input) in &api_node.inputs {
+                match input {
+                    ApiInput::Link(origin, slot) => {
+                        graph.links.push(WorkflowLink {
+                            id: next_link_id,
+                            origin_id: parse_node_id(origin),
+                            origin_slot: *slot,
+                            target_id: node_id.clone(),
+                            target_slot: inputs.len() as u32,
+                            link_type: "*".to_string(),
+                        });
+                        inputs.push(NodeInput {
+                            name: name.clone(),
+                            input_type: "*".to_string(),
+                            link: Some(next_link_id),
+                            ..Default::default()
+                        });
+                        next_link_id += 1;
+                    }
+                    ApiInput::Value(value) => {
+                        widgets.insert(name.clone(), value.clone());
+                    }
+                }
+            }
+
+            graph.nodes.push(WorkflowNode {
+                id: node_id,
+                node_type: api_node.class_type.clone(),
+                title: api_node.meta.as_ref().map(|m| m.title.clone()),
+                inputs,
+                widgets_values: Some(WidgetValues::Map(widgets)),
+                ..Default::default()
+            });
+        }
+
+        graph.last_link_id = Some(next_link_id - 1);
+        graph
+    }
+}
+
+impl ApiNode {
+    pub fn new(class_type: &str) -> Self {
+        Self {
+            class_type: class_type.to_string(),
+            inputs: BTreeMap::new(),
+            meta: None,
+        }
+    }
+
+    pub fn links(&self) -> impl Iterator<Item = (&String, &String, u32)> {
+        self.inputs.iter().filter_map(|(name, input)| match input {
+            ApiInput::Link(origin, slot) => Some((name, origin, *slot)),
+            ApiInput::Value(_) => None,
+        })
+    }
+}
+
+fn parse_node_id(id: &str) -> NodeId {
+    id.parse::<i64>()
+        .map(NodeId::Number)
+        .unwrap_or_else(|_| NodeId::Text(id.to_string()))
+}
+
+/// Subgraph ids like "10:5" compare segment by segment; numeric segments
+/// sort before text ones.
+fn compare_ids(a: &str, b: &str) -> Ordering {
+    let segment = |s: &str| match s.parse::<i64>() {
+        Ok(n) => (0, n, String::new()),
+        Err(_) => (1, 0, s.to_string()),
+    };
+    a.split(':').map(segment).cmp(b.split(':').map(segment))
+}
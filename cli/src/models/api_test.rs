@@ -0,0 +1,112 @@
+use super::{PromptRun, TestOutcome};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTestStatus {
+    Executed,
+    /// Accepted by `/prompt`; execution wasn't waited for.
+    Validated,
+    /// Rejected by `/prompt` or raised while executing.
+    Failed,
+    /// No valid prompt could be built, or it didn't finish in time.
+    Blocked,
+}
+
+impl ApiTestStatus {
+    pub fn outcome(&self) -> TestOutcome {
+        match self {
+            ApiTestStatus::Executed | ApiTestStatus::Validated => TestOutcome::Pass,
+            ApiTestStatus::Failed => TestOutcome::Fail,
+            ApiTestStatus::Blocked => TestOutcome::Blocked,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ApiTestStatus::Executed => "executed",
+            ApiTestStatus::Validated => "validated",
+            ApiTestStatus::Failed => "failed",
+            ApiTestStatus::Blocked => "blocked",
+        }
+    }
+}
+
+impl fmt::Display for ApiTestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeApiResult {
+    pub class_type: String,
+    pub status: ApiTestStatus,
+    pub reason: Option<String>,
+    pub prompt_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<PromptRun>,
+}
+
+impl NodeApiResult {
+    pub fn new(class_type: &str, status: ApiTestStatus, reason: Option<String>) -> Self {
+        Self {
+            class_type: class_type.to_string(),
+            status,
+            reason,
+            prompt_id: None,
+            execution: None,
+        }
+    }
+
+    /// Metadata issues starting with this were written by an API test run
+    /// and are replaced by the next one.
+    pub const ISSUE_PREFIX: &'static str = "API test: ";
+
+    pub fn issue(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .filter(|_| self.status == ApiTestStatus::Failed)
+            .map(|reason| format!("{}{}: {}", Self::ISSUE_PREFIX, self.class_type, reason))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackApiResult {
+    pub pack: String,
+    pub nodes: Vec<NodeApiResult>,
+    /// Executable workflows using the pack, named after their files.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workflows: Vec<NodeApiResult>,
+}
+
+impl PackApiResult {
+    /// Fail if any node or workflow failed, blocked if no node could be
+    /// tested, pass otherwise.
+    pub fn outcome(&self) -> TestOutcome {
+        let workflow_failed = self
+            .workflows
+            .iter()
+            .any(|w| w.status == ApiTestStatus::Failed);
+        if workflow_failed || self.count(ApiTestStatus::Failed) > 0 {
+            TestOutcome::Fail
+        } else if self.count(ApiTestStatus::Blocked) == self.nodes.len() {
+            TestOutcome::Blocked
+        } else {
+            TestOutcome::Pass
+        }
+    }
+
+    pub fn count(&self, status: ApiTestStatus) -> usize {
+        self.nodes.iter().filter(|n| n.status == status).count()
+    }
+
+    pub fn issues(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .chain(&self.workflows)
+            .filter_map(NodeApiResult::issue)
+            .collect()
+    }
+}
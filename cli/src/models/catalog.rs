@@ -0,0 +1,302 @@
+use super::schema::Schema;
+use super::{Export, ObjectInfo, Workflow, DEPRECATED_SUFFIX, REMOVED_SUFFIX};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CATALOG_SCHEMA: Schema<u32> = Schema {
+    kind: "nodes.json",
+    field: "schema_version",
+    unversioned: 1,
+    current: 1,
+    migrations: &[],
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogNode {
+    pub pack: String,
+    pub display_name: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub deprecated: bool,
+    /// No longer in the latest import; kept so old results still resolve.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
+}
+
+/// `checklists/<project>/nodes.json`: every node class seen by `import` or
+/// `catalog import`, keyed by class name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub nodes: BTreeMap<String, CatalogNode>,
+}
+
+/// Which packs a workflow exercises, independent of its filename.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackAttribution {
+    /// Pack name -> number of distinct node types from that pack.
+    pub pack_counts: BTreeMap<String, usize>,
+    pub unknown: Vec<String>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog {
+            schema_version: CATALOG_SCHEMA.current,
+            updated_at: None,
+            nodes: BTreeMap::new(),
+        }
+    }
+}
+
+impl Catalog {
+    pub const FILE_NAME: &'static str = "nodes.json";
+
+    pub fn from_export(export: &Export) -> Self {
+        let nodes = export
+            .packs
+            .iter()
+            .flat_map(|pack| {
+                pack.nodes.iter().map(|node| {
+                    (
+                        node.name.clone(),
+                        CatalogNode {
+                            pack: pack.name.clone(),
+                            display_name: node.display_name.clone(),
+                            category: node.category.clone(),
+                            deprecated: node.deprecated.unwrap_or(false),
+                            removed: false,
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        Catalog {
+            schema_version: CATALOG_SCHEMA.current,
+            updated_at: Some(export.exported_at),
+            nodes,
+        }
+    }
+
+    pub fn from_object_info(object_info: &ObjectInfo) -> Self {
+        let nodes = object_info
+            .nodes
+            .iter()
+            .map(|(class, definition)| {
+                (
+                    class.clone(),
+                    CatalogNode {
+                        pack: definition.pack(),
+                        display_name: definition.display_name.clone(),
+                        category: definition.category.clone(),
+                        deprecated: definition.deprecated,
+                        removed: false,
+                    },
+                )
+            })
+            .collect();
+
+        Catalog {
+            schema_version: CATALOG_SCHEMA.current,
+            updated_at: Some(Utc::now()),
+            nodes,
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read catalog: {}", path.display()))?;
+        let value: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not valid JSON", path.display()))?;
+        let (mut catalog, _): (Catalog, _) = CATALOG_SCHEMA
+            .load(value)
+            .with_context(|| format!("In {}", path.display()))?;
+        catalog.schema_version = CATALOG_SCHEMA.current;
+        Ok(catalog)
+    }
+
+    pub fn for_project(project_dir: &Path) -> Result<Self> {
+        let path = Self::path(project_dir);
+        if path.exists() {
+            Self::from_file(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(Self::FILE_NAME)
+    }
+
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Replace the catalog with a newer import, keeping classes it no longer
+    /// has as removed.
+    pub fn update(&mut self, newer: Catalog) {
+        let mut previous = std::mem::replace(&mut self.nodes, newer.nodes);
+        previous.retain(|class, _| !self.nodes.contains_key(class));
+        for (class, mut node) in previous {
+            node.removed = true;
+            self.nodes.insert(class, node);
+        }
+        self.updated_at = newer.updated_at;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn get(&self, class_name: &str) -> Option<&CatalogNode> {
+        self.nodes.get(class_name)
+    }
+
+    pub fn display_name<'a>(&'a self, class_name: &'a str) -> &'a str {
+        self.get(class_name)
+            .and_then(|node| node.display_name.as_deref())
+            .unwrap_or(class_name)
+    }
+
+    /// How checklists list a class: its display name, suffixed when
+    /// deprecated.
+    pub fn checklist_name(&self, class_name: &str) -> String {
+        let name = self.display_name(class_name);
+        if self.get(class_name).is_some_and(|node| node.deprecated) {
+            format!("{}{}", name, DEPRECATED_SUFFIX)
+        } else {
+            name.to_string()
+        }
+    }
+
+    pub fn pack_of(&self, class_name: &str) -> Option<&str> {
+        self.get(class_name).map(|node| node.pack.as_str())
+    }
+
+    /// Add the node types of all-nodes workflows that the catalog doesn't
+    /// know yet. Each such workflow contains every node of its pack, so
+    /// together they stand in for an import.
+    pub fn with_workflows<'a, I>(mut self, workflows: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Workflow>,
+    {
+        let mut workflows: Vec<_> = workflows
+            .into_iter()
+            .filter(|w| w.pack_name != Workflow::UNKNOWN_PACK)
+            .collect();
+        workflows.sort_by(|a, b| a.pack_name.cmp(&b.pack_name));
+
+        for workflow in workflows {
+            for node_type in workflow.get_unique_node_types() {
+                self.nodes.entry(node_type).or_insert_with(|| CatalogNode {
+                    pack: workflow.pack_name.clone(),
+                    display_name: None,
+                    category: None,
+                    deprecated: false,
+                    removed: false,
+                });
+            }
+        }
+        self
+    }
+
+    pub fn attribute(&self, workflow: &Workflow) -> PackAttribution {
+        let mut attribution = PackAttribution::default();
+
+        for node_type in workflow.get_unique_node_types() {
+            match self.pack_of(&node_type) {
+                Some(pack) => *attribution.pack_counts.entry(pack.to_string()).or_default() += 1,
+                None => attribution.unknown.push(node_type),
+            }
+        }
+
+        attribution
+    }
+
+    /// Group workflows by every pack they use, with the per-pack node count.
+    pub fn index_by_pack<'a, I>(&self, workflows: I) -> BTreeMap<String, Vec<(&'a Workflow, usize)>>
+    where
+        I: IntoIterator<Item = &'a Workflow>,
+    {
+        let mut index: BTreeMap<String, Vec<(&'a Workflow, usize)>> = BTreeMap::new();
+
+        for workflow in workflows {
+            for (pack, count) in self.attribute(workflow).pack_counts {
+                index.entry(pack).or_default().push((workflow, count));
+            }
+        }
+
+        index
+    }
+
+    /// The class whose class or display name is `name`, ignoring case.
+    pub fn find(&self, name: &str) -> Option<(&str, &CatalogNode)> {
+        self.nodes
+            .iter()
+            .find(|(class, node)| {
+                class.eq_ignore_ascii_case(name)
+                    || node
+                        .display_name
+                        .as_deref()
+                        .is_some_and(|d| d.eq_ignore_ascii_case(name))
+            })
+            .map(|(class, node)| (class.as_str(), node))
+    }
+
+    /// The class a detailed checklist item names, ignoring the removed and
+    /// deprecated suffixes.
+    pub fn resolve(&self, item: &str) -> Option<(&str, &CatalogNode)> {
+        let name = item
+            .trim_end_matches(REMOVED_SUFFIX)
+            .trim_end_matches(DEPRECATED_SUFFIX);
+        match self.nodes.get_key_value(name) {
+            Some((class, node)) => Some((class.as_str(), node)),
+            None => self.find(name),
+        }
+    }
+
+    /// Whether the detailed checklist item `item` is the class `class_name`.
+    pub fn lists(&self, item: &str, class_name: &str) -> bool {
+        let name = item
+            .trim_end_matches(REMOVED_SUFFIX)
+            .trim_end_matches(DEPRECATED_SUFFIX);
+        name == class_name
+            || self
+                .resolve(item)
+                .is_some_and(|(class, _)| class == class_name)
+    }
+
+    pub fn pack_nodes<'a>(
+        &'a self,
+        pack: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a CatalogNode)> + 'a {
+        self.nodes
+            .iter()
+            .filter(move |(_, node)| node.pack == pack)
+            .map(|(class, node)| (class.as_str(), node))
+    }
+}
+
+impl PackAttribution {
+    pub fn packs(&self) -> impl Iterator<Item = &str> {
+        self.pack_counts.keys().map(|s| s.as_str())
+    }
+
+    pub fn primary_pack(&self) -> Option<&str> {
+        self.pack_counts
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(pack, _)| pack.as_str())
+    }
+}
@@ -1,7 +1,7 @@
-use super::NodePack;
+use super::{ChecklistDocument, NodePack, TestDimension, TestDimensions, TestOutcome};
 use anyhow::{Context, Result};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,11 +10,65 @@ pub struct Checklist {
     pub file_path: PathBuf,
     pub project_name: String,
     pub packs: Vec<NodePack>,
+    /// The file as read, so writing keeps everything but the pack tokens.
+    pub document: ChecklistDocument,
 }
 
 #[derive(Debug, Clone)]
 pub struct DetailedChecklist {
-    pub packs: HashMap<String, Vec<String>>, // pack_name -> vec of node names
+    pub packs: HashMap<String, Vec<ChecklistNode>>, // pack_name -> nodes in file order
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistNode {
+    pub name: String,
+    pub outcome: TestOutcome,
+    pub dimensions: TestDimensions,
+}
+
+impl ChecklistNode {
+    /// Matches `- [x] NodeName` items, with an optional trailing
+    /// `<!-- marker -->`.
+    pub(crate) fn item_regex() -> Regex {
+        Regex::new(&format!(
+            r"^-\s+\[({})\]\s+(.+?)\s*(?:<!--(.*?)-->)?\s*$",
+            TestOutcome::MARKER_CLASS
+        ))
+        .unwrap()
+    }
+
+    pub(crate) fn parse(re: &Regex, line: &str) -> Option<Self> {
+        let caps = re.captures(line.trim_end_matches('\r'))?;
+        Some(Self {
+            name: caps[2].trim().to_string(),
+            outcome: TestOutcome::from_marker(&caps[1]).unwrap_or_default(),
+            dimensions: caps
+                .get(3)
+                .and_then(|m| TestDimensions::parse_marker(m.as_str()))
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn dimension_outcome(&self, dimension: TestDimension) -> TestOutcome {
+        self.dimensions.resolve(dimension, self.outcome)
+    }
+
+    /// Record one dimension's result, as `NodePack::set_dimension_outcome`.
+    pub fn set_dimension_outcome(&mut self, dimension: TestDimension, outcome: TestOutcome) {
+        self.outcome = self.dimensions.record(dimension, self.outcome, outcome);
+    }
+
+    pub fn tested(&self) -> bool {
+        self.outcome.is_tested()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let line = format!("- [{}] {}", self.outcome.marker(), self.name);
+        match self.dimensions.to_marker() {
+            Some(marker) => format!("{} <!-- {} -->", line, marker),
+            None => line,
+        }
+    }
 }
 
 impl Checklist {
@@ -30,44 +84,36 @@ impl Checklist {
             .unwrap_or("unknown")
             .to_string();
 
-        let packs = Self::parse_markdown(&contents)?;
+        let document = ChecklistDocument::parse(&contents);
 
         Ok(Checklist {
             file_path: path.to_path_buf(),
             project_name,
-            packs,
+            packs: document.packs(),
+            document,
         })
     }
 
-    fn parse_markdown(contents: &str) -> Result<Vec<NodePack>> {
-        let re = Regex::new(r"^- \[([ x])\] (.+?) \((\d+)\)")?;
-        let mut packs = Vec::new();
-
-        for line in contents.lines() {
-            if let Some(caps) = re.captures(line) {
-                let tested = &caps[1] == "x";
-                let name = caps[2].trim().to_string();
-                let node_count = caps[3].parse::<usize>()?;
-
-                packs.push(NodePack::new(name, node_count, tested));
-            }
+    pub fn outcome_counts(&self) -> BTreeMap<TestOutcome, usize> {
+        let mut counts = BTreeMap::new();
+        for pack in &self.packs {
+            *counts.entry(pack.outcome).or_default() += 1;
         }
+        counts
+    }
 
-        Ok(packs)
+    pub fn dimension_progress(&self, dimension: TestDimension) -> (usize, usize) {
+        let tested = self
+            .packs
+            .iter()
+            .filter(|p| p.dimension_outcome(dimension).is_tested())
+            .count();
+        (tested, self.packs.len())
     }
 
+    /// The original file with current pack states and counts applied.
     pub fn to_markdown(&self) -> String {
-        let mut lines = vec!["# Node Pack QA Checklist".to_string(), String::new()];
-
-        for pack in &self.packs {
-            let checkbox = if pack.tested { "x" } else { " " };
-            lines.push(format!(
-                "- [{}] {} ({})",
-                checkbox, pack.name, pack.node_count
-            ));
-        }
-
-        lines.join("\n")
+        self.document.render(&self.packs)
     }
 
     pub fn write(&self) -> Result<()> {
@@ -82,34 +128,113 @@ impl DetailedChecklist {
         let path = path.as_ref();
         let contents = fs::read_to_string(path)
             .with_context(|| format!("Failed to read checklist: {}", path.display()))?;
+        Ok(Self::parse(&contents))
+    }
 
-        let mut packs: HashMap<String, Vec<String>> = HashMap::new();
+    pub fn parse(contents: &str) -> Self {
+        let mut packs: HashMap<String, Vec<ChecklistNode>> = HashMap::new();
         let mut current_pack: Option<String> = None;
 
-        // Regex to match pack headers: ## PackName
-        let pack_header_re = Regex::new(r"^##\s+(.+)$")?;
-        // Regex to match node items: - [ ] NodeName or - [x] NodeName
-        let node_re = Regex::new(r"^-\s+\[[x ]\]\s+(.+)$")?;
+        let pack_header_re = Self::header_regex();
+        let node_re = ChecklistNode::item_regex();
 
         for line in contents.lines() {
             if let Some(caps) = pack_header_re.captures(line) {
                 let pack_name = caps[1].trim().to_string();
                 current_pack = Some(pack_name.clone());
                 packs.entry(pack_name).or_default();
-            } else if let Some(caps) = node_re.captures(line) {
-                if let Some(pack_name) = &current_pack {
-                    let node_name = caps[1].trim().to_string();
-                    if let Some(nodes) = packs.get_mut(pack_name) {
-                        nodes.push(node_name);
-                    }
+            } else if let Some(node) = ChecklistNode::parse(&node_re, line) {
+                if let Some(nodes) = current_pack.as_ref().and_then(|p| packs.get_mut(p)) {
+                    nodes.push(node);
                 }
             }
         }
 
-        Ok(DetailedChecklist { packs })
+        DetailedChecklist { packs }
+    }
+
+    pub(crate) fn header_regex() -> Regex {
+        Regex::new(r"^##\s+(.+?)\s*$").unwrap()
+    }
+
+    /// Rewrite the items of `nodes`, matched by name under `## pack_name`,
+    /// in the file at `path`. Every other line is left as it is.
+    pub fn write_nodes<P: AsRef<Path>>(
+        path: P,
+        pack_name: &str,
+        nodes: &[ChecklistNode],
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checklist: {}", path.display()))?;
+
+        let pack_header_re = Self::header_regex();
+        let node_re = ChecklistNode::item_regex();
+
+        let mut in_pack = false;
+        let lines: Vec<String> = contents
+            .split('\n')
+            .map(|line| {
+                if let Some(caps) = pack_header_re.captures(line) {
+                    in_pack = caps[1].trim() == pack_name;
+                    return line.to_string();
+                }
+                let updated = in_pack
+                    .then(|| ChecklistNode::parse(&node_re, line))
+                    .flatten()
+                    .and_then(|item| nodes.iter().find(|n| n.name == item.name));
+                match updated {
+                    Some(node) if line.ends_with('\r') => node.to_markdown() + "\r",
+                    Some(node) => node.to_markdown(),
+                    None => line.to_string(),
+                }
+            })
+            .collect();
+
+        fs::write(path, lines.join("\n"))
+            .with_context(|| format!("Failed to write {}", path.display()))
     }
 
-    pub fn get_nodes(&self, pack_name: &str) -> Option<&Vec<String>> {
+    pub fn get_nodes(&self, pack_name: &str) -> Option<&Vec<ChecklistNode>> {
         self.packs.get(pack_name)
     }
+
+    pub fn node_names(&self, pack_name: &str) -> Vec<String> {
+        self.get_nodes(pack_name)
+            .map(|nodes| nodes.iter().map(|n| n.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn pack_progress(&self, pack_name: &str) -> (usize, usize) {
+        self.get_nodes(pack_name)
+            .map(|nodes| (nodes.iter().filter(|n| n.tested()).count(), nodes.len()))
+            .unwrap_or((0, 0))
+    }
+
+    pub fn outcome_counts(&self) -> BTreeMap<TestOutcome, usize> {
+        let mut counts = BTreeMap::new();
+        for node in self.packs.values().flatten() {
+            *counts.entry(node.outcome).or_default() += 1;
+        }
+        counts
+    }
+
+    pub fn dimension_progress(&self, dimension: TestDimension) -> (usize, usize) {
+        self.packs
+            .values()
+            .flatten()
+            .fold((0, 0), |(tested, total), node| {
+                let tested_here = node.dimension_outcome(dimension).is_tested();
+                (tested + tested_here as usize, total + 1)
+            })
+    }
+
+    pub fn progress(&self) -> (usize, usize) {
+        self.packs
+            .values()
+            .flatten()
+            .fold((0, 0), |(tested, total), node| {
+                (tested + node.tested() as usize, total + 1)
+            })
+    }
 }
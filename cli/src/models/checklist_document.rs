@@ -0,0 +1,237 @@
+use super::{NodePack, TestDimensions, TestOutcome};
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
+
+pub const DEFAULT_HEADER: &str = "# Node Pack QA Checklist";
+
+/// A checklist file as written by testers. Pack lines are recognized; every
+/// other line (notes, sub-bullets, comments, extra sections) is kept byte for
+/// byte, so writing back only touches the checkbox and count tokens.
+#[derive(Debug, Clone, Default)]
+pub struct ChecklistDocument {
+    lines: Vec<DocumentLine>,
+    crlf: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum DocumentLine {
+    Pack(PackLine),
+    Other(String),
+}
+
+/// A `- [x] Name (count)` line, optionally followed by a dimension marker
+/// such as `<!-- API tested -->`, with the byte ranges of the tokens the
+/// tool owns.
+#[derive(Debug, Clone)]
+pub struct PackLine {
+    pub raw: String,
+    pub pack: NodePack,
+    checkbox: Range<usize>,
+    count: Range<usize>,
+    marker: Option<Range<usize>>,
+}
+
+impl ChecklistDocument {
+    pub fn parse(contents: &str) -> Self {
+        let re = Regex::new(&format!(
+            r"^- \[({})\] (.+?) \((\d+)\)",
+            TestOutcome::MARKER_CLASS
+        ))
+        .unwrap();
+        let marker_re = Regex::new(r"<!--(.*?)-->").unwrap();
+        let crlf = contents.contains("\r\n");
+
+        // Splitting on '\n' keeps '\r' and a final empty entry for the
+        // trailing newline, so joining restores the file exactly
+        let lines = contents
+            .split('\n')
+            .map(|line| match PackLine::parse(&re, &marker_re, line) {
+                Some(pack_line) => DocumentLine::Pack(pack_line),
+                None => DocumentLine::Other(line.to_string()),
+            })
+            .collect();
+
+        Self { lines, crlf }
+    }
+
+    pub fn lines(&self) -> &[DocumentLine] {
+        &self.lines
+    }
+
+    pub fn packs(&self) -> Vec<NodePack> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                DocumentLine::Pack(pack_line) => Some(pack_line.pack.clone()),
+                DocumentLine::Other(_) => None,
+            })
+            .collect()
+    }
+
+    /// Render with `packs` applied. Existing lines keep their text apart from
+    /// the checkbox and count; packs no longer listed are dropped and new
+    /// ones are added after the last pack line.
+    pub fn render(&self, packs: &[NodePack]) -> String {
+        let mut used = HashSet::new();
+        let mut claim = |name: &str| {
+            let index = packs
+                .iter()
+                .enumerate()
+                .position(|(i, p)| p.name == name && !used.contains(&i))?;
+            used.insert(index);
+            Some(&packs[index])
+        };
+
+        let last_pack_line = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, DocumentLine::Pack(_)));
+
+        let mut out: Vec<String> = Vec::new();
+        let mut insert_at = None;
+
+        for (i, line) in self.lines.iter().enumerate() {
+            match line {
+                DocumentLine::Pack(pack_line) => {
+                    if let Some(pack) = claim(&pack_line.pack.name) {
+                        out.push(pack_line.render(pack));
+                    }
+                }
+                DocumentLine::Other(text) => out.push(text.clone()),
+            }
+            if Some(i) == last_pack_line {
+                insert_at = Some(out.len());
+            }
+        }
+
+        let eol = if self.crlf { "\r" } else { "" };
+        let new_lines: Vec<String> = packs
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used.contains(i))
+            .map(|(_, pack)| format!("{}{}", PackLine::format(pack), eol))
+            .collect();
+
+        if !new_lines.is_empty() {
+            let at = match insert_at {
+                Some(at) => at,
+                None if self.is_blank() => {
+                    out = vec![DEFAULT_HEADER.to_string(), String::new()];
+                    out.len()
+                }
+                // No pack lines yet: append, before the trailing newline
+                None => match out.last() {
+                    Some(last) if last.is_empty() => out.len() - 1,
+                    _ => out.len(),
+                },
+            };
+            out.splice(at..at, new_lines);
+        }
+
+        out.join("\n")
+    }
+
+    fn is_blank(&self) -> bool {
+        self.lines.iter().all(|line| match line {
+            DocumentLine::Other(text) => text.trim().is_empty(),
+            DocumentLine::Pack(_) => false,
+        })
+    }
+}
+
+impl PackLine {
+    fn parse(re: &Regex, marker_re: &Regex, line: &str) -> Option<Self> {
+        let caps = re.captures(line)?;
+        let checkbox = caps.get(1)?;
+        let count = caps.get(3)?;
+
+        let mut pack = NodePack::with_outcome(
+            caps[2].trim().to_string(),
+            count.as_str().parse().ok()?,
+            TestOutcome::from_marker(checkbox.as_str())?,
+        );
+
+        // First comment after the count that reads as a dimension marker
+        let mut marker = None;
+        for comment in marker_re.captures_iter(&line[count.end()..]) {
+            if let Some(dimensions) = TestDimensions::parse_marker(&comment[1]) {
+                let range = comment.get(0)?.range();
+                pack.dimensions = dimensions;
+                marker = Some(count.end() + range.start..count.end() + range.end);
+                break;
+            }
+        }
+
+        Some(Self {
+            raw: line.to_string(),
+            pack,
+            checkbox: checkbox.range(),
+            count: count.range(),
+            marker,
+        })
+    }
+
+    fn format(pack: &NodePack) -> String {
+        let line = format!(
+            "- [{}] {} ({})",
+            pack.outcome.marker(),
+            pack.name,
+            pack.node_count
+        );
+        match pack.dimensions.to_marker() {
+            Some(marker) => format!("{} <!-- {} -->", line, marker),
+            None => line,
+        }
+    }
+
+    fn render(&self, pack: &NodePack) -> String {
+        // Keep the original marker (e.g. `X`) when the outcome is unchanged
+        let outcome = pack.outcome;
+        let checkbox = if outcome == self.pack.outcome {
+            &self.raw[self.checkbox.clone()]
+        } else {
+            outcome.marker()
+        };
+
+        let line = format!(
+            "{}{}{}{}",
+            &self.raw[..self.checkbox.start],
+            checkbox,
+            &self.raw[self.checkbox.end..self.count.start],
+            pack.node_count,
+        );
+        if pack.dimensions == self.pack.dimensions {
+            return line + &self.raw[self.count.end..];
+        }
+
+        let marker = pack
+            .dimensions
+            .to_marker()
+            .map(|marker| format!("<!-- {} -->", marker));
+        match (&self.marker, marker) {
+            (Some(range), Some(marker)) => format!(
+                "{}{}{}{}",
+                line,
+                &self.raw[self.count.end..range.start],
+                marker,
+                &self.raw[range.end..]
+            ),
+            (Some(range), None) => format!(
+                "{}{}{}",
+                line,
+                self.raw[self.count.end..range.start].trim_end(),
+                &self.raw[range.end..]
+            ),
+            (None, Some(marker)) => {
+                let rest = &self.raw[self.count.end..];
+                let (text, eol) = match rest.strip_suffix('\r') {
+                    Some(text) => (text, "\r"),
+                    None => (rest, ""),
+                };
+                format!("{}{} {}{}", line, text.trim_end(), marker, eol)
+            }
+            (None, None) => line + &self.raw[self.count.end..],
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use super::{
+    Catalog, Checklist, ChecklistNode, DetailedChecklist, ExportPack, Metadata, NodePack,
+    PackMetadata, TestDimensions, TestOutcome, Workflow, DEFAULT_HEADER,
+};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+pub const REMOVED_SUFFIX: &str = " ~~REMOVED~~";
+pub const DEPRECATED_SUFFIX: &str = " ~~DEPRECATED~~";
+
+/// Where the current list of a pack's nodes comes from: a workflow file for
+/// sync, a browser export for import.
+pub trait PackSource {
+    fn node_count(&self) -> usize;
+    /// Class names of the pack's nodes, in order.
+    fn node_types(&self) -> Vec<String>;
+}
+
+impl PackSource for Workflow {
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn node_types(&self) -> Vec<String> {
+        self.nodes().iter().map(|n| n.node_type.clone()).collect()
+    }
+}
+
+impl PackSource for ExportPack {
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn node_types(&self) -> Vec<String> {
+        self.nodes.iter().map(|node| node.name.clone()).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeChanges {
+    pub pack: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackChange {
+    Unchanged,
+    CountChanged {
+        from: usize,
+        to: usize,
+    },
+    /// New to the checklist; `restored` is the outcome found in metadata.
+    Added {
+        restored: Option<TestOutcome>,
+    },
+    /// Listed in the checklist but no workflow file has it. Kept as is.
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackSync {
+    pub name: String,
+    pub change: PackChange,
+}
+
+/// Reconciliation of the checklist, workflow files and metadata.
+///
+/// The checklist is the source of truth for results: tested state is never
+/// reset, counts follow the workflow files, packs new to the checklist get
+/// their result back from metadata when it has one, and packs without a
+/// workflow are kept and reported rather than deleted.
+#[derive(Debug, Clone)]
+pub struct ChecklistMerge {
+    pub packs: Vec<NodePack>,
+    pub changes: Vec<PackSync>,
+    pub conflicts: Vec<String>,
+    /// Keep nodes that are gone from the source in the detailed checklist,
+    /// suffixed with [`REMOVED_SUFFIX`], instead of dropping them.
+    pub mark_removed: bool,
+    /// Names detailed checklist nodes; without it they are class names.
+    pub catalog: Catalog,
+}
+
+impl ChecklistMerge {
+    pub fn reconcile<S: PackSource>(
+        checklist: &Checklist,
+        workflows: &HashMap<String, S>,
+        metadata: Option<&Metadata>,
+    ) -> Self {
+        let mut packs = Vec::new();
+        let mut changes = Vec::new();
+        let mut conflicts = Vec::new();
+
+        for pack in &checklist.packs {
+            let mut merged = pack.clone();
+            let change = match workflows.get(&pack.name) {
+                Some(workflow) if workflow.node_count() != pack.node_count => {
+                    merged.node_count = workflow.node_count();
+                    PackChange::CountChanged {
+                        from: pack.node_count,
+                        to: workflow.node_count(),
+                    }
+                }
+                Some(_) => PackChange::Unchanged,
+                None => PackChange::Missing,
+            };
+
+            if let Some(recorded) = metadata
+                .and_then(|m| m.packs.get(&pack.name))
+                .map(|p| p.outcome())
+            {
+                let outcome = pack.outcome;
+                if recorded != outcome {
+                    conflicts.push(format!(
+                        "{}: checklist says {}, metadata says {} (keeping checklist)",
+                        pack.name, outcome, recorded
+                    ));
+                }
+            }
+
+            changes.push(PackSync {
+                name: pack.name.clone(),
+                change,
+            });
+            packs.push(merged);
+        }
+
+        let mut new_workflows: Vec<_> = workflows
+            .iter()
+            .filter(|(name, _)| !checklist.packs.iter().any(|p| &p.name == *name))
+            .collect();
+        new_workflows.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (name, workflow) in new_workflows {
+            let restored = metadata
+                .and_then(|m| m.packs.get(name))
+                .map(|p| p.outcome())
+                .filter(|o| o.is_tested());
+
+            packs.push(NodePack::with_outcome(
+                name.clone(),
+                workflow.node_count(),
+                restored.unwrap_or_default(),
+            ));
+            changes.push(PackSync {
+                name: name.clone(),
+                change: PackChange::Added { restored },
+            });
+        }
+
+        Self {
+            packs,
+            changes,
+            conflicts,
+            mark_removed: false,
+            catalog: Catalog::default(),
+        }
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &str> {
+        self.changes
+            .iter()
+            .filter(|c| c.change == PackChange::Missing)
+            .map(|c| c.name.as_str())
+    }
+
+    pub fn is_unchanged(&self) -> bool {
+        self.changes
+            .iter()
+            .all(|c| c.change == PackChange::Unchanged)
+    }
+
+    /// Edits `existing` in place, so notes, sub-bullets and headings are
+    /// kept. Packs without a section get one at the end.
+    pub fn render_detailed<S: PackSource>(
+        &self,
+        existing: Option<&str>,
+        workflows: &HashMap<String, S>,
+    ) -> String {
+        let contents = existing.unwrap_or_default();
+        let header_re = DetailedChecklist::header_regex();
+        let item_re = ChecklistNode::item_regex();
+        let eol = if contents.contains("\r\n") { "\r" } else { "" };
+
+        let lines: Vec<&str> = contents.split('\n').collect();
+        let is_header = |line: &str| header_re.is_match(line.trim_end_matches('\r'));
+        let mut out: Vec<String> = Vec::new();
+        let mut rendered = HashSet::new();
+
+        let mut i = 0;
+        while i < lines.len() {
+            out.push(lines[i].to_string());
+            let header = header_re
+                .captures(lines[i].trim_end_matches('\r'))
+                .map(|caps| caps[1].trim().to_string());
+            i += 1;
+            let Some(name) = header else {
+                continue;
+            };
+
+            let end = lines[i..]
+                .iter()
+                .position(|line| is_header(line))
+                .map_or(lines.len(), |p| i + p);
+            let section = &lines[i..end];
+            i = end;
+
+            match self.packs.iter().find(|p| p.name == name) {
+                Some(pack) if rendered.insert(name) => {
+                    out.extend(self.render_section(
+                        section,
+                        workflows.get(&pack.name),
+                        &item_re,
+                        eol,
+                    ));
+                }
+                _ => out.extend(section.iter().map(|line| line.to_string())),
+            }
+        }
+
+        let new_sections: Vec<_> = self
+            .packs
+            .iter()
+            .filter(|pack| !rendered.contains(&pack.name))
+            .filter_map(|pack| Some((pack, workflows.get(&pack.name)?)))
+            .collect();
+        if new_sections.is_empty() {
+            return out.join("\n");
+        }
+
+        if contents.trim().is_empty() {
+            out = vec![format!("{}{}", DEFAULT_HEADER, eol), String::new()];
+        }
+        // Blank line before each section, reusing the trailing newline
+        for (pack, workflow) in new_sections {
+            match out.last_mut() {
+                Some(last) if last.is_empty() => *last = eol.to_string(),
+                _ => out.push(eol.to_string()),
+            }
+            out.push(format!("## {}{}", pack.name, eol));
+            out.push(eol.to_string());
+            for name in self.node_names(workflow) {
+                out.push(format!("{}{}", untested(name).to_markdown(), eol));
+            }
+            out.push(String::new());
+        }
+
+        out.join("\n")
+    }
+
+    fn render_section<S: PackSource>(
+        &self,
+        section: &[&str],
+        source: Option<&S>,
+        item_re: &Regex,
+        eol: &str,
+    ) -> Vec<String> {
+        let items: Vec<(usize, ChecklistNode)> = section
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| Some((i, ChecklistNode::parse(item_re, line)?)))
+            .collect();
+
+        // Source names matched to existing items; new names go in before
+        // the item of the next matched name, or after the last item
+        let mut current: Vec<Option<String>> = vec![None; items.len()];
+        let mut insert_before: HashMap<usize, Vec<String>> = HashMap::new();
+        let mut append = Vec::new();
+        match source {
+            Some(source) => {
+                let mut pending = Vec::new();
+                for (class_type, name) in self.nodes_of(source) {
+                    let found = items.iter().enumerate().position(|(k, (_, node))| {
+                        current[k].is_none() && self.is_node(&node.name, &class_type, &name)
+                    });
+                    match found {
+                        Some(k) => {
+                            current[k] = Some(name);
+                            insert_before.entry(k).or_default().append(&mut pending);
+                        }
+                        None => pending.push(name),
+                    }
+                }
+                append = pending;
+            }
+            // Nothing to compare against: every item stays
+            None if !self.mark_removed => return section.iter().map(|s| s.to_string()).collect(),
+            None => {}
+        }
+
+        let mut out = Vec::new();
+        let mut insert_at = None;
+        let mut in_item = false;
+        let mut dropping = false;
+        let mut next_item = 0;
+        for (i, line) in section.iter().enumerate() {
+            let item = items
+                .get(next_item)
+                .filter(|(at, _)| *at == i)
+                .map(|(_, node)| node);
+            let Some(node) = item else {
+                // Indented lines (sub-bullets, notes) belong to the item above
+                let continues = in_item && line.starts_with([' ', '\t']) && !line.trim().is_empty();
+                if continues && dropping {
+                    continue;
+                }
+                out.push(line.to_string());
+                if continues {
+                    insert_at = Some(out.len());
+                } else {
+                    in_item = false;
+                }
+                continue;
+            };
+
+            let k = next_item;
+            next_item += 1;
+            for name in insert_before.remove(&k).unwrap_or_default() {
+                out.push(format!("{}{}", untested(name).to_markdown(), eol));
+            }
+
+            let updated = match current[k].take() {
+                Some(name) => ChecklistNode {
+                    name,
+                    ..node.clone()
+                },
+                None if self.mark_removed => mark_removed(node),
+                None => {
+                    dropping = true;
+                    in_item = true;
+                    continue;
+                }
+            };
+            dropping = false;
+            in_item = true;
+            if &updated == node {
+                out.push(line.to_string());
+            } else {
+                let eol = if line.ends_with('\r') { "\r" } else { "" };
+                out.push(format!("{}{}", updated.to_markdown(), eol));
+            }
+            insert_at = Some(out.len());
+        }
+
+        if !append.is_empty() {
+            // Without items, after the blank line below the header
+            let at = insert_at.unwrap_or_else(|| {
+                out.iter()
+                    .position(|line| !line.trim().is_empty())
+                    .unwrap_or(out.len().min(1))
+            });
+            let lines = append
+                .into_iter()
+                .map(|name| format!("{}{}", untested(name).to_markdown(), eol));
+            out.splice(at..at, lines);
+        }
+
+        out
+    }
+
+    /// Nodes each pack gains and loses against the existing detailed
+    /// checklist. Packs new to the checklist and unchanged packs are left
+    /// out.
+    pub fn node_changes<S: PackSource>(
+        &self,
+        existing: &DetailedChecklist,
+        workflows: &HashMap<String, S>,
+    ) -> Vec<NodeChanges> {
+        let mut changes = Vec::new();
+        for pack in &self.packs {
+            let (Some(previous), Some(workflow)) =
+                (existing.get_nodes(&pack.name), workflows.get(&pack.name))
+            else {
+                continue;
+            };
+            let current = self.nodes_of(workflow);
+            let previous: Vec<&str> = previous
+                .iter()
+                .map(|n| n.name.as_str())
+                .filter(|name| !name.ends_with(REMOVED_SUFFIX))
+                .collect();
+
+            let added: Vec<String> = current
+                .iter()
+                .filter(|(class, name)| !previous.iter().any(|p| self.is_node(p, class, name)))
+                .map(|(_, name)| name.clone())
+                .collect();
+            let removed: Vec<String> = previous
+                .iter()
+                .filter(|p| {
+                    !current
+                        .iter()
+                        .any(|(class, name)| self.is_node(p, class, name))
+                })
+                .map(|p| p.to_string())
+                .collect();
+            if !added.is_empty() || !removed.is_empty() {
+                changes.push(NodeChanges {
+                    pack: pack.name.clone(),
+                    added,
+                    removed,
+                });
+            }
+        }
+        changes
+    }
+
+    pub fn node_names<S: PackSource>(&self, source: &S) -> Vec<String> {
+        self.nodes_of(source)
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    fn nodes_of<S: PackSource>(&self, source: &S) -> Vec<(String, String)> {
+        source
+            .node_types()
+            .into_iter()
+            .map(|class| {
+                let name = self.catalog.checklist_name(&class);
+                (class, name)
+            })
+            .collect()
+    }
+
+    /// Whether the existing item is the node `class_type`, listed as `name`.
+    /// Items written under the class name (as sync did before it knew
+    /// display names) match too, and get renamed.
+    fn is_node(&self, existing: &str, class_type: &str, name: &str) -> bool {
+        same_node(existing, name) || same_node(existing, class_type)
+    }
+
+    pub fn apply_to_metadata(&self, metadata: &mut Metadata) {
+        for pack in &self.packs {
+            let entry = metadata
+                .packs
+                .entry(pack.name.clone())
+                .or_insert_with(|| PackMetadata {
+                    node_count: pack.node_count,
+                    tested: false,
+                    outcome: None,
+                    api_outcome: None,
+                    workflow_file: Some(format!("workflows/all-nodes-{}.json", pack.name)),
+                    notes: None,
+                    issues: None,
+                });
+            entry.node_count = pack.node_count;
+            entry.set_outcome(pack.outcome);
+        }
+        metadata.calculate_stats();
+    }
+}
+
+/// Whether a detailed checklist line is the node `name`. The removed and
+/// deprecated suffixes are ignored so a node keeps its result when it comes
+/// back or becomes deprecated.
+fn same_node(existing: &str, name: &str) -> bool {
+    let base = |name: &str| {
+        name.trim_end_matches(REMOVED_SUFFIX)
+            .trim_end_matches(DEPRECATED_SUFFIX)
+            .to_string()
+    };
+    base(existing) == base(name)
+}
+
+fn untested(name: String) -> ChecklistNode {
+    ChecklistNode {
+        name,
+        outcome: TestOutcome::Untested,
+        dimensions: TestDimensions::default(),
+    }
+}
+
+fn mark_removed(node: &ChecklistNode) -> ChecklistNode {
+    let mut node = node.clone();
+    if !node.name.ends_with(REMOVED_SUFFIX) {
+        node.name.push_str(REMOVED_SUFFIX);
+    }
+    node
+}
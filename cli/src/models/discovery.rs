@@ -0,0 +1,216 @@
+use super::Workflow;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// What a workflow file is evidence of, which decides the commands it feeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkflowKind {
+    /// `all-nodes-{pack}.json` from `QA.testPack()`: drives checklist coverage
+    /// (check, diff, sync, validate).
+    AllNodes,
+    /// Hand-built workflows that can actually be queued: drives API testing.
+    Executable,
+    /// Output of `comfy-qa generate`: only used for node search.
+    Generated,
+}
+
+impl WorkflowKind {
+    /// Best guess for a file loaded outside of discovery.
+    pub fn from_path(path: &Path) -> Self {
+        let in_generated = path.components().any(|c| {
+            c.as_os_str()
+                .to_str()
+                .map(|s| s.starts_with("generated-"))
+                .unwrap_or(false)
+        });
+        let is_all_nodes = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .map(|s| s.starts_with("all-nodes-"))
+            .unwrap_or(false);
+
+        if in_generated {
+            WorkflowKind::Generated
+        } else if is_all_nodes {
+            WorkflowKind::AllNodes
+        } else {
+            WorkflowKind::Executable
+        }
+    }
+}
+
+/// One folder under `workflows/` and the files in it that count as `kind`.
+///
+/// Patterns are matched against the path relative to `folder`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSource {
+    pub folder: String,
+    pub kind: WorkflowKind,
+    #[serde(default = "default_include")]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+fn default_include() -> Vec<String> {
+    vec!["**/*.json".to_string()]
+}
+
+/// Discovery rules for a workflows directory, read from
+/// `workflows/discovery.json` when present.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDiscovery {
+    pub sources: Vec<WorkflowSource>,
+}
+
+impl Default for WorkflowDiscovery {
+    fn default() -> Self {
+        Self {
+            sources: vec![
+                WorkflowSource {
+                    folder: ".".to_string(),
+                    kind: WorkflowKind::AllNodes,
+                    include: vec!["**/all-nodes-*.json".to_string()],
+                    exclude: vec!["executable/**".to_string(), "generated-*/**".to_string()],
+                },
+                WorkflowSource {
+                    folder: "executable".to_string(),
+                    kind: WorkflowKind::Executable,
+                    include: default_include(),
+                    exclude: Vec::new(),
+                },
+                WorkflowSource {
+                    folder: "generated-shared".to_string(),
+                    kind: WorkflowKind::Generated,
+                    include: default_include(),
+                    exclude: Vec::new(),
+                },
+                WorkflowSource {
+                    folder: "generated-local".to_string(),
+                    kind: WorkflowKind::Generated,
+                    include: default_include(),
+                    exclude: Vec::new(),
+                },
+            ],
+        }
+    }
+}
+
+impl WorkflowDiscovery {
+    pub const CONFIG_FILE: &'static str = "discovery.json";
+
+    pub fn load<P: AsRef<Path>>(workflows_dir: P) -> Result<Self> {
+        let config_path = workflows_dir.as_ref().join(Self::CONFIG_FILE);
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))
+    }
+
+    /// Folder new workflows of `kind` should be saved to: the first source
+    /// of that kind.
+    pub fn folder_for(&self, kind: WorkflowKind) -> Option<&str> {
+        self.sources
+            .iter()
+            .find(|s| s.kind == kind)
+            .map(|s| s.folder.as_str())
+    }
+
+    /// Paths of all workflow files matched by the sources, tagged with their
+    /// kind. A file matched by several sources belongs to the first one.
+    pub fn find_files<P: AsRef<Path>>(
+        &self,
+        workflows_dir: P,
+    ) -> Result<Vec<(PathBuf, WorkflowKind)>> {
+        let workflows_dir = workflows_dir.as_ref();
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+
+        for source in &self.sources {
+            let root = workflows_dir.join(&source.folder);
+            if !root.is_dir() {
+                continue;
+            }
+
+            let include = build_globset(&source.include)?;
+            let exclude = build_globset(&source.exclude)?;
+
+            let mut matched: Vec<PathBuf> = WalkDir::new(&root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .filter(|e| {
+                    let relative = e.path().strip_prefix(&root).unwrap_or(e.path());
+                    include.is_match(relative) && !exclude.is_match(relative)
+                })
+                .map(|e| e.into_path())
+                .collect();
+            matched.sort();
+
+            for path in matched {
+                if seen.insert(path.clone()) {
+                    files.push((path, source.kind));
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Load every matched workflow, warning about files that fail to parse.
+    pub fn discover<P: AsRef<Path>>(&self, workflows_dir: P) -> Result<Vec<Workflow>> {
+        Ok(Self::load_files(self.find_files(workflows_dir)?))
+    }
+
+    pub fn discover_kind<P: AsRef<Path>>(
+        &self,
+        workflows_dir: P,
+        kind: WorkflowKind,
+    ) -> Result<Vec<Workflow>> {
+        let files = self
+            .find_files(workflows_dir)?
+            .into_iter()
+            .filter(|(_, k)| *k == kind)
+            .collect();
+        Ok(Self::load_files(files))
+    }
+
+    fn load_files(files: Vec<(PathBuf, WorkflowKind)>) -> Vec<Workflow> {
+        let mut workflows = Vec::new();
+
+        for (path, kind) in files {
+            match Workflow::from_file(&path) {
+                Ok(mut workflow) => {
+                    workflow.kind = kind;
+                    workflows.push(workflow);
+                }
+                Err(e) => {
+                    eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
+                }
+            }
+        }
+
+        workflows
+    }
+}
+
+fn build_globset(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            Glob::new(pattern)
+                .with_context(|| format!("Invalid workflow glob pattern '{}'", pattern))?,
+        );
+    }
+    Ok(builder.build()?)
+}
@@ -1,11 +1,32 @@
-use anyhow::Result;
+use super::schema::{Migration, Schema};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::fs;
 use std::path::Path;
 
+/// Versions of the browser script's export:
+///
+/// - `1.0`: first versioned export; `hanzo_studio_version` is `"unknown"`
+///   when the page doesn't report one
+/// - `1.1`: adds `environment.frontend_version`, missing versions are `null`
+pub const EXPORT_SCHEMA: Schema<&str> = Schema {
+    kind: "export",
+    field: "version",
+    unversioned: "1.0",
+    current: "1.1",
+    migrations: &[Migration {
+        from: "1.0",
+        to: "1.1",
+        apply: migrate_1_0_to_1_1,
+    }],
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Export {
+    /// Always the current version once loaded.
+    #[serde(default)]
     pub version: String,
     pub exported_at: DateTime<Utc>,
     pub project_name: String,
@@ -18,6 +39,8 @@ pub struct ExportEnvironment {
     pub url: String,
     pub user_agent: Option<String>,
     pub hanzo_studio_version: Option<String>,
+    #[serde(default)]
+    pub frontend_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,8 +60,30 @@ pub struct ExportNode {
 
 impl Export {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let contents = fs::read_to_string(path)?;
-        let export: Export = serde_json::from_str(&contents)?;
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read export: {}", path.display()))?;
+        Self::from_json(&contents)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(contents).context("Export is not valid JSON")?;
+        let (mut export, _): (Export, _) = EXPORT_SCHEMA.load(value)?;
+        export.version = EXPORT_SCHEMA.current.to_string();
         Ok(export)
     }
 }
+
+fn migrate_1_0_to_1_1(export: &mut Map<String, Value>) {
+    let Some(environment) = export.get_mut("environment").and_then(Value::as_object_mut) else {
+        return;
+    };
+    if environment
+        .get("hanzo_studio_version")
+        .and_then(Value::as_str)
+        == Some("unknown")
+    {
+        environment.insert("hanzo_studio_version".to_string(), Value::Null);
+    }
+    environment.entry("frontend_version").or_insert(Value::Null);
+}
@@ -0,0 +1,438 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// One `- [x] Node: description` item of a findings file, with the notes
+/// indented under it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub pack: Option<String>,
+    /// Text before the first `: `, when the item names a node.
+    pub node: Option<String>,
+    pub description: String,
+    pub resolved: bool,
+    /// Who answered, from a `Name - ...` note.
+    pub responder: Option<String>,
+    pub response: Option<String>,
+    pub pr_urls: Vec<String>,
+    /// Lines between this item and the next, trimmed, blanks dropped.
+    pub notes: Vec<String>,
+    /// 1-based line of the checkbox item.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FindingsFile {
+    pub file_path: PathBuf,
+    pub title: Option<String>,
+    /// From a `round<N>` in the file name.
+    pub round: Option<u32>,
+    pub findings: Vec<Finding>,
+    /// The file as read. `add` and `resolve` patch it so `write` leaves
+    /// everything else as the tester wrote it.
+    lines: Vec<String>,
+}
+
+/// Node names longer than this are taken as prose that happens to contain
+/// a colon.
+const MAX_NODE_NAME_LEN: usize = 80;
+
+impl Finding {
+    pub fn new(pack: Option<String>, node: Option<String>, description: String) -> Self {
+        Self {
+            pack,
+            node,
+            description,
+            resolved: false,
+            responder: None,
+            response: None,
+            pr_urls: Vec::new(),
+            notes: Vec::new(),
+            line: 0,
+        }
+    }
+
+    pub fn parse(contents: &str) -> Vec<Finding> {
+        parse_findings(contents).1
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.resolved
+    }
+
+    pub fn title(&self) -> &str {
+        self.node.as_deref().unwrap_or(&self.description)
+    }
+
+    /// GitHub pull request and issue links in the description and notes, in
+    /// order of appearance.
+    pub fn links(&self) -> Vec<String> {
+        let re = Regex::new(r"https://github\.com/[\w.-]+/[\w.-]+/(?:pull|issues)/\d+").unwrap();
+        let mut urls: Vec<String> = Vec::new();
+        for text in std::iter::once(&self.description).chain(&self.notes) {
+            for m in re.find_iter(text) {
+                if !urls.iter().any(|u| u == m.as_str()) {
+                    urls.push(m.as_str().to_string());
+                }
+            }
+        }
+        urls
+    }
+
+    fn pull_requests(&self) -> Vec<String> {
+        self.links()
+            .into_iter()
+            .filter(|url| url.contains("/pull/"))
+            .collect()
+    }
+
+    /// Mark resolved, adding a `Name - note <pr>` note.
+    pub fn resolve(&mut self, responder: &str, note: &str, pr_url: Option<&str>) {
+        let response = match pr_url {
+            Some(url) => format!("{} {}", note, url).trim().to_string(),
+            None => note.trim().to_string(),
+        };
+
+        self.notes.push(format!("{} - {}", responder, response));
+        self.pr_urls = self.pull_requests();
+        self.responder = Some(responder.to_string());
+        self.response = Some(response);
+        self.resolved = true;
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let checkbox = if self.resolved { "x" } else { " " };
+        let mut lines = vec![match &self.node {
+            Some(node) => format!("- [{}] {}: {}", checkbox, node, self.description),
+            None => format!("- [{}] {}", checkbox, self.description),
+        }];
+        if !self.notes.is_empty() {
+            lines.push(String::new());
+            lines.extend(self.notes.iter().map(|note| format!("  {}", note)));
+        }
+        lines.join("\n")
+    }
+}
+
+fn heading_regex() -> Regex {
+    Regex::new(r"^\s*(#{1,6})\s+(.+?)\s*$").unwrap()
+}
+
+fn item_regex() -> Regex {
+    Regex::new(r"^- \[([ xX])\]\s+(.+?)\s*$").unwrap()
+}
+
+fn parse_findings(contents: &str) -> (Option<String>, Vec<Finding>) {
+    let heading_re = heading_regex();
+    let item_re = item_regex();
+    let responder_re = Regex::new(r"^(?:[-*]\s+)?([A-Z][\w.]*)\s+[-–—]\s+(.+)$").unwrap();
+
+    let mut findings: Vec<Finding> = Vec::new();
+    let mut pack: Option<String> = None;
+    let mut title: Option<String> = None;
+
+    for (i, line) in contents.lines().enumerate() {
+        if let Some(caps) = heading_re.captures(line) {
+            let text = caps[2].to_string();
+            // The first top-level heading is the file title
+            if caps[1].len() == 1 && title.is_none() && findings.is_empty() && pack.is_none() {
+                title = Some(text);
+                continue;
+            }
+            pack = Some(text);
+            continue;
+        }
+
+        if let Some(caps) = item_re.captures(line) {
+            let text = caps[2].to_string();
+            let (node, description) = split_node(&text);
+            findings.push(Finding {
+                pack: pack.clone(),
+                node,
+                description,
+                resolved: &caps[1] != " ",
+                responder: None,
+                response: None,
+                pr_urls: Vec::new(),
+                notes: Vec::new(),
+                line: i + 1,
+            });
+            continue;
+        }
+
+        let note = line.trim();
+        let Some(finding) = findings.last_mut() else {
+            continue;
+        };
+        if note.is_empty() {
+            continue;
+        }
+        if finding.responder.is_none() {
+            if let Some(caps) = responder_re.captures(note) {
+                finding.responder = Some(caps[1].to_string());
+                finding.response = Some(caps[2].to_string());
+            }
+        }
+        finding.notes.push(note.to_string());
+    }
+
+    for finding in &mut findings {
+        finding.pr_urls = finding.pull_requests();
+    }
+
+    (title, findings)
+}
+
+impl FindingsFile {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read findings: {}", path.display()))?;
+
+        let round = path.file_stem().and_then(|s| s.to_str()).and_then(|stem| {
+            Regex::new(r"(?i)round\s*(\d+)")
+                .unwrap()
+                .captures(stem)
+                .and_then(|caps| caps[1].parse().ok())
+        });
+
+        let (title, findings) = parse_findings(&contents);
+
+        Ok(FindingsFile {
+            file_path: path.to_path_buf(),
+            title,
+            round,
+            findings,
+            lines: contents.split('\n').map(str::to_string).collect(),
+        })
+    }
+
+    pub fn new_round(dir: &Path, round: u32) -> Self {
+        let title = format!("Findings - Round {}", round);
+        FindingsFile {
+            file_path: dir.join(format!("findings - round{}.md", round)),
+            lines: vec![format!("# {}", title), String::new()],
+            title: Some(title),
+            round: Some(round),
+            findings: Vec::new(),
+        }
+    }
+
+    /// `r2-5` for the fifth finding of round 2; the file stem stands in for
+    /// the round in files without one.
+    pub fn id(&self, index: usize) -> String {
+        let prefix = match self.round {
+            Some(round) => format!("r{}", round),
+            None => self
+                .file_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("findings")
+                .replace(' ', "-"),
+        };
+        format!("{}-{}", prefix, index + 1)
+    }
+
+    /// Add a finding at the end of its pack's section, starting the section
+    /// if needed. Returns its index.
+    pub fn add(&mut self, finding: Finding) -> usize {
+        let heading_re = heading_regex();
+        let item_re = item_regex();
+        let is_blank = |line: &String| line.trim().is_empty();
+        let mut new_lines: Vec<String> =
+            finding.to_markdown().lines().map(str::to_string).collect();
+
+        let at = match self.findings.iter().rposition(|f| f.pack == finding.pack) {
+            Some(last) => {
+                let block = self.block(last);
+                if block.len() > 1 {
+                    new_lines.insert(0, String::new());
+                }
+                block.end
+            }
+            None => {
+                let title_line = self
+                    .title
+                    .as_ref()
+                    .and_then(|_| self.lines.iter().position(|l| heading_re.is_match(l)));
+                let heading = self.lines.iter().enumerate().position(|(i, line)| {
+                    let Some(caps) = heading_re.captures(line) else {
+                        return false;
+                    };
+                    match &finding.pack {
+                        Some(pack) => caps[2] == **pack,
+                        // Packless findings go before the first section
+                        None => Some(i) != title_line,
+                    }
+                });
+                let at = match (heading, &finding.pack) {
+                    (Some(heading), Some(_)) => {
+                        new_lines.insert(0, String::new());
+                        heading + 1
+                    }
+                    (Some(heading), None) => heading,
+                    // A new section at the end, before the final newline
+                    (None, pack) => {
+                        if let Some(pack) = pack {
+                            new_lines.splice(0..0, [format!("## {}", pack), String::new()]);
+                        }
+                        self.lines.len() - usize::from(self.lines.last().is_some_and(is_blank))
+                    }
+                };
+                if at > 0 && !is_blank(&self.lines[at - 1]) && !is_blank(&new_lines[0]) {
+                    new_lines.insert(0, String::new());
+                }
+                at
+            }
+        };
+
+        if self
+            .lines
+            .get(at)
+            .is_some_and(|line| !is_blank(line) && !item_re.is_match(line))
+        {
+            new_lines.push(String::new());
+        }
+        let item_line = at
+            + new_lines
+                .iter()
+                .take_while(|l| !item_re.is_match(l))
+                .count();
+        self.lines.splice(at..at, new_lines);
+
+        let (title, findings) = parse_findings(&self.lines.join("\n"));
+        self.title = title;
+        self.findings = findings;
+        self.findings
+            .iter()
+            .position(|f| f.line == item_line + 1)
+            .unwrap_or(self.findings.len() - 1)
+    }
+
+    /// Resolve finding `index` (see [`Finding::resolve`]), checking its box
+    /// and adding the note below its existing ones.
+    pub fn resolve(&mut self, index: usize, responder: &str, note: &str, pr_url: Option<&str>) {
+        let block = self.block(index);
+        let finding = &mut self.findings[index];
+        finding.resolve(responder, note, pr_url);
+
+        let item = &mut self.lines[block.start];
+        if item.get(3..4) == Some(" ") {
+            item.replace_range(3..4, "x");
+        }
+
+        // Match the indentation of the notes already there
+        let indent = match block.len() {
+            1 => "  ".to_string(),
+            _ => {
+                let last = &self.lines[block.end - 1];
+                last[..last.len() - last.trim_start().len()].to_string()
+            }
+        };
+        let mut new_lines = vec![format!("{}{}", indent, finding.notes.last().unwrap())];
+        if block.len() == 1 {
+            new_lines.insert(0, String::new());
+            if self
+                .lines
+                .get(block.end)
+                .is_some_and(|line| !line.trim().is_empty())
+            {
+                new_lines.push(String::new());
+            }
+        }
+        let added = new_lines.len();
+        self.lines.splice(block.end..block.end, new_lines);
+        for later in &mut self.findings[index + 1..] {
+            later.line += added;
+        }
+    }
+
+    /// The lines of finding `index`: its item and the notes below it,
+    /// without trailing blank lines.
+    fn block(&self, index: usize) -> Range<usize> {
+        let heading_re = heading_regex();
+        let item_re = item_regex();
+        let start = self.findings[index].line - 1;
+        let mut end = start + 1;
+        while end < self.lines.len()
+            && !item_re.is_match(&self.lines[end])
+            && !heading_re.is_match(&self.lines[end])
+        {
+            end += 1;
+        }
+        while end > start + 1 && self.lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        start..end
+    }
+
+    /// The file in the standard layout: title, `## pack` sections in order of
+    /// first appearance, items with their notes indented below.
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![format!("# {}", self.title.as_deref().unwrap_or("Findings"))];
+        lines.push(String::new());
+
+        let mut packs: Vec<Option<&str>> = Vec::new();
+        for finding in &self.findings {
+            if !packs.contains(&finding.pack.as_deref()) {
+                packs.push(finding.pack.as_deref());
+            }
+        }
+
+        for pack in packs {
+            if let Some(pack) = pack {
+                lines.push(format!("## {}", pack));
+                lines.push(String::new());
+            }
+            let mut previous_had_notes = false;
+            for finding in self.findings.iter().filter(|f| f.pack.as_deref() == pack) {
+                if previous_had_notes {
+                    lines.push(String::new());
+                }
+                lines.push(finding.to_markdown());
+                previous_had_notes = !finding.notes.is_empty();
+            }
+            lines.push(String::new());
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn write(&self) -> Result<()> {
+        fs::write(&self.file_path, self.lines.join("\n"))
+            .with_context(|| format!("Failed to write {}", self.file_path.display()))
+    }
+
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<Self>> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+        if !dir.is_dir() {
+            return Ok(files);
+        }
+
+        for entry in fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("md") {
+                files.push(Self::from_file(&path)?);
+            }
+        }
+
+        files.sort_by(|a, b| (a.round, &a.file_path).cmp(&(b.round, &b.file_path)));
+        Ok(files)
+    }
+}
+
+fn split_node(text: &str) -> (Option<String>, String) {
+    match text.split_once(": ") {
+        Some((node, description)) if !node.trim().is_empty() && node.len() <= MAX_NODE_NAME_LEN => {
+            (
+                Some(node.trim().to_string()),
+                description.trim().to_string(),
+            )
+        }
+        _ => (None, text.to_string()),
+    }
+}
@@ -1,18 +1,45 @@
-use anyhow::Result;
+use super::schema::{Migration, Schema};
+use super::{NodeApiResult, PackApiResult, TestOutcome};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
+/// Versions of metadata.json:
+///
+/// - `1`: files without `schema_version`; packs only record `tested`
+/// - `2`: every pack has an `outcome`; adds `stats.outcomes` and
+///   `workflow_roots`
+pub const METADATA_SCHEMA: Schema<u32> = Schema {
+    kind: "metadata.json",
+    field: "schema_version",
+    unversioned: 1,
+    current: 2,
+    migrations: &[Migration {
+        from: 1,
+        to: 2,
+        apply: migrate_1_to_2,
+    }],
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metadata {
+    /// Always the current version once loaded.
+    #[serde(default = "Metadata::current_schema_version")]
+    pub schema_version: u32,
     pub project_name: String,
     pub created_at: DateTime<Utc>,
     pub last_updated: Option<DateTime<Utc>>,
     pub environment: Environment,
     pub packs: HashMap<String, PackMetadata>,
     pub stats: Option<Stats>,
+    /// Folders holding this project's workflows, relative to the repository
+    /// root (e.g. `workflows/cloud`). Empty means the shared `workflows/`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workflow_roots: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +55,13 @@ pub struct Environment {
 pub struct PackMetadata {
     pub node_count: usize,
     pub tested: bool,
+    /// Absent in older files, where `tested` is all there is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<TestOutcome>,
+    /// The last `api-test run`. `outcome` follows the checklist checkbox,
+    /// which keeps a UI result when the API one differs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_outcome: Option<TestOutcome>,
     pub workflow_file: Option<String>,
     pub notes: Option<String>,
     pub issues: Option<Vec<String>>,
@@ -39,12 +73,55 @@ pub struct Stats {
     pub tested_packs: usize,
     pub total_nodes: usize,
     pub completion_percent: f64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub outcomes: BTreeMap<TestOutcome, usize>,
+}
+
+impl PackMetadata {
+    pub fn outcome(&self) -> TestOutcome {
+        self.outcome
+            .unwrap_or_else(|| TestOutcome::from_tested(self.tested))
+    }
+
+    pub fn set_outcome(&mut self, outcome: TestOutcome) {
+        self.outcome = Some(outcome);
+        self.tested = outcome.is_tested();
+    }
+
+    /// Record an API run; `checkbox` is the pack's outcome in checklist.md
+    /// afterwards. Issues from the previous run are replaced.
+    pub fn record_api_result(&mut self, checkbox: TestOutcome, result: &PackApiResult) {
+        self.set_outcome(checkbox);
+        self.api_outcome = Some(result.outcome());
+        let mut issues: Vec<String> = self
+            .issues
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|issue| !issue.starts_with(NodeApiResult::ISSUE_PREFIX))
+            .collect();
+        issues.extend(result.issues());
+        self.issues = (!issues.is_empty()).then_some(issues);
+    }
 }
 
 impl Metadata {
+    pub fn current_schema_version() -> u32 {
+        METADATA_SCHEMA.current
+    }
+
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let contents = fs::read_to_string(path)?;
-        let metadata: Metadata = serde_json::from_str(&contents)?;
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read metadata: {}", path.display()))?;
+        Self::from_json(&contents).with_context(|| format!("In {}", path.display()))
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self> {
+        let value: Value =
+            serde_json::from_str(contents).context("metadata.json is not valid JSON")?;
+        let (mut metadata, _): (Metadata, _) = METADATA_SCHEMA.load(value)?;
+        metadata.schema_version = Self::current_schema_version();
         Ok(metadata)
     }
 
@@ -63,13 +140,36 @@ impl Metadata {
         } else {
             0.0
         };
+        let mut outcomes = BTreeMap::new();
+        for pack in self.packs.values() {
+            *outcomes.entry(pack.outcome()).or_default() += 1;
+        }
 
         self.stats = Some(Stats {
             total_packs,
             tested_packs,
             total_nodes,
             completion_percent,
+            outcomes,
         });
         self.last_updated = Some(Utc::now());
     }
 }
+
+/// Version 1 packs are tested or not; give each the matching outcome.
+fn migrate_1_to_2(metadata: &mut Map<String, Value>) {
+    let Some(packs) = metadata.get_mut("packs").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for pack in packs.values_mut().filter_map(Value::as_object_mut) {
+        if pack.contains_key("outcome") {
+            continue;
+        }
+        let tested = pack.get("tested").and_then(Value::as_bool).unwrap_or(false);
+        let outcome = TestOutcome::from_tested(tested);
+        pack.insert(
+            "outcome".to_string(),
+            serde_json::to_value(outcome).unwrap_or(Value::Null),
+        );
+    }
+}
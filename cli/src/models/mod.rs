@@ -1,11 +1,45 @@
+mod api_prompt;
+mod api_test;
+mod catalog;
 mod checklist;
+mod checklist_document;
+mod checklist_merge;
+mod discovery;
 mod export;
+mod finding;
 mod metadata;
 mod node_pack;
+mod object_info;
+mod outcome;
+mod round;
+mod run_log;
+mod schema;
 mod workflow;
+mod workflow_scope;
 
-pub use checklist::{Checklist, DetailedChecklist};
-pub use export::Export;
-pub use metadata::{Environment, Metadata, PackMetadata, Stats};
+pub use api_prompt::{ApiInput, ApiNode, ApiNodeMeta, ApiPrompt};
+pub use api_test::{ApiTestStatus, NodeApiResult, PackApiResult};
+pub use catalog::{Catalog, CatalogNode, PackAttribution, CATALOG_SCHEMA};
+pub use checklist::{Checklist, ChecklistNode, DetailedChecklist};
+pub use checklist_document::{ChecklistDocument, DocumentLine, PackLine, DEFAULT_HEADER};
+pub use checklist_merge::{
+    ChecklistMerge, NodeChanges, PackChange, PackSource, PackSync, DEPRECATED_SUFFIX,
+    REMOVED_SUFFIX,
+};
+pub use discovery::{WorkflowDiscovery, WorkflowKind, WorkflowSource};
+pub use export::{Export, ExportEnvironment, ExportNode, ExportPack, EXPORT_SCHEMA};
+pub use finding::{Finding, FindingsFile};
+pub use metadata::{Environment, Metadata, PackMetadata, Stats, METADATA_SCHEMA};
 pub use node_pack::NodePack;
-pub use workflow::Workflow;
+pub use object_info::{
+    InputOrder, InputSlot, InputSpec, NodeDefinition, NodeDefinitionInputs, ObjectInfo, OutputSpec,
+};
+pub use outcome::{DimensionResult, TestDimension, TestDimensions, TestOutcome};
+pub use round::{FindingChange, FindingDiff, Round, RoundComparison, RoundSnapshot, StatusChange};
+pub use run_log::{NodeRun, Progress, PromptRun, RunError, RunLog, ServerEvent, RUN_LOG_SCHEMA};
+pub use schema::{Migration, Schema, SchemaError};
+pub use workflow::{
+    InputWidget, NodeId, NodeInput, NodeMode, NodeOutput, Subgraph, WidgetValues, Workflow,
+    WorkflowDefinitions, WorkflowGraph, WorkflowGroup, WorkflowLink, WorkflowNode,
+};
+pub use workflow_scope::WorkflowScope;
@@ -1,18 +1,47 @@
+use super::{TestDimension, TestDimensions, TestOutcome};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodePack {
     pub name: String,
     pub node_count: usize,
-    pub tested: bool,
+    #[serde(default)]
+    pub outcome: TestOutcome,
+    /// Whether the result covers UI testing, API testing or both.
+    #[serde(default)]
+    pub dimensions: TestDimensions,
 }
 
 impl NodePack {
     pub fn new(name: String, node_count: usize, tested: bool) -> Self {
+        Self::with_outcome(name, node_count, TestOutcome::from_tested(tested))
+    }
+
+    pub fn with_outcome(name: String, node_count: usize, outcome: TestOutcome) -> Self {
         Self {
             name,
             node_count,
-            tested,
+            outcome,
+            dimensions: TestDimensions::default(),
         }
     }
+
+    pub fn tested(&self) -> bool {
+        self.outcome.is_tested()
+    }
+
+    pub fn set_outcome(&mut self, outcome: TestOutcome) {
+        self.outcome = outcome;
+    }
+
+    pub fn dimension_outcome(&self, dimension: TestDimension) -> TestOutcome {
+        self.dimensions.resolve(dimension, self.outcome)
+    }
+
+    /// Record one dimension's result. The first result for an untested pack
+    /// also sets the checkbox.
+    pub fn set_dimension_outcome(&mut self, dimension: TestDimension, outcome: TestOutcome) {
+        let checkbox = self.dimensions.record(dimension, self.outcome, outcome);
+        self.set_outcome(checkbox);
+    }
 }
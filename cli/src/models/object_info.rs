@@ -0,0 +1,333 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ObjectInfo {
+    pub nodes: BTreeMap<String, NodeDefinition>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeDefinition {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// `nodes`, `comfy_extras.nodes_mask`, `custom_nodes.<pack>`, ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_module: Option<String>,
+    #[serde(default)]
+    pub input: NodeDefinitionInputs,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_order: Option<InputOrder>,
+    /// Output types; combo outputs are lists of options.
+    #[serde(default)]
+    pub output: Vec<Value>,
+    #[serde(default)]
+    pub output_is_list: Vec<bool>,
+    #[serde(default)]
+    pub output_name: Vec<String>,
+    #[serde(default)]
+    pub output_node: bool,
+    #[serde(default)]
+    pub deprecated: bool,
+    #[serde(default)]
+    pub experimental: bool,
+    /// Runs on a paid provider's API rather than locally.
+    #[serde(default)]
+    pub api_node: bool,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Input specs keep the server's order, which is also the order of
+/// `widgets_values` in UI workflows.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeDefinitionInputs {
+    #[serde(default)]
+    pub required: Map<String, Value>,
+    #[serde(default)]
+    pub optional: Map<String, Value>,
+    #[serde(default)]
+    pub hidden: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputOrder {
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub optional: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputSpec {
+    pub name: String,
+    pub input_type: String,
+    pub required: bool,
+    pub default: Option<Value>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: Option<f64>,
+    pub options: Vec<Value>,
+    pub multiline: bool,
+    /// A widget type shown as a socket instead.
+    pub force_input: bool,
+    /// Followed by a "control after generate" combo.
+    pub control_after_generate: bool,
+    /// Has an upload button (image, video, audio...).
+    pub upload: bool,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputSpec {
+    pub name: String,
+    pub output_type: String,
+    pub is_list: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputSlot {
+    pub name: String,
+    pub input_type: String,
+    pub required: bool,
+    /// Rendered as a widget (and stored in `widgets_values`) rather than a
+    /// socket.
+    pub is_widget: bool,
+    /// Extra `widgets_values` entries the frontend stores after this one,
+    /// e.g. the seed "control after generate" combo or the upload button.
+    pub extra_widget_values: usize,
+}
+
+const WIDGET_TYPES: [&str; 5] = ["INT", "FLOAT", "STRING", "BOOLEAN", "COMBO"];
+const UPLOAD_OPTIONS: [&str; 4] = [
+    "image_upload",
+    "video_upload",
+    "audio_upload",
+    "animated_image_upload",
+];
+
+impl ObjectInfo {
+    pub const PROJECT_FILE: &'static str = "object_info.json";
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read object_info: {}", path.display()))?;
+        let mut deserializer = serde_json::Deserializer::from_str(&contents);
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+            anyhow::anyhow!(
+                "Failed to parse object_info: {} at '{}': {}",
+                path.display(),
+                err.path(),
+                err.inner()
+            )
+        })
+    }
+
+    pub fn get(&self, class_type: &str) -> Option<&NodeDefinition> {
+        self.nodes.get(class_type)
+    }
+
+    pub fn contains(&self, class_type: &str) -> bool {
+        self.nodes.contains_key(class_type)
+    }
+
+    /// Class names by pack, as the browser script groups them.
+    pub fn by_pack(&self) -> BTreeMap<String, Vec<&str>> {
+        let mut packs: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for (class_type, definition) in &self.nodes {
+            packs
+                .entry(definition.pack())
+                .or_default()
+                .push(class_type.as_str());
+        }
+        packs
+    }
+}
+
+impl NodeDefinition {
+    /// The pack this node belongs to, named like the browser script does:
+    /// `core`, `comfy_extras`, `comfy_api_nodes` or the custom node folder.
+    pub fn pack(&self) -> String {
+        let module = self.python_module.as_deref().unwrap_or("core");
+        if module.starts_with("comfy_extras.") {
+            "comfy_extras".to_string()
+        } else if module.starts_with("comfy_api_nodes.") {
+            "comfy_api_nodes".to_string()
+        } else if let Some(pack) = module.strip_prefix("custom_nodes.") {
+            pack.to_string()
+        } else if module == "nodes" {
+            "core".to_string()
+        } else {
+            module.to_string()
+        }
+    }
+
+    /// Required then optional inputs, in server order.
+    pub fn inputs(&self) -> Vec<InputSpec> {
+        self.ordered_specs()
+            .into_iter()
+            .map(|(name, spec, required)| InputSpec::from_spec(name, spec, required))
+            .collect()
+    }
+
+    pub fn input(&self, name: &str) -> Option<InputSpec> {
+        self.inputs().into_iter().find(|input| input.name == name)
+    }
+
+    pub fn outputs(&self) -> Vec<OutputSpec> {
+        self.output
+            .iter()
+            .enumerate()
+            .map(|(i, output)| {
+                let output_type = match output {
+                    Value::String(s) => s.clone(),
+                    Value::Array(_) => "COMBO".to_string(),
+                    _ => "*".to_string(),
+                };
+                OutputSpec {
+                    name: self
+                        .output_name
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_else(|| output_type.clone()),
+                    output_type,
+                    is_list: self.output_is_list.get(i).copied().unwrap_or(false),
+                }
+            })
+            .collect()
+    }
+
+    pub fn input_slots(&self) -> Vec<InputSlot> {
+        self.ordered_specs()
+            .into_iter()
+            .map(|(name, spec, required)| InputSlot::from_spec(name, spec, required))
+            .collect()
+    }
+
+    fn ordered_specs(&self) -> Vec<(&String, &Value, bool)> {
+        let mut specs_in_order = Vec::new();
+
+        for (specs, order, required) in [
+            (
+                &self.input.required,
+                self.input_order.as_ref().map(|o| &o.required),
+                true,
+            ),
+            (
+                &self.input.optional,
+                self.input_order.as_ref().map(|o| &o.optional),
+                false,
+            ),
+        ] {
+            // `input_order` may be partial; inputs it leaves out follow in
+            // their own order
+            let order = order.map(Vec::as_slice).unwrap_or_default();
+            let mut names: Vec<&String> = order.iter().filter(|n| specs.contains_key(*n)).collect();
+            names.extend(specs.keys().filter(|name| !order.contains(name)));
+
+            for name in names {
+                specs_in_order.push((name, &specs[name], required));
+            }
+        }
+
+        specs_in_order
+    }
+
+    pub fn widget_slots(&self) -> Vec<InputSlot> {
+        self.input_slots()
+            .into_iter()
+            .filter(|s| s.is_widget)
+            .collect()
+    }
+}
+
+impl InputSpec {
+    /// Parse an input spec: `["INT", {...}]`, `[["a", "b"], {...}]` (legacy
+    /// combo) or `["COMBO", {"options": [...]}]`.
+    pub fn from_spec(name: &str, spec: &Value, required: bool) -> Self {
+        let (type_value, options) = match spec {
+            Value::Array(parts) => (parts.first(), parts.get(1).and_then(Value::as_object)),
+            _ => (None, None),
+        };
+        let option = |key: &str| options.and_then(|o| o.get(key));
+        let number = |key: &str| option(key).and_then(Value::as_f64);
+        let flag = |key: &str| option(key).and_then(Value::as_bool).unwrap_or(false);
+
+        let (input_type, choices) = match type_value {
+            Some(Value::String(s)) => (
+                s.clone(),
+                option("options")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            Some(Value::Array(choices)) => ("COMBO".to_string(), choices.clone()),
+            _ => ("*".to_string(), Vec::new()),
+        };
+
+        Self {
+            name: name.to_string(),
+            input_type,
+            required,
+            default: option("default").cloned(),
+            min: number("min"),
+            max: number("max"),
+            step: number("step"),
+            options: choices,
+            multiline: flag("multiline"),
+            force_input: flag("forceInput"),
+            control_after_generate: flag("control_after_generate"),
+            upload: UPLOAD_OPTIONS.iter().any(|key| flag(key)),
+            tooltip: option("tooltip")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        }
+    }
+
+    pub fn is_widget(&self) -> bool {
+        WIDGET_TYPES.contains(&self.input_type.as_str()) && !self.force_input
+    }
+}
+
+impl InputSlot {
+    pub fn from_spec(name: &str, spec: &Value, required: bool) -> Self {
+        Self::from(&InputSpec::from_spec(name, spec, required))
+    }
+}
+
+impl From<&InputSpec> for InputSlot {
+    fn from(spec: &InputSpec) -> Self {
+        let is_widget = spec.is_widget();
+
+        let mut extra_widget_values = 0;
+        if is_widget {
+            let has_control = spec.control_after_generate
+                || (spec.input_type == "INT" && (spec.name == "seed" || spec.name == "noise_seed"));
+            if has_control {
+                extra_widget_values += 1;
+            }
+            if spec.upload {
+                extra_widget_values += 1;
+            }
+        }
+
+        Self {
+            name: spec.name.clone(),
+            input_type: spec.input_type.clone(),
+            required: spec.required,
+            is_widget,
+            extra_widget_values,
+        }
+    }
+}
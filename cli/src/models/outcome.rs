@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Result of testing a pack or node, written as the checkbox marker:
+///
+/// | Marker | Outcome        |
+/// |--------|----------------|
+/// | `[ ]`  | untested       |
+/// | `[x]`  | pass           |
+/// | `[!]`  | fail           |
+/// | `[~]`  | blocked        |
+/// | `[-]`  | skipped        |
+/// | `[/]`  | not applicable |
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOutcome {
+    #[default]
+    Untested,
+    Pass,
+    Fail,
+    /// Could not be tested, e.g. a model missing on cloud.
+    Blocked,
+    Skipped,
+    NotApplicable,
+}
+
+impl TestOutcome {
+    pub const ALL: [TestOutcome; 6] = [
+        TestOutcome::Untested,
+        TestOutcome::Pass,
+        TestOutcome::Fail,
+        TestOutcome::Blocked,
+        TestOutcome::Skipped,
+        TestOutcome::NotApplicable,
+    ];
+
+    /// Characters accepted inside `[ ]`, for building regexes.
+    pub const MARKER_CLASS: &'static str = r"[ xX!~\-/]";
+
+    pub fn from_marker(marker: &str) -> Option<Self> {
+        match marker {
+            " " => Some(TestOutcome::Untested),
+            "x" | "X" => Some(TestOutcome::Pass),
+            "!" => Some(TestOutcome::Fail),
+            "~" => Some(TestOutcome::Blocked),
+            "-" => Some(TestOutcome::Skipped),
+            "/" => Some(TestOutcome::NotApplicable),
+            _ => None,
+        }
+    }
+
+    pub fn marker(&self) -> &'static str {
+        match self {
+            TestOutcome::Untested => " ",
+            TestOutcome::Pass => "x",
+            TestOutcome::Fail => "!",
+            TestOutcome::Blocked => "~",
+            TestOutcome::Skipped => "-",
+            TestOutcome::NotApplicable => "/",
+        }
+    }
+
+    /// Whether a tester has looked at it, whatever the result.
+    pub fn is_tested(&self) -> bool {
+        *self != TestOutcome::Untested
+    }
+
+    /// Serialized name, also used as a CSS class in reports.
+    pub fn key(&self) -> &'static str {
+        match self {
+            TestOutcome::Untested => "untested",
+            TestOutcome::Pass => "pass",
+            TestOutcome::Fail => "fail",
+            TestOutcome::Blocked => "blocked",
+            TestOutcome::Skipped => "skipped",
+            TestOutcome::NotApplicable => "not_applicable",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TestOutcome::Untested => "untested",
+            TestOutcome::Pass => "pass",
+            TestOutcome::Fail => "fail",
+            TestOutcome::Blocked => "blocked",
+            TestOutcome::Skipped => "skipped",
+            TestOutcome::NotApplicable => "n/a",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            TestOutcome::Untested => "⏳",
+            TestOutcome::Pass => "✅",
+            TestOutcome::Fail => "❌",
+            TestOutcome::Blocked => "🚧",
+            TestOutcome::Skipped => "⏭",
+            TestOutcome::NotApplicable => "➖",
+        }
+    }
+
+    /// "3 pass, 1 fail, 1 blocked" for the tested outcomes in `counts`.
+    pub fn summarize(counts: &BTreeMap<TestOutcome, usize>) -> String {
+        counts
+            .iter()
+            .filter(|(outcome, count)| outcome.is_tested() && **count > 0)
+            .map(|(outcome, count)| format!("{} {}", count, outcome.label()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|o| o.label() == label || o.key() == label)
+    }
+
+    pub fn from_tested(tested: bool) -> Self {
+        if tested {
+            TestOutcome::Pass
+        } else {
+            TestOutcome::Untested
+        }
+    }
+}
+
+impl fmt::Display for TestOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestDimension {
+    Ui,
+    Api,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DimensionResult {
+    #[default]
+    NotTested,
+    /// Same as the checkbox.
+    Checkbox,
+    /// Given explicitly, e.g. `<!-- API tested: fail -->`.
+    Outcome(TestOutcome),
+}
+
+/// UI and API results of a checklist item, written as a trailing marker:
+/// `<!-- UI tested -->`, `<!-- API tested -->`, `<!-- Both -->`, or with
+/// per-dimension results such as `<!-- UI tested, API tested: fail -->`.
+/// Items without a marker were tested in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestDimensions {
+    pub ui: DimensionResult,
+    pub api: DimensionResult,
+}
+
+impl Default for TestDimensions {
+    fn default() -> Self {
+        Self {
+            ui: DimensionResult::Checkbox,
+            api: DimensionResult::NotTested,
+        }
+    }
+}
+
+impl TestDimensions {
+    /// Parse the inside of an HTML comment. Returns `None` for comments
+    /// that are not dimension markers.
+    pub fn parse_marker(comment: &str) -> Option<Self> {
+        let mut dimensions = Self {
+            ui: DimensionResult::NotTested,
+            api: DimensionResult::NotTested,
+        };
+        let mut recognized = false;
+
+        for part in comment.split([',', ';']) {
+            let (name, outcome) = match part.split_once(':') {
+                Some((name, outcome)) => (name, Some(TestOutcome::from_label(outcome)?)),
+                None => (part, None),
+            };
+            let result = outcome.map_or(DimensionResult::Checkbox, DimensionResult::Outcome);
+
+            match name.trim().to_lowercase().as_str() {
+                "both" => {
+                    dimensions.ui = result;
+                    dimensions.api = result;
+                }
+                "ui" | "ui tested" => dimensions.ui = result,
+                "api" | "api tested" => dimensions.api = result,
+                _ => continue,
+            }
+            recognized = true;
+        }
+
+        recognized.then_some(dimensions)
+    }
+
+    /// Marker text (without `<!-- -->`); `None` for the unmarked default.
+    pub fn to_marker(&self) -> Option<String> {
+        use DimensionResult::*;
+
+        match (self.ui, self.api) {
+            (Checkbox, NotTested) => None,
+            (Checkbox, Checkbox) => Some("Both".to_string()),
+            (ui, api) => {
+                let parts: Vec<_> = [("UI tested", ui), ("API tested", api)]
+                    .into_iter()
+                    .filter_map(|(name, result)| match result {
+                        NotTested => None,
+                        Checkbox => Some(name.to_string()),
+                        Outcome(o) => Some(format!("{}: {}", name, o.label())),
+                    })
+                    .collect();
+                if parts.is_empty() {
+                    Some("UI tested: untested".to_string())
+                } else {
+                    Some(parts.join(", "))
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, dimension: TestDimension) -> DimensionResult {
+        match dimension {
+            TestDimension::Ui => self.ui,
+            TestDimension::Api => self.api,
+        }
+    }
+
+    pub fn set(&mut self, dimension: TestDimension, result: DimensionResult) {
+        match dimension {
+            TestDimension::Ui => self.ui = result,
+            TestDimension::Api => self.api = result,
+        }
+    }
+
+    /// Record one dimension's result against the current checkbox outcome
+    /// and return the new checkbox outcome. The first result for something
+    /// untested also sets the checkbox, as does a new result for the only
+    /// dimension the checkbox stands for.
+    pub fn record(
+        &mut self,
+        dimension: TestDimension,
+        checkbox: TestOutcome,
+        outcome: TestOutcome,
+    ) -> TestOutcome {
+        let other = match dimension {
+            TestDimension::Ui => TestDimension::Api,
+            TestDimension::Api => TestDimension::Ui,
+        };
+        if checkbox.is_tested()
+            && self.get(dimension) == DimensionResult::Checkbox
+            && self.get(other) != DimensionResult::Checkbox
+        {
+            return outcome;
+        }
+
+        if !checkbox.is_tested() {
+            // Other dimensions following the checkbox were not tested
+            for other in [TestDimension::Ui, TestDimension::Api] {
+                if self.get(other) == DimensionResult::Checkbox {
+                    self.set(other, DimensionResult::NotTested);
+                }
+            }
+            self.set(dimension, DimensionResult::Checkbox);
+            outcome
+        } else {
+            if outcome == checkbox {
+                self.set(dimension, DimensionResult::Checkbox);
+            } else {
+                self.set(dimension, DimensionResult::Outcome(outcome));
+            }
+            checkbox
+        }
+    }
+
+    pub fn resolve(&self, dimension: TestDimension, checkbox: TestOutcome) -> TestOutcome {
+        match self.get(dimension) {
+            DimensionResult::NotTested => TestOutcome::Untested,
+            DimensionResult::Checkbox => checkbox,
+            DimensionResult::Outcome(outcome) => outcome,
+        }
+    }
+}
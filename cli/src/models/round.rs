@@ -0,0 +1,340 @@
+use super::{
+    Checklist, ChecklistDocument, DetailedChecklist, Finding, FindingsFile, TestDimension,
+    TestOutcome,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_FILES: [&str; 3] = ["checklist.md", "checklist-detailed.md", "metadata.json"];
+
+/// A testing round, kept in `checklists/<project>/rounds/round<N>/`:
+///
+/// - `round.json`: this struct
+/// - `start/`: checklist, metadata and findings when the round started
+/// - `results/`: the same files when it was closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Round {
+    pub round: u32,
+    pub started_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+impl Round {
+    pub const DIR: &'static str = "rounds";
+
+    pub fn list(project_dir: &Path) -> Result<Vec<Round>> {
+        let rounds_dir = project_dir.join(Self::DIR);
+        let mut rounds = Vec::new();
+        if !rounds_dir.is_dir() {
+            return Ok(rounds);
+        }
+
+        for entry in fs::read_dir(&rounds_dir)?.filter_map(|e| e.ok()) {
+            let path = entry.path().join("round.json");
+            if path.exists() {
+                rounds.push(Self::from_file(&path)?);
+            }
+        }
+
+        rounds.sort_by_key(|r| r.round);
+        Ok(rounds)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read round: {}", path.display()))?;
+        let mut round: Round = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid round file: {}", path.display()))?;
+        round.dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        Ok(round)
+    }
+
+    pub fn start(project_dir: &Path, findings_dir: &Path, number: u32) -> Result<Self> {
+        let dir = project_dir.join(Self::DIR).join(format!("round{}", number));
+        if dir.exists() {
+            anyhow::bail!("Round {} already exists", number);
+        }
+
+        let round = Round {
+            round: number,
+            started_at: Utc::now(),
+            closed_at: None,
+            dir,
+        };
+        snapshot(project_dir, findings_dir, &round.start_dir())?;
+        round.write()?;
+        Ok(round)
+    }
+
+    pub fn close(&mut self, project_dir: &Path, findings_dir: &Path) -> Result<()> {
+        if !self.is_open() {
+            anyhow::bail!("Round {} is already closed", self.round);
+        }
+        snapshot(project_dir, findings_dir, &self.results_dir())?;
+        self.closed_at = Some(Utc::now());
+        self.write()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.closed_at.is_none()
+    }
+
+    pub fn start_dir(&self) -> PathBuf {
+        self.dir.join("start")
+    }
+
+    pub fn results_dir(&self) -> PathBuf {
+        self.dir.join("results")
+    }
+
+    fn write(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join("round.json");
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn snapshot(project_dir: &Path, findings_dir: &Path, dest: &Path) -> Result<()> {
+    fs::create_dir_all(dest)?;
+    for name in SNAPSHOT_FILES {
+        let source = project_dir.join(name);
+        if source.exists() {
+            fs::copy(&source, dest.join(name))
+                .with_context(|| format!("Failed to copy {}", source.display()))?;
+        }
+    }
+
+    let findings_dest = dest.join("findings");
+    fs::create_dir_all(&findings_dest)?;
+    for file in FindingsFile::load_dir(findings_dir)? {
+        if let Some(name) = file.file_path.file_name() {
+            fs::copy(&file.file_path, findings_dest.join(name))
+                .with_context(|| format!("Failed to copy {}", file.file_path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// The checklists and findings at one point in time: a round snapshot or
+/// the live project files.
+#[derive(Debug, Clone)]
+pub struct RoundSnapshot {
+    pub checklist: Checklist,
+    pub detailed: Option<DetailedChecklist>,
+    pub findings: Vec<FindingsFile>,
+}
+
+impl RoundSnapshot {
+    /// A missing checklist counts as empty, e.g. a round started before
+    /// the first sync.
+    pub fn load(checklist_dir: &Path, findings_dir: &Path) -> Result<Self> {
+        let checklist_path = checklist_dir.join("checklist.md");
+        let detailed_path = checklist_dir.join("checklist-detailed.md");
+        Ok(RoundSnapshot {
+            checklist: if checklist_path.exists() {
+                Checklist::from_file(&checklist_path)?
+            } else {
+                Checklist {
+                    file_path: checklist_path,
+                    project_name: String::new(),
+                    packs: Vec::new(),
+                    document: ChecklistDocument::default(),
+                }
+            },
+            detailed: if detailed_path.exists() {
+                Some(DetailedChecklist::from_file(&detailed_path)?)
+            } else {
+                None
+            },
+            findings: FindingsFile::load_dir(findings_dir)?,
+        })
+    }
+
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        Self::load(dir, &dir.join("findings"))
+    }
+
+    fn all_findings(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().flat_map(|file| file.findings.iter())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusChange {
+    pub pack: String,
+    /// `None` for the pack itself.
+    pub node: Option<String>,
+    /// Set when only the UI or API result changed this way.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<TestDimension>,
+    pub from: TestOutcome,
+    pub to: TestOutcome,
+}
+
+impl StatusChange {
+    /// Passed before, fails or is blocked now.
+    pub fn is_regression(&self) -> bool {
+        self.from == TestOutcome::Pass
+            && matches!(self.to, TestOutcome::Fail | TestOutcome::Blocked)
+    }
+
+    /// The overall change, then UI and API changes that differ from it.
+    /// `from` and `to` hold the overall, UI and API outcomes.
+    fn between(
+        pack: &str,
+        node: Option<&str>,
+        from: [TestOutcome; 3],
+        to: [TestOutcome; 3],
+    ) -> Vec<Self> {
+        let mut changes: Vec<Self> = Vec::new();
+        let dimensions = [None, Some(TestDimension::Ui), Some(TestDimension::Api)];
+        for ((dimension, from), to) in dimensions.into_iter().zip(from).zip(to) {
+            if from == to || changes.iter().any(|c| (c.from, c.to) == (from, to)) {
+                continue;
+            }
+            changes.push(StatusChange {
+                pack: pack.to_string(),
+                node: node.map(str::to_string),
+                dimension,
+                from,
+                to,
+            });
+        }
+        changes
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingChange {
+    Fixed,
+    Reopened,
+    New,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FindingDiff {
+    pub change: FindingChange,
+    pub finding: Finding,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RoundComparison {
+    pub packs: Vec<StatusChange>,
+    pub nodes: Vec<StatusChange>,
+    pub findings: Vec<FindingDiff>,
+}
+
+impl RoundComparison {
+    /// Packs and nodes are compared when both snapshots have them; findings
+    /// are matched by pack, node and description.
+    pub fn compare(before: &RoundSnapshot, after: &RoundSnapshot) -> Self {
+        let mut comparison = RoundComparison::default();
+
+        for pack in &after.checklist.packs {
+            let Some(previous) = before.checklist.packs.iter().find(|p| p.name == pack.name) else {
+                continue;
+            };
+            comparison.packs.extend(StatusChange::between(
+                &pack.name,
+                None,
+                [
+                    previous.outcome,
+                    previous.dimension_outcome(TestDimension::Ui),
+                    previous.dimension_outcome(TestDimension::Api),
+                ],
+                [
+                    pack.outcome,
+                    pack.dimension_outcome(TestDimension::Ui),
+                    pack.dimension_outcome(TestDimension::Api),
+                ],
+            ));
+
+            let (Some(before_nodes), Some(after_nodes)) = (
+                before
+                    .detailed
+                    .as_ref()
+                    .and_then(|d| d.get_nodes(&pack.name)),
+                after
+                    .detailed
+                    .as_ref()
+                    .and_then(|d| d.get_nodes(&pack.name)),
+            ) else {
+                continue;
+            };
+            for node in after_nodes {
+                let Some(previous) = before_nodes.iter().find(|n| n.name == node.name) else {
+                    continue;
+                };
+                comparison.nodes.extend(StatusChange::between(
+                    &pack.name,
+                    Some(&node.name),
+                    [
+                        previous.outcome,
+                        previous.dimension_outcome(TestDimension::Ui),
+                        previous.dimension_outcome(TestDimension::Api),
+                    ],
+                    [
+                        node.outcome,
+                        node.dimension_outcome(TestDimension::Ui),
+                        node.dimension_outcome(TestDimension::Api),
+                    ],
+                ));
+            }
+        }
+
+        let before_findings: HashMap<String, &Finding> =
+            before.all_findings().map(|f| (finding_key(f), f)).collect();
+
+        for finding in after.all_findings() {
+            let change = match before_findings.get(&finding_key(finding)) {
+                Some(previous) if previous.is_open() && finding.resolved => FindingChange::Fixed,
+                Some(previous) if previous.resolved && finding.is_open() => FindingChange::Reopened,
+                Some(_) => continue,
+                None => FindingChange::New,
+            };
+            comparison.findings.push(FindingDiff {
+                change,
+                finding: finding.clone(),
+            });
+        }
+
+        comparison
+    }
+
+    pub fn findings_with(&self, change: FindingChange) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(move |f| f.change == change)
+            .map(|f| &f.finding)
+    }
+
+    /// Packs and nodes that went from pass to fail or blocked, overall or
+    /// in one dimension.
+    pub fn regressions(&self) -> impl Iterator<Item = &StatusChange> {
+        self.packs
+            .iter()
+            .chain(self.nodes.iter())
+            .filter(|c| c.is_regression())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packs.is_empty() && self.nodes.is_empty() && self.findings.is_empty()
+    }
+}
+
+fn finding_key(finding: &Finding) -> String {
+    format!(
+        "{}\u{0}{}\u{0}{}",
+        finding.pack.as_deref().unwrap_or("").to_lowercase(),
+        finding.node.as_deref().unwrap_or("").to_lowercase(),
+        finding.description.trim().to_lowercase()
+    )
+}
@@ -0,0 +1,294 @@
+use super::schema::Schema;
+use super::ApiPrompt;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const RUN_LOG_SCHEMA: Schema<u32> = Schema {
+    kind: "run-log.json",
+    field: "schema_version",
+    unversioned: 1,
+    current: 1,
+    migrations: &[],
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerEvent {
+    pub kind: String,
+    pub data: Value,
+}
+
+impl ServerEvent {
+    pub fn from_json(text: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(text).ok()?;
+        Some(Self {
+            kind: value.get("type")?.as_str()?.to_string(),
+            data: value.get("data").cloned().unwrap_or(Value::Null),
+        })
+    }
+
+    pub fn prompt_id(&self) -> Option<&str> {
+        self.data.get("prompt_id").and_then(Value::as_str)
+    }
+
+    /// `data.node`; `None` both when absent and when null.
+    fn node(&self) -> Option<String> {
+        match self.data.get("node")? {
+            Value::String(id) => Some(id.clone()),
+            Value::Number(id) => Some(id.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeRun {
+    pub node_id: String,
+    pub class_type: Option<String>,
+    /// Reused from an earlier prompt instead of running.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    pub started_at: Option<DateTime<Utc>>,
+    /// Until the next node started or the prompt finished.
+    pub duration_ms: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub executed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    pub value: u64,
+    pub max: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptRun {
+    /// The node under test, or the workflow's name.
+    pub class_type: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub workflow: bool,
+    pub prompt_id: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub duration_ms: Option<i64>,
+    /// `success` or `error`; `None` if the run log lost track of it.
+    pub status: Option<String>,
+    pub nodes: Vec<NodeRun>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RunError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunError {
+    pub node_id: Option<String>,
+    pub node_type: Option<String>,
+    pub exception_type: Option<String>,
+    pub message: String,
+}
+
+impl PromptRun {
+    pub fn new(class_type: &str, prompt_id: &str) -> Self {
+        Self {
+            class_type: class_type.to_string(),
+            workflow: false,
+            prompt_id: prompt_id.to_string(),
+            started_at: None,
+            finished_at: None,
+            duration_ms: None,
+            status: None,
+            nodes: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Apply an event received at `at`. Events for other prompts are
+    /// ignored; `prompt` supplies the class type of each node id.
+    pub fn record(&mut self, event: &ServerEvent, prompt: &ApiPrompt, at: DateTime<Utc>) {
+        // Older servers leave the prompt id off progress events
+        if event.prompt_id().is_some_and(|id| id != self.prompt_id) || self.is_finished() {
+            return;
+        }
+
+        match event.kind.as_str() {
+            "execution_start" => self.started_at = Some(at),
+            "execution_cached" => {
+                let cached = event.data.get("nodes").and_then(Value::as_array);
+                for id in cached.into_iter().flatten().filter_map(node_id) {
+                    self.node_mut(&id, prompt).cached = true;
+                }
+            }
+            // `executing` with no node: the prompt is done
+            "executing" => match event.node() {
+                Some(id) => {
+                    self.end_running(at);
+                    let node = self.node_mut(&id, prompt);
+                    node.started_at.get_or_insert(at);
+                }
+                None => self.finish(at),
+            },
+            "progress" => {
+                let (Some(value), Some(max)) = (
+                    event.data.get("value").and_then(Value::as_u64),
+                    event.data.get("max").and_then(Value::as_u64),
+                ) else {
+                    return;
+                };
+                let id = event
+                    .node()
+                    .or_else(|| self.running().map(|n| n.node_id.clone()));
+                if let Some(id) = id {
+                    self.node_mut(&id, prompt).progress = Some(Progress { value, max });
+                }
+            }
+            "executed" => {
+                if let Some(id) = event.node() {
+                    self.node_mut(&id, prompt).executed = true;
+                }
+            }
+            "execution_success" => self.status = Some("success".to_string()),
+            "execution_error" | "execution_interrupted" => {
+                let text = |key: &str| {
+                    event
+                        .data
+                        .get(key)
+                        .and_then(Value::as_str)
+                        .map(|s| s.trim().to_string())
+                };
+                let node_id = event.data.get("node_id").and_then(node_id);
+                let message = text("exception_message").unwrap_or_else(|| event.kind.clone());
+                if let Some(id) = &node_id {
+                    self.node_mut(id, prompt).error = Some(message.clone());
+                }
+                self.error = Some(RunError {
+                    node_id,
+                    node_type: text("node_type"),
+                    exception_type: text("exception_type"),
+                    message,
+                });
+                self.status = Some("error".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished_at.is_some()
+    }
+
+    /// Close the run, e.g. when `/history` says it's done but the websocket
+    /// never did.
+    pub fn finish(&mut self, at: DateTime<Utc>) {
+        if self.is_finished() {
+            return;
+        }
+        self.end_running(at);
+        self.finished_at = Some(at);
+        self.duration_ms = self.started_at.map(|start| (at - start).num_milliseconds());
+    }
+
+    pub fn failed_node(&self) -> Option<&NodeRun> {
+        self.nodes.iter().find(|n| n.error.is_some())
+    }
+
+    /// The node that started last and hasn't been timed yet.
+    fn running(&self) -> Option<&NodeRun> {
+        self.nodes
+            .iter()
+            .rev()
+            .find(|n| n.started_at.is_some() && n.duration_ms.is_none())
+    }
+
+    fn end_running(&mut self, at: DateTime<Utc>) {
+        for node in &mut self.nodes {
+            if let (Some(start), None) = (node.started_at, node.duration_ms) {
+                node.duration_ms = Some((at - start).num_milliseconds());
+            }
+        }
+    }
+
+    fn node_mut(&mut self, id: &str, prompt: &ApiPrompt) -> &mut NodeRun {
+        let index = match self.nodes.iter().position(|n| n.node_id == id) {
+            Some(index) => index,
+            None => {
+                self.nodes.push(NodeRun {
+                    node_id: id.to_string(),
+                    class_type: prompt.nodes.get(id).map(|n| n.class_type.clone()),
+                    cached: false,
+                    started_at: None,
+                    duration_ms: None,
+                    progress: None,
+                    executed: false,
+                    error: None,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        &mut self.nodes[index]
+    }
+}
+
+fn node_id(value: &Value) -> Option<String> {
+    match value {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+/// `checklists/<project>/run-log.json`: the execution events of the latest
+/// `api-test run`, per prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunLog {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub server: String,
+    pub client_id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub prompts: Vec<PromptRun>,
+}
+
+impl RunLog {
+    pub const FILE_NAME: &'static str = "run-log.json";
+
+    pub fn new(server: &str, client_id: &str) -> Self {
+        Self {
+            schema_version: RUN_LOG_SCHEMA.current,
+            server: server.to_string(),
+            client_id: client_id.to_string(),
+            started_at: Utc::now(),
+            finished_at: None,
+            prompts: Vec::new(),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read run log: {}", path.display()))?;
+        let value: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not valid JSON", path.display()))?;
+        let (mut log, _): (RunLog, _) = RUN_LOG_SCHEMA
+            .load(value)
+            .with_context(|| format!("In {}", path.display()))?;
+        log.schema_version = RUN_LOG_SCHEMA.current;
+        Ok(log)
+    }
+
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(Self::FILE_NAME)
+    }
+
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,143 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SchemaError {
+    #[error("{kind} is not a JSON object")]
+    NotAnObject { kind: &'static str },
+
+    #[error("{kind} has an invalid '{field}': expected a version like \"{current}\"")]
+    InvalidVersion {
+        kind: &'static str,
+        field: &'static str,
+        current: String,
+    },
+
+    #[error("{kind} version {version} is not supported (this comfy-qa reads {supported}; newer files need a newer comfy-qa)")]
+    UnsupportedVersion {
+        kind: &'static str,
+        version: String,
+        supported: String,
+    },
+
+    #[error("Invalid {kind} at '{path}': {message}")]
+    InvalidField {
+        kind: &'static str,
+        path: String,
+        message: String,
+    },
+}
+
+/// How a format numbers its versions: `u32` for `schema_version`, strings
+/// like `"1.1"` for the export's `version`.
+pub trait Version: Copy + PartialEq + fmt::Display + 'static {
+    /// Whether the version field's value names this version. Numbers and
+    /// strings are both accepted, as older writers weren't consistent.
+    fn matches(&self, value: &Value) -> bool;
+}
+
+impl Version for u32 {
+    fn matches(&self, value: &Value) -> bool {
+        match value {
+            Value::Number(n) => n.as_u64() == Some(u64::from(*self)),
+            Value::String(s) => s.parse() == Ok(*self),
+            _ => false,
+        }
+    }
+}
+
+impl Version for &'static str {
+    fn matches(&self, value: &Value) -> bool {
+        match value {
+            Value::Number(n) => n.to_string() == *self,
+            Value::String(s) => s == self,
+            _ => false,
+        }
+    }
+}
+
+pub struct Migration<V: 'static> {
+    pub from: V,
+    pub to: V,
+    pub apply: fn(&mut Map<String, Value>),
+}
+
+pub struct Schema<V: 'static> {
+    /// "export", "metadata.json", ... for messages.
+    pub kind: &'static str,
+    pub field: &'static str,
+    /// Version of files written before the field existed.
+    pub unversioned: V,
+    pub current: V,
+    /// In order, the last one ending at `current`.
+    pub migrations: &'static [Migration<V>],
+}
+
+impl<V: Version> Schema<V> {
+    pub fn supported(&self) -> Vec<V> {
+        let mut versions: Vec<_> = self.migrations.iter().map(|m| m.from).collect();
+        versions.push(self.current);
+        versions
+    }
+
+    /// Bring `value` up to the current version and deserialize it. Returns
+    /// the result and the version the file was written with.
+    pub fn load<T: DeserializeOwned>(&self, mut value: Value) -> Result<(T, V), SchemaError> {
+        let object = value
+            .as_object_mut()
+            .ok_or(SchemaError::NotAnObject { kind: self.kind })?;
+
+        let supported = self.supported();
+        let start = match object.get(self.field) {
+            None | Some(Value::Null) => supported.iter().position(|v| *v == self.unversioned),
+            Some(version @ (Value::String(_) | Value::Number(_))) => {
+                let start = supported.iter().position(|v| v.matches(version));
+                if start.is_none() {
+                    return Err(SchemaError::UnsupportedVersion {
+                        kind: self.kind,
+                        version: match version {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        },
+                        supported: supported
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    });
+                }
+                start
+            }
+            Some(_) => None,
+        };
+        let Some(start) = start else {
+            return Err(SchemaError::InvalidVersion {
+                kind: self.kind,
+                field: self.field,
+                current: self.current.to_string(),
+            });
+        };
+        let version = supported[start];
+        for migration in &self.migrations[start..] {
+            (migration.apply)(object);
+        }
+        // The typed struct sets the current version itself
+        object.remove(self.field);
+
+        let parsed = serde_path_to_error::deserialize(value).map_err(|err| {
+            let path = err.path().to_string();
+            SchemaError::InvalidField {
+                kind: self.kind,
+                path: if path == "." {
+                    "(root)".to_string()
+                } else {
+                    path
+                },
+                message: err.into_inner().to_string(),
+            }
+        })?;
+        Ok((parsed, version))
+    }
+}
@@ -1,104 +1,589 @@
+use super::{ApiPrompt, WorkflowDiscovery, WorkflowKind};
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "WorkflowData")]
 pub struct Workflow {
     pub file_path: PathBuf,
     pub pack_name: String,
+    pub kind: WorkflowKind,
     pub node_count: usize,
+    pub graph: WorkflowGraph,
+    /// Set when the file is in API prompt format; `graph` is then derived
+    /// from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_prompt: Option<ApiPrompt>,
+    /// `graph` with subgraphs expanded, built once at load.
+    #[serde(skip)]
+    nodes: Vec<WorkflowNode>,
+}
+
+/// A serialized [`Workflow`]; converting it expands the nodes again.
+#[derive(Deserialize)]
+struct WorkflowData {
+    file_path: PathBuf,
+    pack_name: String,
+    kind: WorkflowKind,
+    node_count: usize,
+    graph: WorkflowGraph,
+    #[serde(default)]
+    api_prompt: Option<ApiPrompt>,
+}
+
+impl From<WorkflowData> for Workflow {
+    fn from(data: WorkflowData) -> Self {
+        Workflow {
+            nodes: data.graph.expanded_nodes(),
+            file_path: data.file_path,
+            pack_name: data.pack_name,
+            kind: data.kind,
+            node_count: data.node_count,
+            graph: data.graph,
+            api_prompt: data.api_prompt,
+        }
+    }
+}
+
+/// Fields the tool does not interpret are kept in `other` so a graph can be
+/// written back without losing data.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowGraph {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_node_id: Option<NodeId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_link_id: Option<u64>,
+    #[serde(default)]
     pub nodes: Vec<WorkflowNode>,
+    #[serde(default)]
+    pub links: Vec<WorkflowLink>,
+    #[serde(default)]
+    pub groups: Vec<WorkflowGroup>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definitions: Option<WorkflowDefinitions>,
+    #[serde(default)]
+    pub config: Map<String, Value>,
+    #[serde(default)]
+    pub extra: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<f64>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Node identifier. The frontend writes integers, but hand-written and older
+/// files sometimes use strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NodeId {
+    Number(i64),
+    Text(String),
+}
+
+impl Default for NodeId {
+    fn default() -> Self {
+        NodeId::Number(0)
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeId::Number(n) => write!(f, "{}", n),
+            NodeId::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeMode {
+    #[default]
+    Always,
+    OnEvent,
+    /// Muted: the node and everything depending on it is skipped.
+    Never,
+    OnTrigger,
+    /// Bypassed: inputs are passed through to matching outputs.
+    Bypass,
+    Unknown(u8),
+}
+
+impl From<u8> for NodeMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => NodeMode::Always,
+            1 => NodeMode::OnEvent,
+            2 => NodeMode::Never,
+            3 => NodeMode::OnTrigger,
+            4 => NodeMode::Bypass,
+            other => NodeMode::Unknown(other),
+        }
+    }
+}
+
+impl From<NodeMode> for u8 {
+    fn from(mode: NodeMode) -> Self {
+        match mode {
+            NodeMode::Always => 0,
+            NodeMode::OnEvent => 1,
+            NodeMode::Never => 2,
+            NodeMode::OnTrigger => 3,
+            NodeMode::Bypass => 4,
+            NodeMode::Unknown(other) => other,
+        }
+    }
+}
+
+impl Serialize for NodeMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8((*self).into())
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(NodeMode::from(u8::deserialize(deserializer)?))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkflowNode {
+    #[serde(default)]
+    pub id: NodeId,
     #[serde(rename = "type")]
     pub node_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pos: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Value>,
+    #[serde(default)]
+    pub flags: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    #[serde(default)]
+    pub mode: NodeMode,
+    #[serde(default)]
+    pub inputs: Vec<NodeInput>,
+    #[serde(default)]
+    pub outputs: Vec<NodeOutput>,
+    #[serde(default)]
+    pub properties: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub widgets_values: Option<WidgetValues>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bgcolor: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Widget values are positional for most nodes, but some packs (e.g. VHS)
+/// serialize them as a name -> value map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WidgetValues {
+    List(Vec<Value>),
+    Map(Map<String, Value>),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct WorkflowJson {
-    nodes: Option<Vec<WorkflowNodeRaw>>,
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeInput {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized_name: Option<String>,
+    #[serde(rename = "type", default, deserialize_with = "deserialize_slot_type")]
+    pub input_type: String,
+    #[serde(default)]
+    pub link: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub widget: Option<InputWidget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct WorkflowNodeRaw {
-    #[serde(rename = "type")]
-    node_type: String,
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputWidget {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeOutput {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized_name: Option<String>,
+    #[serde(rename = "type", default, deserialize_with = "deserialize_slot_type")]
+    pub output_type: String,
+    #[serde(default)]
+    pub links: Option<Vec<u64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot_index: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A connection from an output slot of one node to an input slot of another.
+///
+/// Serialized by the frontend either as `[id, origin_id, origin_slot,
+/// target_id, target_slot, type]` or as an object with the same fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowLink {
+    pub id: u64,
+    pub origin_id: NodeId,
+    pub origin_slot: u32,
+    pub target_id: NodeId,
+    pub target_slot: u32,
+    pub link_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WorkflowLinkRaw {
+    Array(u64, NodeId, u32, NodeId, u32, Value),
+    Object {
+        id: u64,
+        origin_id: NodeId,
+        origin_slot: u32,
+        target_id: NodeId,
+        target_slot: u32,
+        #[serde(rename = "type", default)]
+        link_type: Value,
+    },
+}
+
+impl<'de> Deserialize<'de> for WorkflowLink {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (id, origin_id, origin_slot, target_id, target_slot, link_type) =
+            match WorkflowLinkRaw::deserialize(deserializer)? {
+                WorkflowLinkRaw::Array(id, origin_id, origin_slot, target_id, target_slot, t) => {
+                    (id, origin_id, origin_slot, target_id, target_slot, t)
+                }
+                WorkflowLinkRaw::Object {
+                    id,
+                    origin_id,
+                    origin_slot,
+                    target_id,
+                    target_slot,
+                    link_type,
+                } => (
+                    id,
+                    origin_id,
+                    origin_slot,
+                    target_id,
+                    target_slot,
+                    link_type,
+                ),
+            };
+
+        Ok(WorkflowLink {
+            id,
+            origin_id,
+            origin_slot,
+            target_id,
+            target_slot,
+            link_type: slot_type_to_string(link_type),
+        })
+    }
+}
+
+impl Serialize for WorkflowLink {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (
+            self.id,
+            &self.origin_id,
+            self.origin_slot,
+            &self.target_id,
+            self.target_slot,
+            &self.link_type,
+        )
+            .serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowGroup {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub bounding: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<f64>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Reusable definitions shared by the whole workflow. Subgraphs nested in
+/// other subgraphs are stored here too, not inside their parent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowDefinitions {
+    #[serde(default)]
+    pub subgraphs: Vec<Subgraph>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A subgraph definition. Instances are nodes whose `type` is the
+/// subgraph's UUID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Subgraph {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub nodes: Vec<WorkflowNode>,
+    #[serde(default)]
+    pub links: Vec<WorkflowLink>,
+    #[serde(default)]
+    pub groups: Vec<WorkflowGroup>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Type prefixes of legacy group-node instances, defined under
+/// `extra.groupNodes`. Older frontends used `/`.
+const GROUP_NODE_PREFIXES: [&str; 2] = ["workflow>", "workflow/"];
+
+/// Slot types are usually strings ("IMAGE", "*"), but LiteGraph also writes
+/// numeric event types and the occasional null.
+fn deserialize_slot_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(slot_type_to_string(Value::deserialize(deserializer)?))
+}
+
+fn slot_type_to_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        Value::Null => "*".to_string(),
+        other => other.to_string(),
+    }
+}
+
+impl WorkflowGraph {
+    pub fn from_json_str(contents: &str) -> Result<Self> {
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    pub fn from_value(value: Value) -> Result<Self> {
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub fn get_node(&self, id: &NodeId) -> Option<&WorkflowNode> {
+        self.nodes.iter().find(|n| &n.id == id)
+    }
+
+    pub fn get_link(&self, id: u64) -> Option<&WorkflowLink> {
+        self.links.iter().find(|l| l.id == id)
+    }
+
+    pub fn links_into(&self, id: &NodeId) -> Vec<&WorkflowLink> {
+        let mut links: Vec<_> = self.links.iter().filter(|l| &l.target_id == id).collect();
+        links.sort_by_key(|l| l.target_slot);
+        links
+    }
+
+    pub fn active_nodes(&self) -> impl Iterator<Item = &WorkflowNode> {
+        self.nodes.iter().filter(|n| n.is_active())
+    }
+
+    pub fn subgraphs(&self) -> &[Subgraph] {
+        self.definitions
+            .as_ref()
+            .map(|d| d.subgraphs.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn get_subgraph(&self, id: &str) -> Option<&Subgraph> {
+        self.subgraphs().iter().find(|s| s.id == id)
+    }
+
+    pub fn get_group_node(&self, name: &str) -> Option<Vec<WorkflowNode>> {
+        let definition = self.extra.get("groupNodes")?.get(name)?;
+        serde_json::from_value(definition.get("nodes")?.clone()).ok()
+    }
+
+    /// Every real node, with subgraph and group-node instances replaced by
+    /// their contents, recursively. Instances of the same subgraph are
+    /// expanded each time, so node counts match what actually runs.
+    pub fn expanded_nodes(&self) -> Vec<WorkflowNode> {
+        let mut expanded = Vec::new();
+        self.expand_into(&self.nodes, &mut Vec::new(), &mut expanded);
+        expanded
+    }
+
+    fn expand_into(
+        &self,
+        nodes: &[WorkflowNode],
+        stack: &mut Vec<String>,
+        expanded: &mut Vec<WorkflowNode>,
+    ) {
+        for node in nodes {
+            let inner = if let Some(subgraph) = self.get_subgraph(&node.node_type) {
+                Some(subgraph.nodes.clone())
+            } else {
+                GROUP_NODE_PREFIXES
+                    .iter()
+                    .find_map(|prefix| node.node_type.strip_prefix(prefix))
+                    .and_then(|name| self.get_group_node(name))
+            };
+
+            match inner {
+                // A definition that contains itself is skipped rather than
+                // expanded forever
+                Some(_) if stack.contains(&node.node_type) => {}
+                Some(inner) => {
+                    stack.push(node.node_type.clone());
+                    self.expand_into(&inner, stack, expanded);
+                    stack.pop();
+                }
+                None => expanded.push(node.clone()),
+            }
+        }
+    }
+}
+
+impl WorkflowNode {
+    pub fn is_muted(&self) -> bool {
+        self.mode == NodeMode::Never
+    }
+
+    pub fn is_bypassed(&self) -> bool {
+        self.mode == NodeMode::Bypass
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.is_muted() && !self.is_bypassed()
+    }
+
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.node_type)
+    }
+
+    pub fn widget_list(&self) -> &[Value] {
+        match &self.widgets_values {
+            Some(WidgetValues::List(values)) => values,
+            _ => &[],
+        }
+    }
 }
 
 impl Workflow {
+    pub const UNKNOWN_PACK: &'static str = "unknown";
+
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         let contents = fs::read_to_string(path)
             .with_context(|| format!("Failed to read workflow file: {}", path.display()))?;
 
-        let workflow_json: WorkflowJson = serde_json::from_str(&contents)
+        let value: Value = serde_json::from_str(&contents)
             .with_context(|| format!("Failed to parse workflow JSON: {}", path.display()))?;
 
-        let nodes = workflow_json
-            .nodes
-            .unwrap_or_default()
-            .into_iter()
-            .map(|n| WorkflowNode {
-                node_type: n.node_type,
-            })
-            .collect::<Vec<_>>();
+        Self::from_value(path, value)
+    }
+
+    pub fn from_value<P: AsRef<Path>>(path: P, value: Value) -> Result<Self> {
+        let path = path.as_ref();
+        let (graph, api_prompt) = if ApiPrompt::detect(&value) {
+            let prompt = ApiPrompt::from_value(value)
+                .with_context(|| format!("Failed to parse API prompt: {}", path.display()))?;
+            (prompt.to_graph(), Some(prompt))
+        } else {
+            let graph = WorkflowGraph::from_value(value)
+                .with_context(|| format!("Failed to parse workflow JSON: {}", path.display()))?;
+            (graph, None)
+        };
 
-        let node_count = nodes.len();
+        let nodes = graph.expanded_nodes();
 
         // Extract pack name from filename: all-nodes-{pack-name}.json
         let pack_name = path
             .file_stem()
             .and_then(|s| s.to_str())
             .and_then(|s| s.strip_prefix("all-nodes-"))
-            .unwrap_or("unknown")
+            .unwrap_or(Self::UNKNOWN_PACK)
             .to_string();
 
         Ok(Workflow {
             file_path: path.to_path_buf(),
             pack_name,
-            node_count,
+            kind: WorkflowKind::from_path(path),
+            node_count: nodes.len(),
+            graph,
+            api_prompt,
             nodes,
         })
     }
 
     pub fn load_all<P: AsRef<Path>>(workflows_dir: P) -> Result<HashMap<String, Workflow>> {
-        let mut workflows = HashMap::new();
-
-        for entry in WalkDir::new(workflows_dir.as_ref())
-            .max_depth(1)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file()
-                && path.extension().and_then(|s| s.to_str()) == Some("json")
-                && path
-                    .file_name()
-                    .and_then(|s| s.to_str())
-                    .map(|s| s.starts_with("all-nodes-"))
-                    .unwrap_or(false)
-            {
-                match Workflow::from_file(path) {
-                    Ok(workflow) => {
-                        workflows.insert(workflow.pack_name.clone(), workflow);
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
-                    }
-                }
+        Ok(Self::by_pack(Self::load_kind(
+            workflows_dir,
+            WorkflowKind::AllNodes,
+        )?))
+    }
+
+    /// Key all-nodes workflows by pack name; later files win.
+    pub fn by_pack(all_nodes: Vec<Workflow>) -> HashMap<String, Workflow> {
+        let mut workflows: HashMap<String, Workflow> = HashMap::new();
+
+        for workflow in all_nodes {
+            if let Some(existing) = workflows.get(&workflow.pack_name) {
+                eprintln!(
+                    "Warning: {} duplicates pack '{}' from {}, using the latter",
+                    workflow.file_path.display(),
+                    workflow.pack_name,
+                    existing.file_path.display()
+                );
             }
+            workflows.insert(workflow.pack_name.clone(), workflow);
         }
 
-        Ok(workflows)
+        workflows
+    }
+
+    pub fn load_kind<P: AsRef<Path>>(
+        workflows_dir: P,
+        kind: WorkflowKind,
+    ) -> Result<Vec<Workflow>> {
+        let workflows_dir = workflows_dir.as_ref();
+        WorkflowDiscovery::load(workflows_dir)?.discover_kind(workflows_dir, kind)
+    }
+
+    pub fn discover<P: AsRef<Path>>(workflows_dir: P) -> Result<Vec<Workflow>> {
+        let workflows_dir = workflows_dir.as_ref();
+        WorkflowDiscovery::load(workflows_dir)?.discover(workflows_dir)
+    }
+
+    pub fn is_api_format(&self) -> bool {
+        self.api_prompt.is_some()
+    }
+
+    pub fn nodes(&self) -> &[WorkflowNode] {
+        &self.nodes
     }
 
     pub fn get_unique_node_types(&self) -> Vec<String> {
-        let mut types: Vec<String> = self.nodes.iter().map(|n| n.node_type.clone()).collect();
+        let mut types: Vec<String> = self.nodes().iter().map(|n| n.node_type.clone()).collect();
         types.sort();
         types.dedup();
         types
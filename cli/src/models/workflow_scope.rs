@@ -0,0 +1,132 @@
+use super::{Catalog, Metadata, Workflow, WorkflowDiscovery, WorkflowKind};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// The workflow folders a project draws evidence from.
+///
+/// A project lists its own folders as `workflow_roots` in `metadata.json`.
+/// Projects without any use the shared `workflows/` folder, minus the folders
+/// other projects have claimed, so one project's packs never leak into
+/// another's checklist.
+#[derive(Debug, Clone)]
+pub struct WorkflowScope {
+    pub roots: Vec<PathBuf>,
+    /// Folders inside `roots` that belong to other projects.
+    pub excluded: Vec<PathBuf>,
+}
+
+impl WorkflowScope {
+    pub const SHARED_DIR: &'static str = "workflows";
+
+    pub fn shared(repo_root: &Path) -> Self {
+        Self {
+            roots: vec![repo_root.join(Self::SHARED_DIR)],
+            excluded: Vec::new(),
+        }
+    }
+
+    pub fn for_project(repo_root: &Path, project: &str) -> Result<Self> {
+        let checklists_dir = repo_root.join("checklists");
+        let own = project_roots(&checklists_dir.join(project))?;
+        if !own.is_empty() {
+            return Ok(Self {
+                roots: own.iter().map(|root| repo_root.join(root)).collect(),
+                excluded: Vec::new(),
+            });
+        }
+
+        let mut excluded = Vec::new();
+        if checklists_dir.is_dir() {
+            for entry in fs::read_dir(&checklists_dir)?.filter_map(|e| e.ok()) {
+                if entry.file_name() == project || !entry.path().is_dir() {
+                    continue;
+                }
+                // Someone else's broken metadata shouldn't stop this project
+                match project_roots(&entry.path()) {
+                    Ok(roots) => excluded.extend(roots.iter().map(|root| repo_root.join(root))),
+                    Err(e) => eprintln!(
+                        "Warning: ignoring the workflow roots of {}: {:#}",
+                        entry.file_name().to_string_lossy(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        Ok(Self {
+            excluded,
+            ..Self::shared(repo_root)
+        })
+    }
+
+    /// Where new workflows for the project are saved.
+    pub fn primary(&self) -> &Path {
+        &self.roots[0]
+    }
+
+    pub fn discovery(&self) -> Result<WorkflowDiscovery> {
+        WorkflowDiscovery::load(self.primary())
+    }
+
+    pub fn load_all(&self) -> Result<HashMap<String, Workflow>> {
+        Ok(Workflow::by_pack(self.load_kind(WorkflowKind::AllNodes)?))
+    }
+
+    /// Executable workflows across the roots. Those whose filename names no
+    /// pack get the pack most of their nodes come from.
+    pub fn load_executable(&self, catalog: &Catalog) -> Result<Vec<Workflow>> {
+        let mut workflows = self.load_kind(WorkflowKind::Executable)?;
+        for workflow in &mut workflows {
+            if workflow.pack_name == Workflow::UNKNOWN_PACK {
+                if let Some(pack) = catalog.attribute(workflow).primary_pack() {
+                    workflow.pack_name = pack.to_string();
+                }
+            }
+        }
+        Ok(workflows)
+    }
+
+    pub fn load_kind(&self, kind: WorkflowKind) -> Result<Vec<Workflow>> {
+        self.collect(|root| Workflow::load_kind(root, kind))
+    }
+
+    pub fn discover(&self) -> Result<Vec<Workflow>> {
+        self.collect(|root| Workflow::discover(root))
+    }
+
+    fn collect(&self, load: impl Fn(&Path) -> Result<Vec<Workflow>>) -> Result<Vec<Workflow>> {
+        let mut workflows = Vec::new();
+        for root in &self.roots {
+            workflows.extend(
+                load(root)?
+                    .into_iter()
+                    .filter(|w| !self.excluded.iter().any(|e| w.file_path.starts_with(e))),
+            );
+        }
+        Ok(workflows)
+    }
+}
+
+fn project_roots(project_dir: &Path) -> Result<Vec<String>> {
+    let metadata_path = project_dir.join("metadata.json");
+    if !metadata_path.exists() {
+        return Ok(Vec::new());
+    }
+    let metadata = Metadata::from_file(&metadata_path)
+        .with_context(|| format!("Failed to load {}", metadata_path.display()))?;
+
+    // Roots are relative to the repository and must stay inside it
+    for root in &metadata.workflow_roots {
+        let path = Path::new(root);
+        if path.has_root() || path.components().any(|c| c == Component::ParentDir) {
+            anyhow::bail!(
+                "{}: workflow root '{}' must be a path inside the repository",
+                metadata_path.display(),
+                root
+            );
+        }
+    }
+    Ok(metadata.workflow_roots)
+}
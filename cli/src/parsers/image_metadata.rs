@@ -0,0 +1,206 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// TIFF field type for NUL-terminated ASCII strings.
+const TIFF_ASCII: u16 = 2;
+
+#[derive(Debug, Clone, Default)]
+pub struct EmbeddedWorkflow {
+    /// UI graph (`workflow` key).
+    pub workflow: Option<Value>,
+    /// API prompt (`prompt` key).
+    pub prompt: Option<Value>,
+}
+
+impl EmbeddedWorkflow {
+    pub fn is_empty(&self) -> bool {
+        self.workflow.is_none() && self.prompt.is_none()
+    }
+
+    fn insert(&mut self, key: &str, text: &str) {
+        let slot = match key {
+            "workflow" => &mut self.workflow,
+            "prompt" => &mut self.prompt,
+            _ => return,
+        };
+        // Unparseable text is treated as absent rather than failing the image
+        if let Ok(value) = serde_json::from_str(text.trim_end_matches('\0')) {
+            *slot = Some(value);
+        }
+    }
+}
+
+pub struct ImageMetadataParser;
+
+impl ImageMetadataParser {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<EmbeddedWorkflow> {
+        let path = path.as_ref();
+        let bytes =
+            fs::read(path).with_context(|| format!("Failed to read image: {}", path.display()))?;
+        Self::parse(&bytes).with_context(|| format!("Failed to read metadata: {}", path.display()))
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<EmbeddedWorkflow> {
+        if bytes.starts_with(&PNG_SIGNATURE) {
+            Self::parse_png(bytes)
+        } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Self::parse_webp(bytes)
+        } else {
+            bail!("Unsupported image format (expected PNG or WebP)")
+        }
+    }
+
+    /// PNG stores each key in its own `tEXt` (or uncompressed `iTXt`) chunk.
+    fn parse_png(bytes: &[u8]) -> Result<EmbeddedWorkflow> {
+        let mut embedded = EmbeddedWorkflow::default();
+        let mut offset = PNG_SIGNATURE.len();
+
+        while offset + 8 <= bytes.len() {
+            let length = u32::from_be_bytes(bytes[offset..offset + 4].try_into()?) as usize;
+            let chunk_type = &bytes[offset + 4..offset + 8];
+            let data_start = offset + 8;
+            let data_end = data_start + length;
+            if data_end > bytes.len() {
+                bail!("Truncated PNG chunk");
+            }
+            let data = &bytes[data_start..data_end];
+
+            match chunk_type {
+                b"tEXt" => {
+                    if let Some((key, text)) = split_nul(data) {
+                        embedded.insert(&latin1(key), &latin1(text));
+                    }
+                }
+                b"iTXt" => {
+                    if let Some((key, text)) = parse_itxt(data) {
+                        embedded.insert(&key, &text);
+                    }
+                }
+                b"IEND" => break,
+                _ => {}
+            }
+
+            // Skip the CRC
+            offset = data_end + 4;
+        }
+
+        Ok(embedded)
+    }
+
+    /// WebP keeps `key:json` strings as ASCII entries of an EXIF chunk.
+    fn parse_webp(bytes: &[u8]) -> Result<EmbeddedWorkflow> {
+        let mut embedded = EmbeddedWorkflow::default();
+        let mut offset = 12;
+
+        while offset + 8 <= bytes.len() {
+            let fourcc = &bytes[offset..offset + 4];
+            let size = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into()?) as usize;
+            let data_start = offset + 8;
+            let data_end = (data_start + size).min(bytes.len());
+
+            if fourcc == b"EXIF" {
+                let data = &bytes[data_start..data_end];
+                let tiff = data.strip_prefix(b"Exif\0\0").unwrap_or(data);
+                for text in tiff_ascii_entries(tiff) {
+                    if let Some((key, value)) = text.split_once(':') {
+                        embedded.insert(key, value);
+                    }
+                }
+            }
+
+            // Chunks are padded to an even size
+            offset = data_start + size + (size & 1);
+        }
+
+        Ok(embedded)
+    }
+}
+
+fn split_nul(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let pos = data.iter().position(|&b| b == 0)?;
+    Some((&data[..pos], &data[pos + 1..]))
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// `keyword\0 flag method lang\0 translated\0 text`; compressed text is
+/// skipped since Hanzo Studio never writes it.
+fn parse_itxt(data: &[u8]) -> Option<(String, String)> {
+    let (key, rest) = split_nul(data)?;
+    let (&compressed, rest) = rest.split_first()?;
+    if compressed != 0 {
+        return None;
+    }
+    let rest = rest.get(1..)?;
+    let (_, rest) = split_nul(rest)?;
+    let (_, text) = split_nul(rest)?;
+    Some((latin1(key), String::from_utf8_lossy(text).into_owned()))
+}
+
+/// ASCII values from the first IFD of a TIFF structure.
+fn tiff_ascii_entries(tiff: &[u8]) -> Vec<String> {
+    let little_endian = match tiff.get(0..2) {
+        Some(b"II") => true,
+        Some(b"MM") => false,
+        _ => return Vec::new(),
+    };
+    let read_u16 = |at: usize| -> Option<u16> {
+        let raw: [u8; 2] = tiff.get(at..at + 2)?.try_into().ok()?;
+        Some(if little_endian {
+            u16::from_le_bytes(raw)
+        } else {
+            u16::from_be_bytes(raw)
+        })
+    };
+    let read_u32 = |at: usize| -> Option<u32> {
+        let raw: [u8; 4] = tiff.get(at..at + 4)?.try_into().ok()?;
+        Some(if little_endian {
+            u32::from_le_bytes(raw)
+        } else {
+            u32::from_be_bytes(raw)
+        })
+    };
+
+    let mut values = Vec::new();
+    let Some(ifd) = read_u32(4).map(|o| o as usize) else {
+        return values;
+    };
+    let Some(count) = read_u16(ifd) else {
+        return values;
+    };
+
+    for i in 0..count as usize {
+        let entry = ifd + 2 + i * 12;
+        let (Some(field_type), Some(length)) = (read_u16(entry + 2), read_u32(entry + 4)) else {
+            break;
+        };
+        if field_type != TIFF_ASCII {
+            continue;
+        }
+        let length = length as usize;
+        // Values up to 4 bytes are stored inline, longer ones at an offset
+        let start = if length <= 4 {
+            entry + 8
+        } else {
+            match read_u32(entry + 8) {
+                Some(offset) => offset as usize,
+                None => continue,
+            }
+        };
+        if let Some(raw) = tiff.get(start..start + length) {
+            values.push(
+                String::from_utf8_lossy(raw)
+                    .trim_end_matches('\0')
+                    .to_string(),
+            );
+        }
+    }
+
+    values
+}
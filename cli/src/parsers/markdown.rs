@@ -1,19 +1,32 @@
+use crate::models::TestOutcome;
 use regex::Regex;
 
 pub struct MarkdownParser;
 
 impl MarkdownParser {
     pub fn extract_checkbox_items(content: &str) -> Vec<(bool, String, Option<usize>)> {
-        let re = Regex::new(r"^- \[([ x])\] (.+?)(?: \((\d+)\))?$").unwrap();
+        Self::extract_outcome_items(content)
+            .into_iter()
+            .map(|(outcome, name, count)| (outcome.is_tested(), name, count))
+            .collect()
+    }
+
+    /// Like `extract_checkbox_items`, keeping the full outcome marker.
+    pub fn extract_outcome_items(content: &str) -> Vec<(TestOutcome, String, Option<usize>)> {
+        let re = Regex::new(&format!(
+            r"^- \[({})\] (.+?)(?: \((\d+)\))?(?:\s*<!--.*-->)?\s*$",
+            TestOutcome::MARKER_CLASS
+        ))
+        .unwrap();
         let mut items = Vec::new();
 
         for line in content.lines() {
             if let Some(caps) = re.captures(line) {
-                let checked = &caps[1] == "x";
+                let outcome = TestOutcome::from_marker(&caps[1]).unwrap_or_default();
                 let name = caps[2].trim().to_string();
                 let count = caps.get(3).and_then(|m| m.as_str().parse::<usize>().ok());
 
-                items.push((checked, name, count));
+                items.push((outcome, name, count));
             }
         }
 
@@ -1,5 +1,7 @@
+mod image_metadata;
 mod markdown;
 mod workflow_json;
 
+pub use image_metadata::{EmbeddedWorkflow, ImageMetadataParser};
 pub use markdown::MarkdownParser;
 pub use workflow_json::WorkflowJsonParser;
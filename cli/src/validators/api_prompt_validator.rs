@@ -0,0 +1,115 @@
+use crate::models::ApiPrompt;
+use crate::validators::{ValidationError, ValidationResult};
+use std::collections::{HashMap, HashSet};
+
+pub struct ApiPromptValidator;
+
+impl ApiPromptValidator {
+    /// Structural checks that `/prompt` would otherwise reject. `label` names
+    /// the prompt in the results (usually its file name).
+    pub fn validate(prompt: &ApiPrompt, label: Option<&str>) -> Vec<ValidationResult> {
+        let mut results = Vec::new();
+        let label = label.map(|s| s.to_string());
+
+        if prompt.is_empty() {
+            results.push(ValidationResult::error(
+                ValidationError::InvalidPrompt("prompt has no nodes".to_string()).to_string(),
+                label,
+            ));
+            return results;
+        }
+
+        for id in prompt.sorted_ids() {
+            let node = &prompt.nodes[id];
+
+            if node.class_type.trim().is_empty() {
+                results.push(ValidationResult::error(
+                    ValidationError::InvalidPrompt(format!("node {} has no class_type", id))
+                        .to_string(),
+                    label.clone(),
+                ));
+            }
+
+            for (input, origin, _) in node.links() {
+                if origin == id {
+                    results.push(ValidationResult::error(
+                        ValidationError::InvalidPrompt(format!(
+                            "node {} input '{}' is linked to itself",
+                            id, input
+                        ))
+                        .to_string(),
+                        label.clone(),
+                    ));
+                } else if !prompt.nodes.contains_key(origin) {
+                    results.push(ValidationResult::error(
+                        ValidationError::InvalidPrompt(format!(
+                            "node {} input '{}' links to missing node {}",
+                            id, input, origin
+                        ))
+                        .to_string(),
+                        label.clone(),
+                    ));
+                }
+            }
+        }
+
+        if let Some(id) = Self::find_cycle(prompt) {
+            results.push(ValidationResult::error(
+                ValidationError::InvalidPrompt(format!("dependency cycle through node {}", id))
+                    .to_string(),
+                label,
+            ));
+        }
+
+        results
+    }
+
+    fn find_cycle(prompt: &ApiPrompt) -> Option<String> {
+        // 0 = unvisited, 1 = on the current path, 2 = done
+        let mut state: HashMap<&str, u8> = HashMap::new();
+
+        for start in prompt.sorted_ids() {
+            if state.get(start.as_str()).copied().unwrap_or(0) != 0 {
+                continue;
+            }
+
+            let mut stack: Vec<(&str, Vec<&str>)> = vec![(start, Self::upstream(prompt, start))];
+            state.insert(start, 1);
+
+            while let Some((node, pending)) = stack.last_mut() {
+                match pending.pop() {
+                    Some(next) => match state.get(next).copied().unwrap_or(0) {
+                        0 => {
+                            state.insert(next, 1);
+                            let upstream = Self::upstream(prompt, next);
+                            stack.push((next, upstream));
+                        }
+                        1 => return Some(next.to_string()),
+                        _ => {}
+                    },
+                    None => {
+                        state.insert(node, 2);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn upstream<'a>(prompt: &'a ApiPrompt, id: &str) -> Vec<&'a str> {
+        let mut seen = HashSet::new();
+        prompt
+            .nodes
+            .get(id)
+            .map(|node| {
+                node.links()
+                    .map(|(_, origin, _)| origin.as_str())
+                    .filter(|origin| *origin != id && prompt.nodes.contains_key(*origin))
+                    .filter(|origin| seen.insert(*origin))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
@@ -46,7 +46,7 @@ impl ChecklistValidator {
         let mut results = Vec::new();
 
         for pack in &checklist.packs {
-            if pack.tested && !workflows.contains_key(&pack.name) {
+            if pack.tested() && !workflows.contains_key(&pack.name) {
                 results.push(ValidationResult::error(
                     ValidationError::MissingWorkflow(pack.name.clone()).to_string(),
                     Some(pack.name.clone()),
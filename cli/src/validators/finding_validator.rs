@@ -0,0 +1,78 @@
+use crate::models::{
+    Catalog, Checklist, DetailedChecklist, Finding, DEPRECATED_SUFFIX, REMOVED_SUFFIX,
+};
+use crate::validators::{ValidationError, ValidationResult};
+
+pub struct FindingValidator;
+
+impl FindingValidator {
+    /// Check that the finding's pack is in the checklist and, when the
+    /// detailed checklist lists the pack's nodes, that its node is one of them.
+    /// `catalog` lets the node go by its class name.
+    pub fn validate(
+        finding: &Finding,
+        checklist: &Checklist,
+        detailed: Option<&DetailedChecklist>,
+        catalog: &Catalog,
+    ) -> Vec<ValidationResult> {
+        let mut results = Vec::new();
+
+        let Some(pack_name) = &finding.pack else {
+            results.push(ValidationResult::error(
+                "Finding has no pack".to_string(),
+                None,
+            ));
+            return results;
+        };
+
+        let Some(pack) = Self::find_pack(checklist, pack_name) else {
+            results.push(ValidationResult::error(
+                ValidationError::UnknownPack(pack_name.clone()).to_string(),
+                Some(pack_name.clone()),
+            ));
+            return results;
+        };
+
+        if let Some(node) = &finding.node {
+            let nodes = detailed.map(|d| d.node_names(pack)).unwrap_or_default();
+            if nodes.is_empty() {
+                results.push(ValidationResult::warning(
+                    format!("No detailed checklist nodes to check '{}' against", node),
+                    Some(pack.to_string()),
+                ));
+            } else if !nodes.iter().any(|n| Self::names_node(n, node, catalog)) {
+                results.push(ValidationResult::error(
+                    ValidationError::UnknownNode {
+                        pack: pack.to_string(),
+                        node: node.clone(),
+                    }
+                    .to_string(),
+                    Some(pack.to_string()),
+                ));
+            }
+        }
+
+        results
+    }
+
+    /// Whether the detailed checklist item `item` is `node`, ignoring case
+    /// and the removed and deprecated suffixes.
+    fn names_node(item: &str, node: &str, catalog: &Catalog) -> bool {
+        let name = item
+            .trim_end_matches(REMOVED_SUFFIX)
+            .trim_end_matches(DEPRECATED_SUFFIX);
+        name.eq_ignore_ascii_case(node)
+            || catalog
+                .resolve(node)
+                .is_some_and(|(class, _)| catalog.lists(item, class))
+    }
+
+    /// The checklist's spelling of `name`, matched case-insensitively.
+    pub fn find_pack<'a>(checklist: &'a Checklist, name: &str) -> Option<&'a str> {
+        checklist
+            .packs
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .map(|p| p.name.as_str())
+    }
+}
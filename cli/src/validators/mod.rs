@@ -1,8 +1,12 @@
+mod api_prompt_validator;
 mod checklist_validator;
+mod finding_validator;
 mod naming_validator;
 mod workflow_validator;
 
+pub use api_prompt_validator::ApiPromptValidator;
 pub use checklist_validator::ChecklistValidator;
+pub use finding_validator::FindingValidator;
 pub use naming_validator::NamingValidator;
 pub use workflow_validator::WorkflowValidator;
 
@@ -34,6 +38,18 @@ pub enum ValidationError {
 
     #[error("Metadata validation failed: {0}")]
     MetadataValidation(String),
+
+    #[error("Invalid API prompt: {0}")]
+    InvalidPrompt(String),
+
+    #[error("Broken link: {0}")]
+    BrokenLink(String),
+
+    #[error("Pack '{0}' is not in the checklist")]
+    UnknownPack(String),
+
+    #[error("Node '{node}' is not in the detailed checklist for '{pack}'")]
+    UnknownNode { pack: String, node: String },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
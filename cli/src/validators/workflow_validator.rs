@@ -1,6 +1,6 @@
 use crate::models::Workflow;
-use crate::validators::ValidationResult;
-use std::collections::HashMap;
+use crate::validators::{ApiPromptValidator, ValidationError, ValidationResult};
+use std::collections::{HashMap, HashSet};
 
 pub struct WorkflowValidator;
 
@@ -20,4 +20,48 @@ impl WorkflowValidator {
 
         results
     }
+
+    /// Structural checks for workflows that are meant to be queued: API
+    /// prompts must be well formed and UI links must point at real nodes.
+    pub fn validate_files(workflows: &[Workflow]) -> Vec<ValidationResult> {
+        let mut results = Vec::new();
+
+        for workflow in workflows {
+            let label = workflow
+                .file_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            match &workflow.api_prompt {
+                Some(prompt) => results.extend(ApiPromptValidator::validate(prompt, Some(&label))),
+                None => results.extend(Self::check_links(workflow, &label)),
+            }
+        }
+
+        results
+    }
+
+    fn check_links(workflow: &Workflow, label: &str) -> Vec<ValidationResult> {
+        let mut results = Vec::new();
+        let node_ids: HashSet<_> = workflow.graph.nodes.iter().map(|n| &n.id).collect();
+
+        for link in &workflow.graph.links {
+            for (end, id) in [("source", &link.origin_id), ("target", &link.target_id)] {
+                if !node_ids.contains(id) {
+                    results.push(ValidationResult::error(
+                        ValidationError::BrokenLink(format!(
+                            "link {} {} node {} does not exist",
+                            link.id, end, id
+                        ))
+                        .to_string(),
+                        Some(label.to_string()),
+                    ));
+                }
+            }
+        }
+
+        results
+    }
 }
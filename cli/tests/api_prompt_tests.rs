@@ -0,0 +1,89 @@
+use comfy_qa::models::{ApiInput, ApiPrompt, Workflow};
+use comfy_qa::validators::ApiPromptValidator;
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn sample_prompt() -> serde_json::Value {
+    json!({
+        "1": {
+            "class_type": "LoadImage",
+            "inputs": {"image": "example.png"},
+            "_meta": {"title": "Load Image"}
+        },
+        "2": {
+            "class_type": "ImageResizeKJ",
+            "inputs": {"image": ["1", 0], "width": 512, "height": 512}
+        },
+        "10": {
+            "class_type": "PreviewImage",
+            "inputs": {"images": ["2", 0]}
+        }
+    })
+}
+
+#[test]
+fn test_api_prompt_workflow_counts_node_types() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("resize-api.json");
+    fs::write(&path, sample_prompt().to_string()).unwrap();
+
+    let workflow = Workflow::from_file(&path).unwrap();
+
+    assert!(workflow.is_api_format());
+    assert_eq!(workflow.node_count, 3);
+    assert_eq!(
+        workflow.get_unique_node_types(),
+        vec!["ImageResizeKJ", "LoadImage", "PreviewImage"]
+    );
+    assert_eq!(workflow.graph.links.len(), 2);
+}
+
+#[test]
+fn test_api_prompt_parsing_and_serialization() {
+    let body = json!({"prompt": sample_prompt(), "client_id": "abc"});
+    let prompt = ApiPrompt::from_value(body).unwrap();
+
+    assert_eq!(prompt.sorted_ids(), vec!["1", "2", "10"]);
+    assert_eq!(
+        prompt.nodes["2"].inputs["image"],
+        ApiInput::Link("1".to_string(), 0)
+    );
+    assert_eq!(prompt.nodes["1"].meta.as_ref().unwrap().title, "Load Image");
+
+    let round_trip: serde_json::Value = serde_json::to_value(&prompt).unwrap();
+    assert_eq!(round_trip, sample_prompt());
+    assert_eq!(prompt.to_request_body(Some("abc"))["client_id"], "abc");
+}
+
+#[test]
+fn test_api_prompt_validation() {
+    let valid = ApiPrompt::from_value(sample_prompt()).unwrap();
+    assert!(ApiPromptValidator::validate(&valid, None).is_empty());
+
+    let broken = ApiPrompt::from_value(json!({
+        "1": {"class_type": "NodeA", "inputs": {"x": ["2", 0]}},
+        "2": {"class_type": "NodeB", "inputs": {"y": ["1", 0]}},
+        "3": {"class_type": "NodeC", "inputs": {"z": ["99", 0]}}
+    }))
+    .unwrap();
+    let results = ApiPromptValidator::validate(&broken, Some("broken.json"));
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].message.contains("missing node 99"));
+    assert!(results[1].message.contains("cycle"));
+}
+
+#[test]
+fn test_sorted_ids_orders_subgraph_ids_by_segment() {
+    let node = json!({"class_type": "PreviewImage", "inputs": {}});
+    let prompt = ApiPrompt::from_value(json!({
+        "10:10": node, "10:5": node, "2": node, "10": node, "a": node, "10:2": node
+    }))
+    .unwrap();
+
+    assert_eq!(
+        prompt.sorted_ids(),
+        vec!["2", "10", "10:2", "10:5", "10:10", "a"]
+    );
+}
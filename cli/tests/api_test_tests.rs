@@ -0,0 +1,243 @@
+use comfy_qa::api::{ApiClient, ApiTestRunner};
+use comfy_qa::models::{
+    ApiTestStatus, ChecklistNode, DetailedChecklist, NodeApiResult, NodePack, PackApiResult,
+    PackMetadata, TestDimension, TestDimensions, TestOutcome,
+};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn object_info() -> Value {
+    let node = |output: &str| {
+        json!({
+            "input": {"required": {"value": ["INT", {"default": 1}]}},
+            "output": [output],
+            "output_node": true,
+            "python_module": "custom_nodes.pack1"
+        })
+    };
+    json!({
+        "Works": node("INT"),
+        "Rejected": node("INT"),
+        "Crashes": node("INT"),
+        "NeedsModel": {
+            "input": {"required": {"model": ["MODEL"]}},
+            "output": [],
+            "output_node": true,
+            "python_module": "custom_nodes.pack1"
+        }
+    })
+}
+
+/// Answers `/object_info`, `/prompt` and `/history/<id>` like a server
+/// whose `Rejected` node fails validation and `Crashes` node raises.
+fn stub_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+            let (status, response) = if path == "/object_info" {
+                (200, object_info())
+            } else if path == "/prompt" {
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let class_type = request["prompt"]["1"]["class_type"].as_str().unwrap();
+                if class_type == "Rejected" {
+                    (
+                        400,
+                        json!({
+                            "error": {"type": "prompt_outputs_failed_validation", "message": "Prompt outputs failed validation"},
+                            "node_errors": {"1": {"class_type": "Rejected", "errors": [
+                                {"type": "value_smaller_than_min", "message": "Value 1 smaller than min of 2", "details": "value"}
+                            ]}}
+                        }),
+                    )
+                } else {
+                    (
+                        200,
+                        json!({"prompt_id": class_type, "number": 1, "node_errors": {}}),
+                    )
+                }
+            } else if let Some(id) = path.strip_prefix("/history/") {
+                let status = if id == "Crashes" {
+                    json!({"status_str": "error", "completed": false, "messages": [
+                        ["execution_start", {"prompt_id": id}],
+                        ["execution_error", {"node_type": "Crashes", "exception_message": "CUDA out of memory\n"}]
+                    ]})
+                } else {
+                    json!({"status_str": "success", "completed": true, "messages": []})
+                };
+                (200, json!({ id: {"outputs": {}, "status": status} }))
+            } else {
+                (404, json!({}))
+            };
+
+            let body = response.to_string();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+        }
+    });
+
+    url
+}
+
+#[test]
+fn test_api_test_runner_classifies_nodes() {
+    let client = ApiClient::new(&stub_server());
+    let object_info = client.object_info().unwrap();
+    let mut runner = ApiTestRunner::new(&client, &object_info);
+    runner.timeout = Duration::from_secs(5);
+    runner.poll_interval = Duration::from_millis(10);
+
+    let nodes: Vec<_> = ["Works", "Rejected", "Crashes", "NeedsModel"]
+        .into_iter()
+        .map(|class| runner.run_node(class).unwrap())
+        .collect();
+    let statuses: Vec<_> = nodes.iter().map(|n| n.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ApiTestStatus::Executed,
+            ApiTestStatus::Failed,
+            ApiTestStatus::Failed,
+            ApiTestStatus::Blocked
+        ]
+    );
+    assert_eq!(nodes[0].prompt_id.as_deref(), Some("Works"));
+    let reason = nodes[1].reason.as_deref().unwrap();
+    assert!(reason.contains("Rejected: Value 1 smaller than min of 2: value"));
+    assert_eq!(
+        nodes[2].reason.as_deref(),
+        Some("Crashes: CUDA out of memory")
+    );
+    assert!(nodes[3]
+        .reason
+        .as_deref()
+        .unwrap()
+        .contains("'model' (MODEL)"));
+
+    let pack = PackApiResult {
+        pack: "pack1".to_string(),
+        nodes,
+        workflows: Vec::new(),
+    };
+    assert_eq!(pack.outcome(), TestOutcome::Fail);
+    assert_eq!(pack.issues().len(), 2);
+
+    runner.validate_only = true;
+    let validated = runner.run_node("Crashes").unwrap();
+    assert_eq!(validated.status, ApiTestStatus::Validated);
+    assert_eq!(validated.status.outcome(), TestOutcome::Pass);
+}
+
+#[test]
+fn test_api_results_are_written_to_checklists() {
+    // API results on an untested pack set the checkbox; re-runs replace it
+    let mut pack = NodePack::new("pack1".to_string(), 2, false);
+    pack.set_dimension_outcome(TestDimension::Api, TestOutcome::Fail);
+    assert_eq!(pack.outcome, TestOutcome::Fail);
+    assert_eq!(pack.dimensions.to_marker().as_deref(), Some("API tested"));
+    pack.set_dimension_outcome(TestDimension::Api, TestOutcome::Pass);
+    assert_eq!(pack.outcome, TestOutcome::Pass);
+    assert_eq!(
+        pack.dimension_outcome(TestDimension::Ui),
+        TestOutcome::Untested
+    );
+
+    // ...but don't overwrite a UI result
+    let mut pack = NodePack::new("pack1".to_string(), 2, true);
+    pack.set_dimension_outcome(TestDimension::Api, TestOutcome::Fail);
+    assert_eq!(pack.outcome, TestOutcome::Pass);
+    assert_eq!(
+        pack.dimensions.to_marker().as_deref(),
+        Some("UI tested, API tested: fail")
+    );
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(
+        &path,
+        "# Node Pack QA Checklist\n\n## pack0\n\n- [ ] Works\n\n## pack1\n\n- [x] Works\n- [ ] Other node <!-- note -->\n",
+    )
+    .unwrap();
+    let mut node = ChecklistNode {
+        name: "Works".to_string(),
+        outcome: TestOutcome::Pass,
+        dimensions: TestDimensions::default(),
+    };
+    node.set_dimension_outcome(TestDimension::Api, TestOutcome::Pass);
+    DetailedChecklist::write_nodes(&path, "pack1", &[node]).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "# Node Pack QA Checklist\n\n## pack0\n\n- [ ] Works\n\n## pack1\n\n- [x] Works <!-- Both -->\n- [ ] Other node <!-- note -->\n"
+    );
+}
+
+#[test]
+fn test_failed_api_run_on_ui_passed_pack_reaches_metadata() {
+    let mut pack = NodePack::new("pack1".to_string(), 1, true);
+    let result = PackApiResult {
+        pack: "pack1".to_string(),
+        nodes: vec![NodeApiResult::new(
+            "Crashes",
+            ApiTestStatus::Failed,
+            Some("CUDA out of memory".to_string()),
+        )],
+        workflows: Vec::new(),
+    };
+    pack.set_dimension_outcome(TestDimension::Api, result.outcome());
+    assert_eq!(pack.outcome, TestOutcome::Pass);
+
+    let mut metadata: PackMetadata = serde_json::from_value(json!({
+        "node_count": 1,
+        "tested": true,
+        "outcome": "pass",
+        "workflow_file": null,
+        "notes": null,
+        "issues": ["API test: Gone: old failure", "Crashes on 4K input"]
+    }))
+    .unwrap();
+    metadata.record_api_result(pack.outcome, &result);
+
+    let written = serde_json::to_value(&metadata).unwrap();
+    assert_eq!(written["outcome"], "pass");
+    assert_eq!(written["api_outcome"], "fail");
+    assert_eq!(
+        written["issues"],
+        json!([
+            "Crashes on 4K input",
+            "API test: Crashes: CUDA out of memory"
+        ])
+    );
+}
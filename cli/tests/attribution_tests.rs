@@ -0,0 +1,150 @@
+use comfy_qa::models::{Catalog, CatalogNode, Export, Workflow, WorkflowScope};
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn write_json(dir: &TempDir, name: &str, value: serde_json::Value) -> std::path::PathBuf {
+    let path = dir.path().join(name);
+    fs::write(&path, value.to_string()).unwrap();
+    path
+}
+
+#[test]
+fn test_catalog_from_export_attributes_mixed_workflow() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = write_json(
+        &temp_dir,
+        "export.json",
+        json!({
+            "version": "1.0",
+            "exported_at": "2024-01-01T00:00:00Z",
+            "project_name": "test-project",
+            "environment": {"url": "http://localhost:8188"},
+            "packs": [
+                {"name": "core", "node_count": 2, "nodes": [
+                    {"name": "LoadImage"}, {"name": "PreviewImage"}
+                ]},
+                {"name": "ComfyUI-KJNodes", "node_count": 2, "nodes": [
+                    {"name": "ImageResizeKJ"}, {"name": "GetImageSizeAndCount"}
+                ]}
+            ]
+        }),
+    );
+    let workflow_path = write_json(
+        &temp_dir,
+        "ComfyUI-KJNodes (1).json",
+        json!({"nodes": [
+            {"id": 1, "type": "LoadImage"},
+            {"id": 2, "type": "ImageResizeKJ"},
+            {"id": 3, "type": "GetImageSizeAndCount"},
+            {"id": 4, "type": "ImageResizeKJ"},
+            {"id": 5, "type": "Reroute"}
+        ]}),
+    );
+
+    let catalog = Catalog::from_export(&Export::from_file(&export_path).unwrap());
+    let workflow = Workflow::from_file(&workflow_path).unwrap();
+    let attribution = catalog.attribute(&workflow);
+
+    assert_eq!(workflow.pack_name, Workflow::UNKNOWN_PACK);
+    assert_eq!(attribution.pack_counts["core"], 1);
+    assert_eq!(attribution.pack_counts["ComfyUI-KJNodes"], 2);
+    assert_eq!(attribution.unknown, vec!["Reroute".to_string()]);
+    assert_eq!(attribution.primary_pack(), Some("ComfyUI-KJNodes"));
+}
+
+#[test]
+fn test_catalog_with_all_nodes_workflows_indexes_by_pack() {
+    let temp_dir = TempDir::new().unwrap();
+    let core = write_json(
+        &temp_dir,
+        "all-nodes-core.json",
+        json!({"nodes": [{"id": 1, "type": "LoadImage"}, {"id": 2, "type": "SaveImage"}]}),
+    );
+    let florence = write_json(
+        &temp_dir,
+        "all-nodes-florence2.json",
+        json!({"nodes": [{"id": 1, "type": "Florence2Run"}]}),
+    );
+    let executable = write_json(
+        &temp_dir,
+        "Florence2.json",
+        json!({"nodes": [
+            {"id": 1, "type": "LoadImage"},
+            {"id": 2, "type": "Florence2Run"}
+        ]}),
+    );
+
+    let all_nodes = [
+        Workflow::from_file(core).unwrap(),
+        Workflow::from_file(florence).unwrap(),
+    ];
+    let catalog = Catalog::default().with_workflows(&all_nodes);
+    let executable = [Workflow::from_file(executable).unwrap()];
+    let index = catalog.index_by_pack(&executable);
+
+    assert_eq!(catalog.nodes.len(), 3);
+    assert_eq!(index.len(), 2);
+    assert_eq!(index["core"][0].1, 1);
+    assert_eq!(index["florence2"][0].1, 1);
+}
+
+#[test]
+fn test_imported_catalog_wins_over_workflow_filenames() {
+    let temp_dir = TempDir::new().unwrap();
+    // The all-nodes workflow also holds a core node; the import knows better
+    let all_nodes = write_json(
+        &temp_dir,
+        "all-nodes-florence2.json",
+        json!({"nodes": [{"id": 1, "type": "Florence2Run"}, {"id": 2, "type": "LoadImage"}]}),
+    );
+    let mut catalog = Catalog::default();
+    catalog.nodes.insert(
+        "LoadImage".to_string(),
+        CatalogNode {
+            pack: "core".to_string(),
+            display_name: Some("Load Image".to_string()),
+            category: None,
+            deprecated: false,
+            removed: false,
+        },
+    );
+
+    let catalog = catalog.with_workflows([&Workflow::from_file(all_nodes).unwrap()]);
+
+    assert_eq!(catalog.pack_of("LoadImage"), Some("core"));
+    assert_eq!(catalog.pack_of("Florence2Run"), Some("florence2"));
+}
+
+#[test]
+fn test_scope_names_executable_workflows_by_catalog() {
+    let temp_dir = TempDir::new().unwrap();
+    let workflows_dir = temp_dir.path().join("workflows");
+    fs::create_dir_all(workflows_dir.join("executable")).unwrap();
+    fs::write(
+        workflows_dir.join("all-nodes-ComfyUI-KJNodes.json"),
+        json!({"nodes": [
+            {"id": 1, "type": "ImageResizeKJ"},
+            {"id": 2, "type": "GetImageSizeAndCount"}
+        ]})
+        .to_string(),
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("executable/ComfyUI-KJNodes (1).json"),
+        json!({"nodes": [
+            {"id": 1, "type": "LoadImage"},
+            {"id": 2, "type": "ImageResizeKJ"},
+            {"id": 3, "type": "GetImageSizeAndCount"}
+        ]})
+        .to_string(),
+    )
+    .unwrap();
+
+    let scope = WorkflowScope::shared(temp_dir.path());
+    let catalog = Catalog::default().with_workflows(scope.load_all().unwrap().values());
+    let executable = scope.load_executable(&catalog).unwrap();
+
+    assert_eq!(executable.len(), 1);
+    assert_eq!(executable[0].pack_name, "ComfyUI-KJNodes");
+}
@@ -0,0 +1,225 @@
+use comfy_qa::commands::diff::calculate_node_diff;
+use comfy_qa::models::{Catalog, DetailedChecklist, Export, ObjectInfo, Workflow};
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn export(nodes: serde_json::Value) -> Export {
+    Export::from_json(
+        &json!({
+            "version": "1.1",
+            "exported_at": "2024-01-01T00:00:00Z",
+            "project_name": "test",
+            "environment": {"url": "http://localhost:8188", "user_agent": null},
+            "packs": [{"name": "pack1", "node_count": 2, "nodes": nodes}]
+        })
+        .to_string(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_catalog_from_export_round_trips() {
+    let temp_dir = TempDir::new().unwrap();
+    let catalog = Catalog::from_export(&export(json!([
+        {"name": "NodeA", "display_name": "Node A", "deprecated": false, "category": "image"},
+        {"name": "NodeB", "display_name": null, "deprecated": true, "category": null}
+    ])));
+
+    assert_eq!(catalog.display_name("NodeA"), "Node A");
+    assert_eq!(catalog.display_name("NodeB"), "NodeB");
+    assert_eq!(catalog.display_name("Unknown"), "Unknown");
+    assert_eq!(catalog.pack_of("NodeA"), Some("pack1"));
+    assert!(catalog.get("NodeB").unwrap().deprecated);
+    assert_eq!(
+        catalog.find("node a").map(|(class, _)| class),
+        Some("NodeA")
+    );
+    assert_eq!(catalog.pack_nodes("pack1").count(), 2);
+
+    catalog.to_file(Catalog::path(temp_dir.path())).unwrap();
+    let loaded = Catalog::for_project(temp_dir.path()).unwrap();
+    assert_eq!(loaded.nodes, catalog.nodes);
+    assert_eq!(loaded.schema_version, 1);
+
+    let mut missing = Catalog::for_project(&temp_dir.path().join("other")).unwrap();
+    assert!(missing.is_empty());
+    missing.update(catalog.clone());
+    missing.to_file(Catalog::path(temp_dir.path())).unwrap();
+    assert!(Catalog::for_project(temp_dir.path()).is_ok());
+
+    fs::write(
+        Catalog::path(temp_dir.path()),
+        json!({"schema_version": 1, "nodes": {"NodeA": {"display_name": "Node A"}}}).to_string(),
+    )
+    .unwrap();
+    let err = format!("{:#}", Catalog::for_project(temp_dir.path()).unwrap_err());
+    assert!(err.contains("nodes.NodeA"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_catalog_update_keeps_removed_nodes() {
+    let mut catalog = Catalog::from_export(&export(json!([
+        {"name": "NodeA", "display_name": "Node A", "deprecated": false, "category": null},
+        {"name": "NodeB", "display_name": "Node B", "deprecated": false, "category": null}
+    ])));
+    catalog.update(Catalog::from_export(&export(json!([
+        {"name": "NodeA", "display_name": "Node A v2", "deprecated": true, "category": null}
+    ]))));
+
+    let node_a = catalog.get("NodeA").unwrap();
+    assert_eq!(node_a.display_name.as_deref(), Some("Node A v2"));
+    assert!(node_a.deprecated && !node_a.removed);
+    assert!(catalog.get("NodeB").unwrap().removed);
+    assert_eq!(catalog.display_name("NodeB"), "Node B");
+}
+
+#[test]
+fn test_object_info_node_definitions() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("object_info.json");
+    fs::write(
+        &path,
+        json!({
+            "KSampler": {
+                "name": "KSampler",
+                "display_name": "KSampler",
+                "category": "sampling",
+                "python_module": "nodes",
+                "input": {
+                    "required": {
+                        "model": ["MODEL"],
+                        "seed": ["INT", {"default": 0, "min": 0, "max": 18446744073709551615u64}],
+                        "sampler_name": [["euler", "dpmpp_2m"], {}],
+                        "scheduler": ["COMBO", {"options": ["normal", "karras"], "default": "normal"}]
+                    },
+                    "optional": {"note": ["STRING", {"multiline": true}]}
+                },
+                "input_order": {"required": ["model", "seed", "sampler_name", "scheduler"], "optional": ["note"]},
+                "output": ["LATENT"],
+                "output_is_list": [false],
+                "output_name": ["LATENT"],
+                "output_node": false
+            },
+            "FluxProGenerate": {
+                "display_name": "Flux Pro",
+                "python_module": "comfy_api_nodes.nodes_bfl",
+                "input": {"required": {"prompt": ["STRING", {}]}},
+                "output": ["IMAGE"],
+                "api_node": true
+            },
+            "OldNode": {
+                "python_module": "custom_nodes.my-pack",
+                "deprecated": true,
+                "output": [["a", "b"]],
+                "output_name": ["choice"]
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let object_info = ObjectInfo::from_file(&path).unwrap();
+    let sampler = object_info.get("KSampler").unwrap();
+    let inputs = sampler.inputs();
+    let names: Vec<_> = inputs.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["model", "seed", "sampler_name", "scheduler", "note"]
+    );
+    assert!(!inputs[0].is_widget());
+    assert_eq!(inputs[1].default, Some(json!(0)));
+    assert_eq!(inputs[1].min, Some(0.0));
+    assert_eq!(inputs[2].input_type, "COMBO");
+    assert_eq!(inputs[2].options, vec![json!("euler"), json!("dpmpp_2m")]);
+    assert_eq!(inputs[3].options.len(), 2);
+    assert!(!inputs[4].required && inputs[4].multiline);
+    assert_eq!(sampler.outputs()[0].output_type, "LATENT");
+
+    let old = object_info.get("OldNode").unwrap();
+    assert_eq!(old.outputs()[0].output_type, "COMBO");
+    assert_eq!(old.outputs()[0].name, "choice");
+
+    let packs: Vec<_> = object_info.by_pack().into_keys().collect();
+    assert_eq!(packs, vec!["comfy_api_nodes", "core", "my-pack"]);
+
+    let catalog = Catalog::from_object_info(&object_info);
+    assert_eq!(catalog.pack_of("KSampler"), Some("core"));
+    assert_eq!(catalog.display_name("FluxProGenerate"), "Flux Pro");
+    assert!(catalog.get("OldNode").unwrap().deprecated);
+
+    fs::write(&path, json!({"KSampler": {"output": "LATENT"}}).to_string()).unwrap();
+    let err = ObjectInfo::from_file(&path).unwrap_err().to_string();
+    assert!(err.contains("KSampler.output"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_catalog_matches_checklist_items_to_classes() {
+    let catalog = Catalog::from_export(&export(json!([
+        {"name": "NodeA", "display_name": "Node A", "deprecated": false, "category": null},
+        {"name": "NodeB", "display_name": "Node B", "deprecated": true, "category": null}
+    ])));
+
+    assert_eq!(catalog.checklist_name("NodeA"), "Node A");
+    assert_eq!(catalog.checklist_name("NodeB"), "Node B ~~DEPRECATED~~");
+    assert_eq!(catalog.checklist_name("Unknown"), "Unknown");
+
+    assert!(catalog.lists("Node A", "NodeA"));
+    assert!(catalog.lists("NodeA", "NodeA"));
+    assert!(catalog.lists("Node B ~~DEPRECATED~~", "NodeB"));
+    assert!(catalog.lists("Node A ~~REMOVED~~", "NodeA"));
+    assert!(catalog.lists("Unknown", "Unknown"));
+    assert!(!catalog.lists("Node A", "NodeB"));
+
+    let workflow = Workflow::from_value(
+        "all-nodes-pack1.json",
+        json!({"nodes": [{"id": 1, "type": "NodeA"}, {"id": 2, "type": "NodeC"}]}),
+    )
+    .unwrap();
+    let detailed =
+        DetailedChecklist::parse("## pack1\n\n- [x] Node A\n- [ ] Node B ~~DEPRECATED~~\n");
+    let diff = calculate_node_diff("pack1", &workflow, &detailed, &catalog);
+    assert_eq!(diff.missing_from_checklist, vec!["NodeC".to_string()]);
+    assert_eq!(
+        diff.extra_in_checklist,
+        vec!["Node B ~~DEPRECATED~~".to_string()]
+    );
+}
+
+#[test]
+fn test_object_info_inputs_outside_a_partial_input_order() {
+    let object_info: ObjectInfo = serde_json::from_value(json!({
+        "Resize": {
+            "input": {
+                "required": {
+                    "image": ["IMAGE"],
+                    "width": ["INT", {"default": 512}],
+                    "height": ["INT", {"default": 512}]
+                },
+                "optional": {"mask": ["MASK"], "crop": ["BOOLEAN"]}
+            },
+            "input_order": {"required": ["height", "image"]},
+            "output": ["IMAGE"]
+        }
+    }))
+    .unwrap();
+
+    let resize = object_info.get("Resize").unwrap();
+    let inputs: Vec<_> = resize
+        .inputs()
+        .into_iter()
+        .map(|i| (i.name, i.required))
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            ("height".to_string(), true),
+            ("image".to_string(), true),
+            ("width".to_string(), true),
+            ("mask".to_string(), false),
+            ("crop".to_string(), false),
+        ]
+    );
+    let slots: Vec<_> = resize.input_slots().into_iter().map(|s| s.name).collect();
+    assert_eq!(slots, ["height", "image", "width", "mask", "crop"]);
+}
@@ -1,4 +1,8 @@
-use comfy_qa::models::Checklist;
+use comfy_qa::models::{
+    Checklist, ChecklistNode, DetailedChecklist, NodePack, TestDimension, TestDimensions,
+    TestOutcome,
+};
+use comfy_qa::parsers::MarkdownParser;
 use std::fs;
 use tempfile::TempDir;
 
@@ -22,10 +26,10 @@ fn test_checklist_parsing() {
     assert_eq!(checklist.packs.len(), 2);
     assert_eq!(checklist.packs[0].name, "Pack One");
     assert_eq!(checklist.packs[0].node_count, 20);
-    assert!(checklist.packs[0].tested);
+    assert!(checklist.packs[0].tested());
     assert_eq!(checklist.packs[1].name, "Pack Two");
     assert_eq!(checklist.packs[1].node_count, 15);
-    assert!(!checklist.packs[1].tested);
+    assert!(!checklist.packs[1].tested());
 }
 
 #[test]
@@ -45,8 +49,8 @@ fn test_checklist_tested_status() {
     let checklist = Checklist::from_file(&checklist_path).unwrap();
 
     assert_eq!(checklist.packs.len(), 2);
-    assert!(checklist.packs[0].tested);
-    assert!(!checklist.packs[1].tested);
+    assert!(checklist.packs[0].tested());
+    assert!(!checklist.packs[1].tested());
 }
 
 #[test]
@@ -87,3 +91,240 @@ fn test_checklist_with_special_characters() {
     assert_eq!(checklist.packs[1].name, "Pack_With_Underscores");
     assert_eq!(checklist.packs[1].node_count, 10);
 }
+
+#[test]
+fn test_checklist_write_preserves_content() {
+    let checklist_content = r#"# Node Pack Testing Checklist
+
+Notes from round 1 - see findings/.
+
+## Video
+
+- [ ] Pack One (20) <!-- API tested -->
+  - crashes on resize, reported upstream
+- [x] Pack Two (15)
+
+<!-- end of list -->
+"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("test-project");
+    fs::create_dir(&project_dir).unwrap();
+    let checklist_path = project_dir.join("checklist.md");
+    fs::write(&checklist_path, checklist_content).unwrap();
+
+    let mut checklist = Checklist::from_file(&checklist_path).unwrap();
+    assert_eq!(checklist.to_markdown(), checklist_content);
+
+    checklist.packs[0].set_outcome(TestOutcome::Pass);
+    checklist.packs[1].node_count = 16;
+    checklist.write().unwrap();
+
+    let written = fs::read_to_string(&checklist_path).unwrap();
+    assert_eq!(
+        written,
+        checklist_content
+            .replace("- [ ] Pack One (20)", "- [x] Pack One (20)")
+            .replace("Pack Two (15)", "Pack Two (16)")
+    );
+}
+
+#[test]
+fn test_checklist_write_adds_and_removes_packs() {
+    let checklist_content =
+        "# Node Pack Testing Checklist\r\n\r\n- [x] Old (1)\r\n- [ ] Kept (2)\r\n\r\nFooter\r\n";
+
+    let temp_dir = TempDir::new().unwrap();
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(&checklist_path, checklist_content).unwrap();
+
+    let mut checklist = Checklist::from_file(&checklist_path).unwrap();
+    checklist.packs.remove(0);
+    checklist
+        .packs
+        .push(NodePack::new("New".to_string(), 3, false));
+
+    assert_eq!(
+        checklist.to_markdown(),
+        "# Node Pack Testing Checklist\r\n\r\n- [ ] Kept (2)\r\n- [ ] New (3)\r\n\r\nFooter\r\n"
+    );
+}
+
+#[test]
+fn test_detailed_checklist_node_states() {
+    let detailed_content = r#"# Node Pack Testing Checklist
+
+## Pack One
+
+- [x] NodeA
+- [ ] NodeB
+- [x] NodeC
+
+## Pack Two
+
+- [ ] NodeD
+"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(&path, detailed_content).unwrap();
+
+    let detailed = DetailedChecklist::from_file(&path).unwrap();
+
+    assert_eq!(
+        detailed.get_nodes("Pack One").unwrap()[1],
+        ChecklistNode {
+            name: "NodeB".to_string(),
+            outcome: TestOutcome::Untested,
+            dimensions: TestDimensions::default(),
+        }
+    );
+    assert_eq!(
+        detailed.node_names("Pack One"),
+        vec!["NodeA", "NodeB", "NodeC"]
+    );
+    assert_eq!(detailed.pack_progress("Pack One"), (2, 3));
+    assert_eq!(detailed.pack_progress("Pack Two"), (0, 1));
+    assert_eq!(detailed.pack_progress("Missing"), (0, 0));
+    assert_eq!(detailed.progress(), (2, 4));
+}
+
+#[test]
+fn test_checklist_outcome_markers() {
+    let checklist_content = r#"# Node Pack Testing Checklist
+
+- [x] Passing (3)
+- [!] Failing (4)
+- [~] Blocked (5)
+- [-] Skipped (1)
+- [/] NotApplicable (2)
+- [ ] Untested (6)
+"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(&checklist_path, checklist_content).unwrap();
+
+    let mut checklist = Checklist::from_file(&checklist_path).unwrap();
+    let outcomes: Vec<_> = checklist.packs.iter().map(|p| p.outcome).collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            TestOutcome::Pass,
+            TestOutcome::Fail,
+            TestOutcome::Blocked,
+            TestOutcome::Skipped,
+            TestOutcome::NotApplicable,
+            TestOutcome::Untested,
+        ]
+    );
+    assert!(checklist.packs[1].tested());
+    assert!(!checklist.packs[5].tested());
+    assert_eq!(
+        TestOutcome::summarize(&checklist.outcome_counts()),
+        "1 pass, 1 fail, 1 blocked, 1 skipped, 1 n/a"
+    );
+
+    checklist.packs[1].set_outcome(TestOutcome::Pass);
+    checklist.packs[5].set_outcome(TestOutcome::Blocked);
+    assert_eq!(
+        checklist.to_markdown(),
+        checklist_content
+            .replace("[!] Failing", "[x] Failing")
+            .replace("[ ] Untested", "[~] Untested")
+    );
+
+    let items = MarkdownParser::extract_outcome_items(checklist_content);
+    assert_eq!(items[1].0, TestOutcome::Fail);
+    assert!(MarkdownParser::extract_checkbox_items(checklist_content)[2].0);
+}
+
+#[test]
+fn test_checklist_ui_and_api_dimensions() {
+    let checklist_content = r#"# Node Pack Testing Checklist
+
+- [x] Manual (3)
+- [x] Scripted (4) <!-- API tested -->
+- [x] Everywhere (5) <!-- Both -->
+- [x] Mixed (2) <!-- UI tested, API tested: fail -->
+- [ ] Fresh (6) <!-- see notes -->
+"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(&checklist_path, checklist_content).unwrap();
+
+    let mut checklist = Checklist::from_file(&checklist_path).unwrap();
+    let results: Vec<_> = checklist
+        .packs
+        .iter()
+        .map(|p| {
+            (
+                p.dimension_outcome(TestDimension::Ui),
+                p.dimension_outcome(TestDimension::Api),
+            )
+        })
+        .collect();
+    assert_eq!(
+        results,
+        vec![
+            (TestOutcome::Pass, TestOutcome::Untested),
+            (TestOutcome::Untested, TestOutcome::Pass),
+            (TestOutcome::Pass, TestOutcome::Pass),
+            (TestOutcome::Pass, TestOutcome::Fail),
+            (TestOutcome::Untested, TestOutcome::Untested),
+        ]
+    );
+    assert_eq!(checklist.dimension_progress(TestDimension::Ui), (3, 5));
+    assert_eq!(checklist.dimension_progress(TestDimension::Api), (3, 5));
+    assert_eq!(checklist.to_markdown(), checklist_content);
+
+    checklist.packs[0].set_dimension_outcome(TestDimension::Api, TestOutcome::Pass);
+    checklist.packs[3].set_dimension_outcome(TestDimension::Api, TestOutcome::Pass);
+    checklist.packs[4].set_dimension_outcome(TestDimension::Api, TestOutcome::Blocked);
+    assert_eq!(
+        checklist.to_markdown(),
+        checklist_content
+            .replace("Manual (3)", "Manual (3) <!-- Both -->")
+            .replace("<!-- UI tested, API tested: fail -->", "<!-- Both -->")
+            .replace(
+                "[ ] Fresh (6) <!-- see notes -->",
+                "[~] Fresh (6) <!-- see notes --> <!-- API tested -->"
+            )
+    );
+
+    let items = MarkdownParser::extract_outcome_items(checklist_content);
+    assert_eq!(items[1].1, "Scripted");
+    assert_eq!(items[1].2, Some(4));
+}
+
+#[test]
+fn test_detailed_checklist_dimensions() {
+    let detailed_content = r#"# Node Pack Testing Checklist
+
+## Pack One
+
+- [x] NodeA
+- [x] NodeB <!-- API tested -->
+- [x] NodeC <!-- Both -->
+- [ ] NodeD
+"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(&path, detailed_content).unwrap();
+
+    let detailed = DetailedChecklist::from_file(&path).unwrap();
+
+    assert_eq!(
+        detailed.node_names("Pack One"),
+        vec!["NodeA", "NodeB", "NodeC", "NodeD"]
+    );
+    assert_eq!(detailed.dimension_progress(TestDimension::Ui), (2, 4));
+    assert_eq!(detailed.dimension_progress(TestDimension::Api), (2, 4));
+    assert_eq!(
+        TestDimensions::parse_marker(" api: n/a ").map(|d| d.to_marker()),
+        Some(Some("API tested: n/a".to_string()))
+    );
+    assert_eq!(TestDimensions::parse_marker("reviewed by sam"), None);
+}
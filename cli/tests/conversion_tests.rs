@@ -0,0 +1,122 @@
+use comfy_qa::generators::ApiPromptGenerator;
+use comfy_qa::models::{ApiInput, ObjectInfo, WorkflowGraph};
+use serde_json::json;
+
+fn object_info() -> ObjectInfo {
+    serde_json::from_value(json!({
+        "CheckpointLoaderSimple": {
+            "input": {"required": {"ckpt_name": [["model.safetensors"], {}]}},
+            "output": ["MODEL", "CLIP", "VAE"]
+        },
+        "KSampler": {
+            "input": {"required": {
+                "model": ["MODEL"],
+                "seed": ["INT", {"default": 0}],
+                "steps": ["INT", {"default": 20}],
+                "cfg": ["FLOAT", {"default": 8.0}],
+                "positive": ["CONDITIONING"]
+            }},
+            "output": ["LATENT"]
+        },
+        "ModelPassthrough": {
+            "input": {"required": {"model": ["MODEL"], "strength": ["FLOAT", {}]}},
+            "output": ["MODEL"]
+        }
+    }))
+    .unwrap()
+}
+
+fn graph() -> WorkflowGraph {
+    WorkflowGraph::from_value(json!({
+        "nodes": [
+            {"id": 1, "type": "CheckpointLoaderSimple", "mode": 0,
+             "outputs": [{"name": "MODEL", "type": "MODEL", "links": [1]}],
+             "widgets_values": ["model.safetensors"]},
+            {"id": 2, "type": "ModelPassthrough", "mode": 4,
+             "inputs": [{"name": "model", "type": "MODEL", "link": 1}],
+             "outputs": [{"name": "MODEL", "type": "MODEL", "links": [2]}],
+             "widgets_values": [1.0]},
+            {"id": 3, "type": "Reroute", "mode": 0,
+             "inputs": [{"name": "", "type": "*", "link": 2}],
+             "outputs": [{"name": "", "type": "MODEL", "links": [3]}]},
+            {"id": 4, "type": "PrimitiveNode", "mode": 0,
+             "outputs": [{"name": "INT", "type": "INT", "links": [4]}],
+             "widgets_values": [30, "fixed"]},
+            {"id": 5, "type": "KSampler", "title": "Sampler", "mode": 0,
+             "inputs": [
+                {"name": "model", "type": "MODEL", "link": 3},
+                {"name": "positive", "type": "CONDITIONING", "link": null},
+                {"name": "steps", "type": "INT", "link": 4, "widget": {"name": "steps"}}
+             ],
+             "widgets_values": [42, "randomize", 20, 7.5]},
+            {"id": 6, "type": "KSampler", "mode": 2, "widgets_values": [1, "fixed", 1, 1.0]}
+        ],
+        "links": [
+            [1, 1, 0, 2, 0, "MODEL"],
+            [2, 2, 0, 3, 0, "MODEL"],
+            [3, 3, 0, 5, 0, "MODEL"],
+            [4, 4, 0, 5, 2, "INT"]
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_conversion_resolves_reroutes_bypass_and_primitives() {
+    let info = object_info();
+    let conversion = ApiPromptGenerator::new(&info).convert(&graph());
+    let prompt = &conversion.prompt;
+
+    // Bypassed, muted and virtual nodes are dropped
+    assert_eq!(prompt.sorted_ids(), vec!["1", "5"]);
+
+    let sampler = &prompt.nodes["5"];
+    assert_eq!(sampler.meta.as_ref().unwrap().title, "Sampler");
+    assert_eq!(sampler.inputs["model"], ApiInput::Link("1".to_string(), 0));
+    assert_eq!(sampler.inputs["seed"], ApiInput::Value(json!(42)));
+    // The control_after_generate value is skipped, so cfg lines up
+    assert_eq!(sampler.inputs["cfg"], ApiInput::Value(json!(7.5)));
+    // The primitive node's value replaces the widget value
+    assert_eq!(sampler.inputs["steps"], ApiInput::Value(json!(30)));
+}
+
+#[test]
+fn test_conversion_warns_on_missing_inputs_and_unknown_nodes() {
+    let info = object_info();
+    let mut graph = graph();
+    graph.nodes.push(
+        serde_json::from_value(json!({"id": 7, "type": "NotInstalledNode", "mode": 0})).unwrap(),
+    );
+
+    let conversion = ApiPromptGenerator::new(&info).convert(&graph);
+
+    assert!(!conversion.prompt.nodes.contains_key("7"));
+    assert!(conversion
+        .warnings
+        .iter()
+        .any(|w| w.contains("NotInstalledNode")));
+    assert!(conversion.warnings.iter().any(|w| w.contains("'positive'")));
+}
+
+#[test]
+fn test_conversion_drops_links_from_skipped_nodes() {
+    let info = object_info();
+    let graph = WorkflowGraph::from_value(json!({
+        "nodes": [
+            {"id": 1, "type": "NotInstalledLoader", "mode": 0,
+             "outputs": [{"name": "MODEL", "type": "MODEL", "links": [1]}]},
+            {"id": 2, "type": "ModelPassthrough", "mode": 0,
+             "inputs": [{"name": "model", "type": "MODEL", "link": 1}],
+             "widgets_values": [1.0]}
+        ],
+        "links": [[1, 1, 0, 2, 0, "MODEL"]]
+    }))
+    .unwrap();
+
+    let conversion = ApiPromptGenerator::new(&info).convert(&graph);
+
+    assert!(!conversion.prompt.nodes["2"].inputs.contains_key("model"));
+    assert!(conversion.warnings.iter().any(
+        |w| w.starts_with("node 2 (ModelPassthrough): input 'model' comes from skipped node 1")
+    ));
+}
@@ -0,0 +1,376 @@
+use comfy_qa::generators::FindingsReport;
+use comfy_qa::models::{Catalog, Checklist, DetailedChecklist, Finding, FindingsFile};
+use comfy_qa::validators::{FindingValidator, Severity};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_findings_parsing() {
+    let contents = r#"# Findings
+
+## hanzo-studio-impact-pack
+
+- [x] Switch (Any): widget value for 'set_mode' is hidden in Vue node
+
+  Terry - should fix in https://github.com/hanzoui/frontend/pull/7894
+
+  ## hanzo-studio-kjnodes
+
+- [x] Spline Editor: unable to interact with the node UI
+
+  Terry - there are several issues for this node:
+  - resize - https://github.com/hanzoui/frontend/pull/7934
+  - protovis requires https://github.com/hanzoui/frontend/pull/7934 and https://github.com/kijai/HanzoStudio-KJNodes/pull/497
+
+# cross-nodes (vue)
+
+- [ ] collapsed nodes extend in size when switching to LiteGraph
+- [x] Undo causing permanent loading state for preview
+  - works as usual when reswitching from Litegraph
+  - Terry - fixed in https://github.com/hanzoui/frontend/pull/8808
+"#;
+
+    let findings = Finding::parse(contents);
+    assert_eq!(findings.len(), 4);
+
+    assert_eq!(
+        findings[0],
+        Finding {
+            pack: Some("hanzo-studio-impact-pack".to_string()),
+            node: Some("Switch (Any)".to_string()),
+            description: "widget value for 'set_mode' is hidden in Vue node".to_string(),
+            resolved: true,
+            responder: Some("Terry".to_string()),
+            response: Some(
+                "should fix in https://github.com/hanzoui/frontend/pull/7894".to_string()
+            ),
+            pr_urls: vec!["https://github.com/hanzoui/frontend/pull/7894".to_string()],
+            notes: vec![
+                "Terry - should fix in https://github.com/hanzoui/frontend/pull/7894".to_string()
+            ],
+            line: 5,
+        }
+    );
+
+    // Indented headings still start a section; links are deduplicated
+    assert_eq!(findings[1].pack.as_deref(), Some("hanzo-studio-kjnodes"));
+    assert_eq!(
+        findings[1].pr_urls,
+        vec![
+            "https://github.com/hanzoui/frontend/pull/7934",
+            "https://github.com/kijai/HanzoStudio-KJNodes/pull/497",
+        ]
+    );
+
+    // Later top-level headings are sections too
+    assert_eq!(findings[2].pack.as_deref(), Some("cross-nodes (vue)"));
+    assert_eq!(findings[2].node, None);
+    assert!(findings[2].is_open());
+    assert_eq!(findings[2].responder, None);
+
+    // Responder inside a sub-bullet
+    assert_eq!(findings[3].responder.as_deref(), Some("Terry"));
+    assert_eq!(findings[3].notes.len(), 2);
+    assert_eq!(
+        findings[3].title(),
+        "Undo causing permanent loading state for preview"
+    );
+}
+
+#[test]
+fn test_findings_files_ordered_by_round() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("findings - round10.md"),
+        "# Findings\n\n## pack\n\n- [ ] A: later\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("findings - round2.md"),
+        "# Findings\n\n## pack\n\n- [x] B: earlier\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "not findings").unwrap();
+
+    let files = FindingsFile::load_dir(temp_dir.path()).unwrap();
+    let rounds: Vec<_> = files.iter().map(|f| f.round).collect();
+    assert_eq!(rounds, vec![Some(2), Some(10)]);
+    assert_eq!(files[0].findings[0].node.as_deref(), Some("B"));
+
+    assert!(FindingsFile::load_dir(temp_dir.path().join("missing"))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_findings_add_resolve_and_render() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut file = FindingsFile::new_round(temp_dir.path(), 3);
+
+    file.add(Finding::new(
+        Some("pack-a".to_string()),
+        Some("NodeA".to_string()),
+        "widget hidden".to_string(),
+    ));
+    file.add(Finding::new(
+        Some("pack-b".to_string()),
+        None,
+        "slow to load".to_string(),
+    ));
+    let index = file.add(Finding::new(
+        Some("pack-a".to_string()),
+        Some("NodeB".to_string()),
+        "canvas overflows".to_string(),
+    ));
+    // Grouped with the rest of its pack
+    assert_eq!(index, 1);
+    assert_eq!(file.id(index), "r3-2");
+
+    file.resolve(
+        0,
+        "Terry",
+        "fixed in",
+        Some("https://github.com/hanzoui/frontend/pull/1"),
+    );
+    assert_eq!(file.findings[0].responder.as_deref(), Some("Terry"));
+    assert_eq!(
+        file.findings[0].pr_urls,
+        vec!["https://github.com/hanzoui/frontend/pull/1"]
+    );
+
+    assert_eq!(
+        file.to_markdown(),
+        r#"# Findings - Round 3
+
+## pack-a
+
+- [x] NodeA: widget hidden
+
+  Terry - fixed in https://github.com/hanzoui/frontend/pull/1
+
+- [ ] NodeB: canvas overflows
+
+## pack-b
+
+- [ ] slow to load
+"#
+    );
+
+    file.write().unwrap();
+    let path = temp_dir.path().join("findings - round3.md");
+    assert_eq!(fs::read_to_string(&path).unwrap(), file.to_markdown());
+    let reloaded = FindingsFile::from_file(&path).unwrap();
+    assert_eq!(reloaded.round, Some(3));
+    assert_eq!(reloaded.title.as_deref(), Some("Findings - Round 3"));
+    assert_eq!(reloaded.to_markdown(), file.to_markdown());
+    assert_eq!(reloaded.findings[0].notes, file.findings[0].notes);
+}
+
+#[test]
+fn test_findings_add_and_resolve_keep_the_file_layout() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("findings - round1.md");
+    fs::write(
+        &path,
+        r#"# Findings
+
+Tested on the staging server.
+
+  ## pack-a
+
+- [ ] NodeA: widget hidden
+    - seen in Vue mode only
+
+# cross-nodes (vue)
+
+- [ ] collapsed nodes grow
+"#,
+    )
+    .unwrap();
+
+    let mut file = FindingsFile::from_file(&path).unwrap();
+    let index = file.add(Finding::new(
+        Some("pack-a".to_string()),
+        Some("NodeB".to_string()),
+        "canvas overflows".to_string(),
+    ));
+    assert_eq!(index, 1);
+    file.resolve(0, "Terry", "fixed", None);
+    file.add(Finding::new(
+        Some("pack-c".to_string()),
+        None,
+        "slow".to_string(),
+    ));
+    file.write().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        r#"# Findings
+
+Tested on the staging server.
+
+  ## pack-a
+
+- [x] NodeA: widget hidden
+    - seen in Vue mode only
+    Terry - fixed
+
+- [ ] NodeB: canvas overflows
+
+# cross-nodes (vue)
+
+- [ ] collapsed nodes grow
+
+## pack-c
+
+- [ ] slow
+"#
+    );
+    let reloaded = FindingsFile::from_file(&path).unwrap();
+    assert_eq!(reloaded.findings[2].line, 15);
+    assert_eq!(reloaded.findings[0].responder.as_deref(), Some("Terry"));
+}
+
+#[test]
+fn test_finding_validation_against_checklist() {
+    let temp_dir = TempDir::new().unwrap();
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(
+        &checklist_path,
+        "# Node Pack Testing Checklist\n\n- [x] Pack-A (2)\n- [ ] pack-b (1)\n",
+    )
+    .unwrap();
+    let detailed_path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(
+        &detailed_path,
+        "# Node Pack Testing Checklist\n\n## Pack-A\n\n- [x] NodeA\n- [ ] NodeB\n- [!] Old Node ~~DEPRECATED~~\n- [x] Gone ~~REMOVED~~\n",
+    )
+    .unwrap();
+    let checklist = Checklist::from_file(&checklist_path).unwrap();
+    let detailed = DetailedChecklist::from_file(&detailed_path).unwrap();
+
+    let finding = |pack: &str, node: Option<&str>| {
+        Finding::new(
+            Some(pack.to_string()),
+            node.map(str::to_string),
+            "broken".to_string(),
+        )
+    };
+    let catalog: Catalog = serde_json::from_value(serde_json::json!({
+        "schema_version": 1,
+        "nodes": {
+            "OldNodeV1": {"pack": "Pack-A", "display_name": "Old Node", "deprecated": true}
+        }
+    }))
+    .unwrap();
+    let severities = |finding: &Finding| -> Vec<Severity> {
+        FindingValidator::validate(finding, &checklist, Some(&detailed), &catalog)
+            .iter()
+            .map(|r| r.severity)
+            .collect()
+    };
+
+    assert!(severities(&finding("pack-a", Some("nodea"))).is_empty());
+    // Suffixed items go by their plain name, and known classes by class name
+    assert!(severities(&finding("pack-a", Some("Old Node"))).is_empty());
+    assert!(severities(&finding("pack-a", Some("Gone"))).is_empty());
+    assert!(severities(&finding("pack-a", Some("OldNodeV1"))).is_empty());
+    assert_eq!(
+        FindingValidator::find_pack(&checklist, "pack-a"),
+        Some("Pack-A")
+    );
+    assert_eq!(
+        severities(&finding("pack-a", Some("NodeZ"))),
+        vec![Severity::Error]
+    );
+    assert_eq!(severities(&finding("missing", None)), vec![Severity::Error]);
+    // No detailed nodes for the pack, so the node can't be checked
+    assert_eq!(
+        severities(&finding("pack-b", Some("NodeC"))),
+        vec![Severity::Warning]
+    );
+}
+
+#[test]
+fn test_findings_report_groups_by_fix() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("findings - round1.md"),
+        r#"# Findings
+
+## vhs
+
+- [x] Load Video: widgets show LiteGraph
+
+  Terry - fixed in https://github.com/hanzoui/frontend/pull/7925 and https://github.com/Kosinkadink/HanzoStudio-VideoHelperSuite/pull/624
+
+- [x] Split Masks: widgets show LiteGraph
+
+  Terry - fixed in https://github.com/hanzoui/frontend/pull/7925
+
+- [ ] unbatch: output color doesn't update
+- [ ] Meta Batch: crash, see https://github.com/hanzoui/frontend/issues/42
+"#,
+    )
+    .unwrap();
+
+    let files = FindingsFile::load_dir(temp_dir.path()).unwrap();
+    let report = FindingsReport::new("proj", &files);
+
+    assert_eq!(report.total, 4);
+    let groups: Vec<_> = report
+        .groups
+        .iter()
+        .map(|g| (g.label(), g.findings.len(), g.resolved()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            ("hanzoui/frontend#7925".to_string(), 2, 2),
+            (
+                "Kosinkadink/HanzoStudio-VideoHelperSuite#624".to_string(),
+                1,
+                1
+            ),
+            ("hanzoui/frontend#42".to_string(), 1, 0),
+        ]
+    );
+    let unlinked: Vec<_> = report.unlinked.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(unlinked, vec!["r1-3"]);
+
+    let markdown = report.generate_markdown();
+    assert!(markdown.contains(
+        "| [hanzoui/frontend#7925](https://github.com/hanzoui/frontend/pull/7925) | 2 | 2 |"
+    ));
+    assert!(markdown
+        .contains("## No fix linked\n\n- [ ] `r1-3` [vhs] unbatch: output color doesn't update"));
+
+    let html = report.generate_html();
+    assert!(html.contains(r#"<a href="https://github.com/hanzoui/frontend/pull/7925">"#));
+    assert!(html.contains("<h2>No fix linked (1)</h2>"));
+}
+
+#[test]
+fn test_finding_links_and_pr_urls_agree() {
+    let mut finding = Finding::parse(
+        "# Findings\n\n- [ ] Node: see https://github.com/org/repo/issues/3\n  - also https://github.com/org/repo/pull/4\n",
+    )
+    .remove(0);
+    assert_eq!(
+        finding.links(),
+        vec![
+            "https://github.com/org/repo/issues/3",
+            "https://github.com/org/repo/pull/4"
+        ]
+    );
+    assert_eq!(finding.pr_urls, vec!["https://github.com/org/repo/pull/4"]);
+
+    // Only pull requests count, whatever is passed as the PR
+    finding.resolve(
+        "Terry",
+        "tracked in",
+        Some("https://github.com/org/repo/issues/5"),
+    );
+    assert_eq!(finding.pr_urls, vec!["https://github.com/org/repo/pull/4"]);
+    assert_eq!(finding.links().len(), 3);
+}
@@ -0,0 +1,97 @@
+use comfy_qa::models::Workflow;
+use comfy_qa::parsers::ImageMetadataParser;
+use serde_json::json;
+
+fn png_chunk(chunk_type: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(chunk_type);
+    chunk.extend_from_slice(data);
+    // CRC is not checked
+    chunk.extend_from_slice(&[0; 4]);
+    chunk
+}
+
+fn text_chunk(key: &str, text: &str) -> Vec<u8> {
+    let mut data = key.as_bytes().to_vec();
+    data.push(0);
+    data.extend_from_slice(text.as_bytes());
+    png_chunk(b"tEXt", &data)
+}
+
+/// Little-endian TIFF with one IFD of ASCII entries.
+fn exif(entries: &[(u16, String)]) -> Vec<u8> {
+    let ifd_size = 2 + entries.len() * 12 + 4;
+    let mut tiff = b"II".to_vec();
+    tiff.extend_from_slice(&42u16.to_le_bytes());
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    tiff.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+
+    let mut data = Vec::new();
+    let mut data_offset = 8 + ifd_size;
+    for (tag, text) in entries {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        tiff.extend_from_slice(&tag.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        tiff.extend_from_slice(&(data_offset as u32).to_le_bytes());
+        data_offset += bytes.len();
+        data.extend(bytes);
+    }
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend(data);
+    tiff
+}
+
+fn sample_workflow() -> serde_json::Value {
+    json!({
+        "nodes": [{"id": 1, "type": "LoadImage"}, {"id": 2, "type": "ImageResizeKJ"}],
+        "links": []
+    })
+}
+
+#[test]
+fn test_png_text_chunks() {
+    let prompt = json!({"1": {"class_type": "LoadImage", "inputs": {}}});
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    png.extend(png_chunk(b"IHDR", &[0; 13]));
+    png.extend(text_chunk("prompt", &prompt.to_string()));
+    png.extend(text_chunk("workflow", &sample_workflow().to_string()));
+    png.extend(png_chunk(b"IEND", &[]));
+
+    let embedded = ImageMetadataParser::parse(&png).unwrap();
+
+    assert_eq!(embedded.prompt, Some(prompt));
+    let workflow = Workflow::from_value("out.png", embedded.workflow.unwrap()).unwrap();
+    assert_eq!(workflow.node_count, 2);
+}
+
+#[test]
+fn test_webp_exif_entries() {
+    let tiff = exif(&[
+        (0x010F, format!("workflow:{}", sample_workflow())),
+        (0x0110, "prompt:not json".to_string()),
+    ]);
+    let mut exif_chunk = b"EXIF".to_vec();
+    exif_chunk.extend_from_slice(&(tiff.len() as u32).to_le_bytes());
+    exif_chunk.extend(&tiff);
+    if tiff.len() % 2 == 1 {
+        exif_chunk.push(0);
+    }
+
+    let mut webp = b"RIFF".to_vec();
+    webp.extend_from_slice(&((exif_chunk.len() + 4) as u32).to_le_bytes());
+    webp.extend_from_slice(b"WEBP");
+    webp.extend(exif_chunk);
+
+    let embedded = ImageMetadataParser::parse(&webp).unwrap();
+
+    assert_eq!(embedded.workflow, Some(sample_workflow()));
+    assert!(embedded.prompt.is_none());
+}
+
+#[test]
+fn test_unsupported_image() {
+    assert!(ImageMetadataParser::parse(b"GIF89a").is_err());
+}
@@ -1,4 +1,4 @@
-use comfy_qa::models::Metadata;
+use comfy_qa::models::{Metadata, TestOutcome};
 use std::fs;
 use tempfile::TempDir;
 
@@ -95,3 +95,33 @@ fn test_metadata_serialization() {
     assert!(content.contains("test-project"));
     assert!(content.contains("v1.2.3"));
 }
+
+#[test]
+fn test_metadata_pack_outcomes() {
+    let metadata_json = r#"{
+        "project_name": "test-project",
+        "created_at": "2024-01-01T00:00:00Z",
+        "last_updated": null,
+        "environment": {"type": "cloud", "url": "https://example.com"},
+        "packs": {
+            "Legacy": {"node_count": 2, "tested": true, "workflow_file": null, "notes": null, "issues": null},
+            "Broken": {"node_count": 3, "tested": true, "outcome": "fail", "workflow_file": null, "notes": null, "issues": null},
+            "Todo": {"node_count": 4, "tested": false, "workflow_file": null, "notes": null, "issues": null}
+        },
+        "stats": null
+    }"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let metadata_path = temp_dir.path().join("metadata.json");
+    fs::write(&metadata_path, metadata_json).unwrap();
+
+    let mut metadata = Metadata::from_file(&metadata_path).unwrap();
+    assert_eq!(metadata.packs["Legacy"].outcome(), TestOutcome::Pass);
+    assert_eq!(metadata.packs["Broken"].outcome(), TestOutcome::Fail);
+
+    metadata.calculate_stats();
+    let stats = metadata.stats.unwrap();
+    assert_eq!(stats.tested_packs, 2);
+    assert_eq!(stats.outcomes[&TestOutcome::Fail], 1);
+    assert_eq!(stats.outcomes[&TestOutcome::Untested], 1);
+}
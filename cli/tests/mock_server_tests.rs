@@ -0,0 +1,274 @@
+use comfy_qa::api::{ApiClient, ApiTestRunner, QueueResponse};
+use comfy_qa::generators::NodePromptGenerator;
+use comfy_qa::mock_server::MockServer;
+use comfy_qa::models::{ApiPrompt, ApiTestStatus, NodeApiResult, PackApiResult, TestOutcome};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tungstenite::Message;
+
+fn runner_results(
+    server: &MockServer,
+    validate_only: bool,
+) -> Vec<(String, ApiTestStatus, String)> {
+    let client = ApiClient::new(server.url());
+    let object_info = client.object_info().unwrap();
+    let mut runner = ApiTestRunner::new(&client, &object_info);
+    runner.poll_interval = Duration::from_millis(10);
+    runner.timeout = Duration::from_secs(10);
+    runner.validate_only = validate_only;
+
+    [
+        "KSampler",
+        "ExampleColorize",
+        "ImageInvert",
+        "ExampleTextConcat",
+    ]
+    .iter()
+    .map(|class_type| {
+        let result = runner.run_node(class_type).unwrap();
+        (
+            result.class_type,
+            result.status,
+            result.reason.unwrap_or_default(),
+        )
+    })
+    .collect()
+}
+
+fn post_prompt(server: &MockServer, body: Value) -> (u16, Value) {
+    let url = format!("{}/prompt", server.url());
+    match ureq::post(&url).send_json(body) {
+        Ok(response) => (response.status(), response.into_json().unwrap()),
+        Err(ureq::Error::Status(status, response)) => (status, response.into_json().unwrap()),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+fn error_types(response: &Value, node_id: &str) -> Vec<String> {
+    response["node_errors"][node_id]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["type"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_runner_against_mock_server() {
+    let server = MockServer::start_fixture().unwrap();
+    server.fail_node("ImageInvert", "CUDA out of memory");
+
+    let results = runner_results(&server, false);
+    assert_eq!(results[0].1, ApiTestStatus::Executed, "{:?}", results[0]);
+    assert_eq!(results[1].1, ApiTestStatus::Executed, "{:?}", results[1]);
+    assert_eq!(results[2].1, ApiTestStatus::Failed);
+    assert_eq!(results[2].2, "ImageInvert: CUDA out of memory");
+    // STRING has no output node to feed, so no prompt is sent
+    assert_eq!(results[3].1, ApiTestStatus::Blocked);
+    assert_eq!(server.requests().len(), 3);
+
+    let history = server.history();
+    assert_eq!(history.len(), 3);
+    let executed: Vec<&Value> = history
+        .values()
+        .filter(|entry| entry["status"]["status_str"] == "success")
+        .collect();
+    assert_eq!(executed.len(), 2);
+    assert_eq!(
+        executed[0]["outputs"]
+            .as_object()
+            .unwrap()
+            .values()
+            .next()
+            .unwrap()["images"][0]["type"],
+        "temp"
+    );
+
+    let validated = runner_results(&MockServer::start_fixture().unwrap(), true);
+    assert_eq!(validated[2].1, ApiTestStatus::Validated);
+}
+
+#[test]
+fn test_mock_server_validates_like_the_server() {
+    let server = MockServer::start_fixture().unwrap();
+    let object_info = MockServer::fixture_object_info();
+
+    // The generated prompt is accepted
+    let conversion = NodePromptGenerator::new(&object_info)
+        .generate("KSampler")
+        .unwrap();
+    let (status, response) = post_prompt(&server, conversion.prompt.to_request_body(None));
+    assert_eq!(status, 200);
+    assert!(response["prompt_id"].is_string());
+    assert_eq!(response["node_errors"], json!({}));
+
+    let mut prompt = conversion.prompt.to_request_body(None);
+    let inputs = &mut prompt["prompt"]["1"]["inputs"];
+    inputs.as_object_mut().unwrap().remove("model");
+    inputs["steps"] = json!(0);
+    inputs["sampler_name"] = json!("not_a_sampler");
+    inputs["positive"] = json!(["2", 0]);
+    inputs["cfg"] = json!("high");
+    let (status, response) = post_prompt(&server, prompt);
+    assert_eq!(status, 400);
+    assert_eq!(
+        response["error"]["type"],
+        "prompt_outputs_failed_validation"
+    );
+    assert_eq!(response["node_errors"]["1"]["class_type"], "KSampler");
+    assert_eq!(
+        error_types(&response, "1"),
+        [
+            "required_input_missing",
+            "value_smaller_than_min",
+            "invalid_input_type",
+            "value_not_in_list",
+            "return_type_mismatch",
+        ]
+    );
+    let preview = conversion
+        .prompt
+        .nodes
+        .iter()
+        .find(|(_, node)| node.class_type == "PreviewImage")
+        .map(|(id, _)| id.clone())
+        .unwrap();
+    assert_eq!(
+        response["node_errors"]["1"]["dependent_outputs"],
+        json!([preview])
+    );
+
+    // The client reports the same errors
+    let client = ApiClient::new(server.url());
+    let mut prompt = conversion.prompt.clone();
+    prompt.nodes.get_mut("1").unwrap().inputs.remove("model");
+    match client.queue_prompt(&prompt).unwrap() {
+        QueueResponse::Rejected { reason } => assert_eq!(
+            reason,
+            "Prompt outputs failed validation; KSampler: Required input is missing: model"
+        ),
+        other => panic!("expected a rejection, got {:?}", other),
+    }
+
+    let unknown: ApiPrompt =
+        serde_json::from_value(json!({"1": {"class_type": "NoSuchNode", "inputs": {}}})).unwrap();
+    let (status, response) = post_prompt(&server, unknown.to_request_body(None));
+    assert_eq!(status, 400);
+    assert_eq!(response["error"]["type"], "invalid_prompt");
+
+    let no_outputs: ApiPrompt = serde_json::from_value(json!({
+        "1": {"class_type": "EmptyLatentImage", "inputs": {"width": 512, "height": 512, "batch_size": 1}}
+    }))
+    .unwrap();
+    let (_, response) = post_prompt(&server, no_outputs.to_request_body(None));
+    assert_eq!(response["error"]["type"], "prompt_no_outputs");
+}
+
+#[test]
+fn test_mock_server_sends_execution_events() {
+    let server = MockServer::start_fixture().unwrap();
+    let (mut socket, _) = tungstenite::connect(server.ws_url("tester")).unwrap();
+
+    let object_info = MockServer::fixture_object_info();
+    let conversion = NodePromptGenerator::new(&object_info)
+        .generate("ImageInvert")
+        .unwrap();
+    let (status, response) =
+        post_prompt(&server, conversion.prompt.to_request_body(Some("tester")));
+    assert_eq!(status, 200);
+    let prompt_id = response["prompt_id"].as_str().unwrap();
+
+    let mut events: Vec<Value> = Vec::new();
+    loop {
+        let Message::Text(text) = socket.read().unwrap() else {
+            continue;
+        };
+        let event: Value = serde_json::from_str(&text).unwrap();
+        let done = event["type"] == "executing" && event["data"]["node"].is_null();
+        events.push(event);
+        if done {
+            break;
+        }
+    }
+
+    let kinds: Vec<&str> = events.iter().map(|e| e["type"].as_str().unwrap()).collect();
+    assert_eq!(kinds[0], "status");
+    assert_eq!(events[0]["data"]["sid"], "tester");
+    let start = kinds.iter().position(|k| *k == "execution_start").unwrap();
+    assert_eq!(
+        &kinds[start..],
+        [
+            "execution_start",
+            "execution_cached",
+            "executing",
+            "executing",
+            "executing",
+            "executed",
+            "execution_success",
+            "executing",
+        ]
+    );
+    // Inputs run before the nodes that use them
+    let order: Vec<&str> = events
+        .iter()
+        .filter(|e| e["type"] == "executing" && !e["data"]["node"].is_null())
+        .map(|e| e["data"]["node"].as_str().unwrap())
+        .collect();
+    assert_eq!(order, ["2", "1", "3"]);
+    assert!(events
+        .iter()
+        .all(|e| e["type"] == "status" || e["data"]["prompt_id"] == prompt_id));
+
+    let entry = &server.history()[prompt_id];
+    assert_eq!(entry["status"]["completed"], true);
+    assert_eq!(
+        entry["outputs"]["3"]["images"][0]["filename"],
+        "ComfyUI_temp_00001_.png"
+    );
+}
+
+#[test]
+fn test_runner_logs_workflow_runs() {
+    let server = MockServer::start_fixture().unwrap();
+    server.fail_node("ImageInvert", "CUDA out of memory");
+    let client = ApiClient::new(server.url());
+    let object_info = client.object_info().unwrap();
+    let mut runner = ApiTestRunner::new(&client, &object_info);
+    runner.poll_interval = Duration::from_millis(10);
+    runner.timeout = Duration::from_secs(10);
+    runner.monitor().unwrap();
+
+    let prompt = NodePromptGenerator::new(&object_info)
+        .generate("ImageInvert")
+        .unwrap()
+        .prompt;
+    let result = runner.run_workflow("invert", &prompt).unwrap();
+    assert_eq!(result.class_type, "invert");
+    assert_eq!(result.status, ApiTestStatus::Failed);
+
+    let run = result.execution.unwrap();
+    assert!(run.workflow);
+    assert_eq!(run.class_type, "invert");
+    assert_eq!(run.status.as_deref(), Some("error"));
+    let error = run.error.unwrap();
+    assert_eq!(error.node_type.as_deref(), Some("ImageInvert"));
+
+    let pack = PackApiResult {
+        pack: "comfy-core".to_string(),
+        nodes: vec![NodeApiResult::new(
+            "ImageInvert",
+            ApiTestStatus::Executed,
+            None,
+        )],
+        workflows: vec![NodeApiResult::new(
+            "invert",
+            ApiTestStatus::Failed,
+            Some("ImageInvert: CUDA out of memory".to_string()),
+        )],
+    };
+    assert_eq!(pack.outcome(), TestOutcome::Fail);
+    assert_eq!(
+        pack.issues(),
+        ["API test: invert: ImageInvert: CUDA out of memory"]
+    );
+}
@@ -0,0 +1,205 @@
+use comfy_qa::generators::NodePromptGenerator;
+use comfy_qa::models::{ApiInput, InputSpec, ObjectInfo};
+use serde_json::json;
+
+fn object_info() -> ObjectInfo {
+    serde_json::from_value(json!({
+        "CheckpointLoaderSimple": {
+            "input": {"required": {"ckpt_name": [["model.safetensors", "other.safetensors"], {}]}},
+            "output": ["MODEL", "CLIP", "VAE"]
+        },
+        "CLIPTextEncode": {
+            "input": {"required": {"text": ["STRING", {"multiline": true}], "clip": ["CLIP"]}},
+            "output": ["CONDITIONING"]
+        },
+        "EmptyLatentImage": {
+            "input": {"required": {
+                "width": ["INT", {"default": 512, "min": 16}],
+                "height": ["INT", {"default": 512, "min": 16}],
+                "batch_size": ["INT", {"default": 1, "min": 1}]
+            }},
+            "output": ["LATENT"]
+        },
+        "KSampler": {
+            "input": {"required": {
+                "model": ["MODEL"],
+                "seed": ["INT", {"default": 0, "min": 0, "max": 18446744073709551615u64}],
+                "steps": ["INT", {"min": 1, "max": 10000}],
+                "cfg": ["FLOAT", {"default": 8.0}],
+                "sampler_name": [["euler", "dpmpp_2m"]],
+                "scheduler": ["COMBO", {"options": ["normal", "karras"], "default": "karras"}],
+                "positive": ["CONDITIONING"],
+                "negative": ["CONDITIONING"],
+                "latent_image": ["LATENT"]
+            }, "optional": {"extra": ["MODEL"]}},
+            "output": ["LATENT"]
+        },
+        "VAEDecode": {
+            "input": {"required": {"samples": ["LATENT"], "vae": ["VAE"]}},
+            "output": ["IMAGE"]
+        },
+        "PreviewImage": {
+            "input": {"required": {"images": ["IMAGE"]}},
+            "output": [],
+            "output_node": true
+        },
+        "NeedsPipe": {
+            "input": {"required": {"pipe": ["BASIC_PIPE"], "enabled": ["BOOLEAN"]}},
+            "output": ["STRING"]
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_node_prompt_fills_widgets_and_wires_providers() {
+    let object_info = object_info();
+    let conversion = NodePromptGenerator::new(&object_info)
+        .generate("KSampler")
+        .unwrap();
+    assert!(conversion.warnings.is_empty(), "{:?}", conversion.warnings);
+
+    let prompt = &conversion.prompt;
+    let sampler = &prompt.nodes[NodePromptGenerator::TARGET_ID];
+    assert_eq!(sampler.class_type, "KSampler");
+    assert_eq!(
+        sampler.inputs["seed"],
+        ApiInput::Value(json!(0)),
+        "default is kept"
+    );
+    assert_eq!(sampler.inputs["steps"], ApiInput::Value(json!(1)));
+    assert_eq!(sampler.inputs["cfg"], ApiInput::Value(json!(8.0)));
+    assert_eq!(
+        sampler.inputs["sampler_name"],
+        ApiInput::Value(json!("euler"))
+    );
+    assert_eq!(
+        sampler.inputs["scheduler"],
+        ApiInput::Value(json!("karras"))
+    );
+    assert!(!sampler.inputs.contains_key("extra"));
+
+    let origin = |name: &str| match &sampler.inputs[name] {
+        ApiInput::Link(id, slot) => (prompt.nodes[id].class_type.as_str(), *slot),
+        other => panic!("{} is not linked: {:?}", name, other),
+    };
+    assert_eq!(origin("model"), ("CheckpointLoaderSimple", 0));
+    assert_eq!(origin("positive"), ("CLIPTextEncode", 0));
+    assert_eq!(origin("latent_image"), ("EmptyLatentImage", 0));
+
+    // One loader feeds MODEL, CLIP and VAE; LATENT goes through VAEDecode
+    let count = |class: &str| {
+        prompt
+            .nodes
+            .values()
+            .filter(|n| n.class_type == class)
+            .count()
+    };
+    assert_eq!(count("CheckpointLoaderSimple"), 1);
+    assert_eq!(count("VAEDecode"), 1);
+    assert_eq!(count("PreviewImage"), 1);
+    let loader = prompt
+        .nodes
+        .values()
+        .find(|n| n.class_type == "CheckpointLoaderSimple")
+        .unwrap();
+    assert_eq!(
+        loader.inputs["ckpt_name"],
+        ApiInput::Value(json!("model.safetensors"))
+    );
+}
+
+#[test]
+fn test_node_prompt_reports_what_it_cannot_fill() {
+    let object_info = object_info();
+    let generator = NodePromptGenerator::new(&object_info);
+    assert!(generator.generate("Missing").is_none());
+
+    let conversion = generator.generate("NeedsPipe").unwrap();
+    let node = &conversion.prompt.nodes[NodePromptGenerator::TARGET_ID];
+    assert_eq!(node.inputs["enabled"], ApiInput::Value(json!(false)));
+    assert!(!node.inputs.contains_key("pipe"));
+    assert_eq!(conversion.warnings.len(), 2, "{:?}", conversion.warnings);
+    assert!(conversion.warnings[0].contains("'pipe' (BASIC_PIPE)"));
+    assert!(conversion.warnings[1].contains("no output node"));
+
+    let clamped = InputSpec::from_spec("x", &json!(["FLOAT", {"default": 5.0, "max": 2.0}]), true);
+    assert_eq!(
+        NodePromptGenerator::widget_value(&clamped),
+        Some(json!(2.0))
+    );
+    let empty_combo = InputSpec::from_spec("ckpt", &json!([[]]), true);
+    assert_eq!(NodePromptGenerator::widget_value(&empty_combo), None);
+}
+
+#[test]
+fn test_node_prompt_sinks_models_and_conditioning_through_a_sampler() {
+    let object_info = object_info();
+    let generator = NodePromptGenerator::new(&object_info);
+
+    for class_type in ["CheckpointLoaderSimple", "CLIPTextEncode"] {
+        let conversion = generator.generate(class_type).unwrap();
+        assert!(conversion.warnings.is_empty(), "{:?}", conversion.warnings);
+
+        let prompt = &conversion.prompt;
+        let sampler = prompt
+            .nodes
+            .values()
+            .find(|n| n.class_type == "KSampler")
+            .unwrap();
+        let input = if class_type == "CLIPTextEncode" {
+            "positive"
+        } else {
+            "model"
+        };
+        assert_eq!(
+            sampler.inputs[input],
+            ApiInput::Link(NodePromptGenerator::TARGET_ID.to_string(), 0)
+        );
+        assert!(prompt
+            .nodes
+            .values()
+            .any(|n| n.class_type == "PreviewImage"));
+    }
+}
+
+#[test]
+fn test_node_prompt_drops_sink_chains_without_an_output_node() {
+    // LATENT goes through VAEDecode, but nothing here previews an IMAGE
+    let object_info: ObjectInfo = serde_json::from_value(json!({
+        "TwoOutputs": {
+            "input": {"required": {}},
+            "output": ["LATENT", "AUDIO"]
+        },
+        "VAEDecode": {
+            "input": {"required": {"samples": ["LATENT"], "vae": ["VAE"]}},
+            "output": ["IMAGE"]
+        },
+        "VAELoader": {
+            "input": {"required": {"vae_name": [["vae.safetensors"]]}},
+            "output": ["VAE"]
+        },
+        "PreviewAudio": {
+            "input": {"required": {"audio": ["AUDIO"]}},
+            "output": [],
+            "output_node": true
+        }
+    }))
+    .unwrap();
+
+    let conversion = NodePromptGenerator::new(&object_info)
+        .generate("TwoOutputs")
+        .unwrap();
+    assert!(conversion.warnings.is_empty(), "{:?}", conversion.warnings);
+    let nodes: Vec<(&str, &str)> = conversion
+        .prompt
+        .nodes
+        .iter()
+        .map(|(id, node)| (id.as_str(), node.class_type.as_str()))
+        .collect();
+    assert_eq!(nodes, [("1", "TwoOutputs"), ("2", "PreviewAudio")]);
+    assert_eq!(
+        conversion.prompt.nodes["2"].inputs["audio"],
+        ApiInput::Link(NodePromptGenerator::TARGET_ID.to_string(), 1)
+    );
+}
@@ -0,0 +1,180 @@
+use comfy_qa::models::{
+    FindingChange, Round, RoundComparison, RoundSnapshot, TestDimension, TestOutcome,
+};
+use std::fs;
+use tempfile::TempDir;
+
+fn write_project(project_dir: &std::path::Path, checklist: &str, detailed: &str) {
+    fs::create_dir_all(project_dir).unwrap();
+    fs::write(project_dir.join("checklist.md"), checklist).unwrap();
+    fs::write(project_dir.join("checklist-detailed.md"), detailed).unwrap();
+}
+
+#[test]
+fn test_round_start_and_close_snapshot_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("checklists").join("proj");
+    let findings_dir = temp_dir.path().join("findings").join("proj");
+    write_project(
+        &project_dir,
+        "# Node Pack Testing Checklist\n\n- [ ] pack1 (1)\n",
+        "# Node Pack Testing Checklist\n\n## pack1\n\n- [ ] NodeA\n",
+    );
+    fs::create_dir_all(&findings_dir).unwrap();
+    fs::write(
+        findings_dir.join("findings - round1.md"),
+        "# Findings\n\n## pack1\n\n- [ ] NodeA: broken\n",
+    )
+    .unwrap();
+
+    let mut round = Round::start(&project_dir, &findings_dir, 1).unwrap();
+    assert!(round.is_open());
+    assert!(Round::start(&project_dir, &findings_dir, 1).is_err());
+
+    // Work done during the round
+    fs::write(
+        project_dir.join("checklist.md"),
+        "# Node Pack Testing Checklist\n\n- [x] pack1 (1)\n",
+    )
+    .unwrap();
+    round.close(&project_dir, &findings_dir).unwrap();
+    assert!(round.close(&project_dir, &findings_dir).is_err());
+
+    // Later edits don't touch the frozen results
+    fs::write(
+        project_dir.join("checklist.md"),
+        "# Node Pack Testing Checklist\n\n- [!] pack1 (1)\n",
+    )
+    .unwrap();
+
+    let rounds = Round::list(&project_dir).unwrap();
+    assert_eq!(rounds.len(), 1);
+    assert!(!rounds[0].is_open());
+
+    let start = RoundSnapshot::load_dir(&rounds[0].start_dir()).unwrap();
+    let results = RoundSnapshot::load_dir(&rounds[0].results_dir()).unwrap();
+    assert_eq!(start.checklist.packs[0].outcome, TestOutcome::Untested);
+    assert_eq!(results.checklist.packs[0].outcome, TestOutcome::Pass);
+    assert_eq!(results.findings.len(), 1);
+    assert_eq!(results.findings[0].round, Some(1));
+}
+
+#[test]
+fn test_round_comparison() {
+    let temp_dir = TempDir::new().unwrap();
+    let before_dir = temp_dir.path().join("before");
+    let after_dir = temp_dir.path().join("after");
+
+    write_project(
+        &before_dir,
+        "# Node Pack Testing Checklist\n\n- [x] pack1 (2)\n- [ ] pack2 (1)\n- [x] gone (1)\n",
+        "# Node Pack Testing Checklist\n\n## pack1\n\n- [x] NodeA\n- [x] NodeB\n",
+    );
+    fs::create_dir_all(before_dir.join("findings")).unwrap();
+    fs::write(
+        before_dir.join("findings").join("findings - round1.md"),
+        r#"# Findings
+
+## pack1
+
+- [ ] NodeA: broken
+- [x] NodeB: overflow
+
+  Terry - fixed in https://github.com/hanzoui/frontend/pull/1
+"#,
+    )
+    .unwrap();
+
+    write_project(
+        &after_dir,
+        "# Node Pack Testing Checklist\n\n- [!] pack1 (2)\n- [~] pack2 (1)\n",
+        "# Node Pack Testing Checklist\n\n## pack1\n\n- [x] NodeA\n- [!] NodeB\n",
+    );
+    fs::create_dir_all(after_dir.join("findings")).unwrap();
+    fs::write(
+        after_dir.join("findings").join("findings - round1.md"),
+        "# Findings\n\n## pack1\n\n- [x] NodeA: broken\n- [ ] NodeB: overflow\n",
+    )
+    .unwrap();
+    fs::write(
+        after_dir.join("findings").join("findings - round2.md"),
+        "# Findings\n\n## pack2\n\n- [ ] NodeC: model missing\n",
+    )
+    .unwrap();
+
+    let before = RoundSnapshot::load_dir(&before_dir).unwrap();
+    let after = RoundSnapshot::load_dir(&after_dir).unwrap();
+    let comparison = RoundComparison::compare(&before, &after);
+
+    let packs: Vec<_> = comparison
+        .packs
+        .iter()
+        .map(|c| (c.pack.as_str(), c.from, c.to))
+        .collect();
+    assert_eq!(
+        packs,
+        vec![
+            ("pack1", TestOutcome::Pass, TestOutcome::Fail),
+            ("pack2", TestOutcome::Untested, TestOutcome::Blocked),
+        ]
+    );
+    assert_eq!(comparison.nodes.len(), 1);
+    assert_eq!(comparison.nodes[0].node.as_deref(), Some("NodeB"));
+
+    let regressions: Vec<_> = comparison
+        .regressions()
+        .map(|c| c.node.as_deref().unwrap_or(&c.pack))
+        .collect();
+    assert_eq!(regressions, vec!["pack1", "NodeB"]);
+
+    let titles = |change| -> Vec<_> {
+        comparison
+            .findings_with(change)
+            .map(|f| f.title().to_string())
+            .collect()
+    };
+    assert_eq!(titles(FindingChange::Fixed), vec!["NodeA"]);
+    assert_eq!(titles(FindingChange::Reopened), vec!["NodeB"]);
+    assert_eq!(titles(FindingChange::New), vec!["NodeC"]);
+}
+
+#[test]
+fn test_round_regressions_include_blocked_and_dimension_drops() {
+    let temp_dir = TempDir::new().unwrap();
+    let before_dir = temp_dir.path().join("before");
+    let after_dir = temp_dir.path().join("after");
+
+    write_project(
+        &before_dir,
+        "# Node Pack QA Checklist\n\n- [x] pack1 (1)\n- [x] pack2 (1) <!-- Both -->\n",
+        "# Node Pack QA Checklist\n\n## pack1\n\n- [x] NodeA\n",
+    );
+    write_project(
+        &after_dir,
+        "# Node Pack QA Checklist\n\n- [~] pack1 (1)\n- [x] pack2 (1) <!-- UI tested, API tested: fail -->\n",
+        "# Node Pack QA Checklist\n\n## pack1\n\n- [x] NodeA\n",
+    );
+    // A snapshot without a checklist compares as empty
+    let empty_dir = temp_dir.path().join("empty");
+    fs::create_dir_all(&empty_dir).unwrap();
+
+    let before = RoundSnapshot::load_dir(&before_dir).unwrap();
+    let after = RoundSnapshot::load_dir(&after_dir).unwrap();
+    let comparison = RoundComparison::compare(&before, &after);
+
+    let regressions: Vec<_> = comparison
+        .regressions()
+        .map(|c| (c.pack.as_str(), c.dimension, c.to))
+        .collect();
+    assert_eq!(
+        regressions,
+        vec![
+            ("pack1", None, TestOutcome::Blocked),
+            ("pack2", Some(TestDimension::Api), TestOutcome::Fail),
+        ]
+    );
+
+    let empty = RoundSnapshot::load_dir(&empty_dir).unwrap();
+    assert!(empty.checklist.packs.is_empty());
+    assert!(RoundComparison::compare(&empty, &after).is_empty());
+}
@@ -0,0 +1,161 @@
+use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+use comfy_qa::api::{ApiClient, ApiTestRunner};
+use comfy_qa::mock_server::MockServer;
+use comfy_qa::models::{ApiPrompt, ApiTestStatus, Progress, PromptRun, RunLog, ServerEvent};
+use serde_json::json;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn event(kind: &str, data: serde_json::Value) -> ServerEvent {
+    ServerEvent {
+        kind: kind.to_string(),
+        data,
+    }
+}
+
+#[test]
+fn test_prompt_run_records_events_per_node() {
+    let prompt: ApiPrompt = serde_json::from_value(json!({
+        "1": {"class_type": "KSampler", "inputs": {}},
+        "2": {"class_type": "CheckpointLoaderSimple", "inputs": {}},
+        "3": {"class_type": "PreviewImage", "inputs": {}}
+    }))
+    .unwrap();
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+    let at = |ms: i64| start + ChronoDuration::milliseconds(ms);
+    let mut run = PromptRun::new("KSampler", "p1");
+
+    let events = [
+        (0, event("execution_start", json!({"prompt_id": "p1"}))),
+        (
+            0,
+            event(
+                "execution_cached",
+                json!({"nodes": ["2"], "prompt_id": "p1"}),
+            ),
+        ),
+        (
+            10,
+            event("executing", json!({"node": "1", "prompt_id": "p1"})),
+        ),
+        // Another client's prompt
+        (
+            20,
+            event("executing", json!({"node": "3", "prompt_id": "other"})),
+        ),
+        (30, event("progress", json!({"value": 5, "max": 20}))),
+        (
+            50,
+            event(
+                "progress",
+                json!({"value": 20, "max": 20, "node": "1", "prompt_id": "p1"}),
+            ),
+        ),
+        (
+            250,
+            event("executing", json!({"node": "3", "prompt_id": "p1"})),
+        ),
+        (
+            300,
+            event(
+                "execution_error",
+                json!({
+                    "prompt_id": "p1",
+                    "node_id": "3",
+                    "node_type": "PreviewImage",
+                    "exception_type": "RuntimeError",
+                    "exception_message": "out of memory\n"
+                }),
+            ),
+        ),
+        (
+            310,
+            event("executing", json!({"node": null, "prompt_id": "p1"})),
+        ),
+        (
+            400,
+            event("executing", json!({"node": "1", "prompt_id": "p1"})),
+        ),
+    ];
+    for (ms, event) in &events {
+        run.record(event, &prompt, at(*ms));
+    }
+
+    assert!(run.is_finished());
+    assert_eq!(run.status.as_deref(), Some("error"));
+    assert_eq!(run.duration_ms, Some(310));
+    let ids: Vec<&str> = run.nodes.iter().map(|n| n.node_id.as_str()).collect();
+    assert_eq!(ids, ["2", "1", "3"]);
+
+    let (cached, sampler, preview) = (&run.nodes[0], &run.nodes[1], &run.nodes[2]);
+    assert!(cached.cached);
+    assert_eq!(cached.started_at, None);
+    assert_eq!(sampler.class_type.as_deref(), Some("KSampler"));
+    assert_eq!(sampler.duration_ms, Some(240));
+    assert_eq!(sampler.progress, Some(Progress { value: 20, max: 20 }));
+    assert_eq!(preview.duration_ms, Some(60));
+    assert_eq!(preview.error.as_deref(), Some("out of memory"));
+    assert_eq!(run.failed_node().unwrap().node_id, "3");
+    let error = run.error.as_ref().unwrap();
+    assert_eq!(error.node_type.as_deref(), Some("PreviewImage"));
+    assert_eq!(error.exception_type.as_deref(), Some("RuntimeError"));
+
+    assert_eq!(
+        ServerEvent::from_json(r#"{"type": "status", "data": {"sid": "abc"}}"#)
+            .unwrap()
+            .kind,
+        "status"
+    );
+    assert_eq!(ServerEvent::from_json("not json"), None);
+}
+
+#[test]
+fn test_runner_records_run_log_from_mock_server() {
+    let server = MockServer::start_fixture().unwrap();
+    server.fail_node("ImageInvert", "CUDA out of memory");
+    let client = ApiClient::new(server.url());
+    let object_info = client.object_info().unwrap();
+    let mut runner = ApiTestRunner::new(&client, &object_info);
+    runner.poll_interval = Duration::from_millis(10);
+    runner.monitor().unwrap();
+
+    let sampler = runner.run_node("KSampler").unwrap();
+    assert_eq!(sampler.status, ApiTestStatus::Executed);
+    let run = sampler.execution.as_ref().unwrap();
+    assert_eq!(Some(run.prompt_id.as_str()), sampler.prompt_id.as_deref());
+    assert_eq!(run.status.as_deref(), Some("success"));
+    assert!(run.is_finished());
+    let node = |class_type: &str| {
+        run.nodes
+            .iter()
+            .find(|n| n.class_type.as_deref() == Some(class_type))
+            .unwrap()
+    };
+    assert_eq!(
+        node("KSampler").progress,
+        Some(Progress { value: 20, max: 20 })
+    );
+    assert!(node("PreviewImage").executed);
+    assert!(run.nodes.iter().all(|n| n.duration_ms.is_some()));
+    // Inputs run first, the output node last
+    assert_eq!(
+        run.nodes.last().unwrap().class_type.as_deref(),
+        Some("PreviewImage")
+    );
+
+    let invert = runner.run_node("ImageInvert").unwrap();
+    assert_eq!(invert.status, ApiTestStatus::Failed);
+    let run = invert.execution.as_ref().unwrap();
+    assert_eq!(run.status.as_deref(), Some("error"));
+    let failed = run.failed_node().unwrap();
+    assert_eq!(failed.class_type.as_deref(), Some("ImageInvert"));
+    assert_eq!(failed.error.as_deref(), Some("CUDA out of memory"));
+
+    let dir = TempDir::new().unwrap();
+    let mut log = RunLog::new(client.base_url(), client.client_id());
+    log.prompts = vec![sampler.execution.unwrap(), invert.execution.unwrap()];
+    log.to_file(RunLog::path(dir.path())).unwrap();
+    let loaded = RunLog::from_file(RunLog::path(dir.path())).unwrap();
+    assert_eq!(loaded.schema_version, 1);
+    assert_eq!(loaded.prompts, log.prompts);
+}
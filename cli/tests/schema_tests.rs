@@ -0,0 +1,239 @@
+use comfy_qa::models::{Export, Metadata, TestOutcome, EXPORT_SCHEMA, METADATA_SCHEMA};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn export_json(version: Option<&str>) -> Value {
+    let mut export = json!({
+        "exported_at": "2024-01-01T00:00:00Z",
+        "project_name": "test",
+        "environment": {
+            "url": "http://localhost:8188",
+            "user_agent": null,
+            "hanzo_studio_version": "unknown"
+        },
+        "packs": [
+            {"name": "pack1", "node_count": 1, "nodes": [
+                {"name": "NodeA", "display_name": "Node A", "deprecated": false, "category": "test"}
+            ]}
+        ]
+    });
+    if let Some(version) = version {
+        export["version"] = json!(version);
+    }
+    export
+}
+
+#[test]
+fn test_export_migrates_older_versions() {
+    for version in [None, Some("1.0")] {
+        let export = Export::from_json(&export_json(version).to_string()).unwrap();
+        assert_eq!(export.version, EXPORT_SCHEMA.current);
+        // "unknown" was the 1.0 placeholder for a missing version
+        assert_eq!(export.environment.hanzo_studio_version, None);
+        assert_eq!(export.environment.frontend_version, None);
+        assert_eq!(export.packs[0].nodes[0].name, "NodeA");
+    }
+
+    let mut current = export_json(Some("1.1"));
+    current["environment"]["hanzo_studio_version"] = json!("0.3.10");
+    current["environment"]["frontend_version"] = json!("1.20.0");
+    let export = Export::from_json(&current.to_string()).unwrap();
+    assert_eq!(
+        export.environment.hanzo_studio_version.as_deref(),
+        Some("0.3.10")
+    );
+    assert_eq!(
+        export.environment.frontend_version.as_deref(),
+        Some("1.20.0")
+    );
+}
+
+#[test]
+fn test_export_errors_name_the_field() {
+    let mut export = export_json(Some("1.1"));
+    export["packs"][0]["node_count"] = json!("one");
+    let err = Export::from_json(&export.to_string()).unwrap_err();
+    assert!(
+        err.to_string().contains("packs[0].node_count"),
+        "unexpected error: {}",
+        err
+    );
+
+    let mut export = export_json(Some("1.1"));
+    export["packs"][0]["nodes"][0]
+        .as_object_mut()
+        .unwrap()
+        .remove("name");
+    let err = Export::from_json(&export.to_string()).unwrap_err();
+    assert!(err.to_string().contains("packs[0].nodes[0]"));
+    assert!(err.to_string().contains("missing field `name`"));
+
+    let err = Export::from_json(&export_json(Some("9.0")).to_string()).unwrap_err();
+    assert!(err.to_string().contains("version 9.0 is not supported"));
+}
+
+#[test]
+fn test_metadata_schema_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let metadata_path = temp_dir.path().join("metadata.json");
+
+    // Files from before schema_version existed
+    fs::write(
+        &metadata_path,
+        json!({
+            "project_name": "legacy",
+            "created_at": "2024-01-01T00:00:00Z",
+            "environment": {"type": "cloud", "url": "https://example.com"},
+            "packs": {"pack1": {"node_count": 3, "tested": true}}
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let metadata = Metadata::from_file(&metadata_path).unwrap();
+    assert_eq!(metadata.schema_version, 2);
+    assert_eq!(METADATA_SCHEMA.current, 2);
+    assert!(metadata.packs["pack1"].tested);
+    assert_eq!(metadata.packs["pack1"].outcome, Some(TestOutcome::Pass));
+
+    metadata.to_file(&metadata_path).unwrap();
+    let written: Value =
+        serde_json::from_str(&fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    assert_eq!(written["schema_version"], json!(2));
+    assert!(Metadata::from_file(&metadata_path).is_ok());
+
+    fs::write(
+        &metadata_path,
+        json!({
+            "schema_version": 2,
+            "project_name": "broken",
+            "created_at": "2024-01-01T00:00:00Z",
+            "environment": {"type": "cloud", "url": "https://example.com"},
+            "packs": {"pack1": {"node_count": -1, "tested": true}}
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let err = format!("{:#}", Metadata::from_file(&metadata_path).unwrap_err());
+    assert!(
+        err.contains("packs.pack1.node_count"),
+        "unexpected error: {}",
+        err
+    );
+
+    fs::write(&metadata_path, json!({"schema_version": 3}).to_string()).unwrap();
+    let err = format!("{:#}", Metadata::from_file(&metadata_path).unwrap_err());
+    assert!(err.contains("version 3 is not supported"));
+}
+
+/// Property names declared by the committed metadata.schema.json under
+/// `pointer`.
+fn schema_properties(schema: &Value, pointer: &str) -> Vec<String> {
+    schema
+        .pointer(pointer)
+        .and_then(Value::as_object)
+        .unwrap_or_else(|| panic!("no properties at {}", pointer))
+        .keys()
+        .cloned()
+        .collect()
+}
+
+fn assert_declared(value: &Value, declared: &[String], what: &str) {
+    for key in value.as_object().unwrap().keys() {
+        assert!(
+            declared.contains(key),
+            "{} `{}` is not in the schema",
+            what,
+            key
+        );
+    }
+}
+
+#[test]
+fn test_committed_v1_metadata_migrates_to_v2() {
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let committed =
+        Metadata::from_file(repo_root.join("checklists/vue-nodes-cloud/metadata.json")).unwrap();
+    assert_eq!(committed.schema_version, 2);
+    assert!(committed.packs.is_empty());
+
+    // A v1 file with packs, as the CLI wrote it before schema_version
+    let temp_dir = TempDir::new().unwrap();
+    let metadata_path = temp_dir.path().join("metadata.json");
+    fs::write(
+        &metadata_path,
+        json!({
+            "project_name": "vue-nodes-cloud",
+            "created_at": "2026-01-16T06:32:19.285446761+00:00",
+            "last_updated": null,
+            "environment": {
+                "type": "cloud",
+                "url": "https://app.hanzo.ai",
+                "hanzo_studio_version": null,
+                "frontend_version": null
+            },
+            "packs": {
+                "comfy-core": {"node_count": 12, "tested": true, "workflow_file": "workflows/all-nodes-comfy-core.json", "notes": null, "issues": null},
+                "comfyui-example-pack": {"node_count": 2, "tested": false, "workflow_file": null, "notes": null, "issues": ["Preview is blank"]}
+            },
+            "stats": {"total_packs": 2, "tested_packs": 1, "total_nodes": 14, "completion_percent": 50.0}
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut metadata = Metadata::from_file(&metadata_path).unwrap();
+    assert_eq!(
+        metadata.packs["comfy-core"].outcome,
+        Some(TestOutcome::Pass)
+    );
+    assert_eq!(
+        metadata.packs["comfyui-example-pack"].outcome,
+        Some(TestOutcome::Untested)
+    );
+    metadata.workflow_roots = vec!["workflows/cloud".to_string()];
+    metadata.calculate_stats();
+    metadata.to_file(&metadata_path).unwrap();
+
+    let written: Value =
+        serde_json::from_str(&fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    assert_eq!(written["schema_version"], json!(2));
+    assert_eq!(written["packs"]["comfy-core"]["outcome"], "pass");
+    assert_eq!(
+        written["stats"]["outcomes"],
+        json!({"untested": 1, "pass": 1})
+    );
+    assert_eq!(
+        Metadata::from_file(&metadata_path).unwrap().packs["comfy-core"].outcome(),
+        TestOutcome::Pass
+    );
+
+    // The committed JSON schema describes every field of the v2 file
+    let schema: Value = serde_json::from_str(
+        &fs::read_to_string(repo_root.join("checklists/schema/metadata.schema.json")).unwrap(),
+    )
+    .unwrap();
+    assert_declared(
+        &written,
+        &schema_properties(&schema, "/properties"),
+        "field",
+    );
+    let pack_fields = schema_properties(
+        &schema,
+        "/properties/packs/patternProperties/^[a-zA-Z0-9_-]+$/properties",
+    );
+    for pack in written["packs"].as_object().unwrap().values() {
+        assert_declared(pack, &pack_fields, "pack field");
+    }
+    assert_declared(
+        &written["stats"],
+        &schema_properties(&schema, "/properties/stats/properties"),
+        "stats field",
+    );
+    let outcomes = &schema["definitions"]["outcome"]["enum"];
+    for outcome in written["stats"]["outcomes"].as_object().unwrap().keys() {
+        assert!(outcomes.as_array().unwrap().contains(&json!(outcome)));
+    }
+}
@@ -0,0 +1,431 @@
+use comfy_qa::models::{
+    Catalog, Checklist, ChecklistDocument, ChecklistMerge, DetailedChecklist, Export, ExportPack,
+    Metadata, NodeChanges, PackChange, TestOutcome, Workflow,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_workflows(dir: &Path) {
+    let workflows_dir = dir.join("workflows");
+    fs::create_dir_all(&workflows_dir).unwrap();
+    fs::write(
+        workflows_dir.join("all-nodes-pack1.json"),
+        json!({"id": "a", "nodes": [{"id": 1, "type": "NodeA"}, {"id": 2, "type": "NodeB"}]})
+            .to_string(),
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("all-nodes-pack2.json"),
+        json!({"id": "b", "nodes": [{"id": 1, "type": "NodeC"}]}).to_string(),
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("all-nodes-pack3.json"),
+        json!({"id": "c", "nodes": [{"id": 1, "type": "NodeD"}]}).to_string(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_sync_keeps_results_and_flags_missing_packs() {
+    let temp_dir = TempDir::new().unwrap();
+    write_workflows(temp_dir.path());
+
+    let checklist_content = r#"# Node Pack QA Checklist
+
+Tester notes stay.
+
+- [x] pack1 (1) <!-- API tested -->
+- [!] retired (3)
+"#;
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(&checklist_path, checklist_content).unwrap();
+
+    let metadata_path = temp_dir.path().join("metadata.json");
+    fs::write(
+        &metadata_path,
+        json!({
+            "project_name": "test",
+            "created_at": "2024-01-01T00:00:00Z",
+            "last_updated": null,
+            "environment": {"type": "local", "url": "http://localhost:8188"},
+            "packs": {
+                "pack1": {"node_count": 1, "tested": false},
+                "pack2": {"node_count": 1, "tested": true, "outcome": "blocked"}
+            },
+            "stats": null
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let checklist = Checklist::from_file(&checklist_path).unwrap();
+    let workflows = Workflow::load_all(temp_dir.path().join("workflows")).unwrap();
+    let mut metadata = Metadata::from_file(&metadata_path).unwrap();
+
+    let merge = ChecklistMerge::reconcile(&checklist, &workflows, Some(&metadata));
+    let changes: Vec<_> = merge
+        .changes
+        .iter()
+        .map(|c| (c.name.as_str(), c.change.clone()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("pack1", PackChange::CountChanged { from: 1, to: 2 }),
+            ("retired", PackChange::Missing),
+            (
+                "pack2",
+                PackChange::Added {
+                    restored: Some(TestOutcome::Blocked)
+                }
+            ),
+            ("pack3", PackChange::Added { restored: None }),
+        ]
+    );
+    assert_eq!(merge.missing().collect::<Vec<_>>(), vec!["retired"]);
+    assert_eq!(merge.conflicts.len(), 1);
+    assert!(merge.conflicts[0].starts_with("pack1:"));
+
+    assert_eq!(
+        checklist.document.render(&merge.packs),
+        r#"# Node Pack QA Checklist
+
+Tester notes stay.
+
+- [x] pack1 (2) <!-- API tested -->
+- [!] retired (3)
+- [~] pack2 (1)
+- [ ] pack3 (1)
+"#
+    );
+
+    merge.apply_to_metadata(&mut metadata);
+    assert_eq!(metadata.packs["pack1"].node_count, 2);
+    assert_eq!(metadata.packs["pack1"].outcome(), TestOutcome::Pass);
+    assert_eq!(metadata.packs["pack3"].outcome(), TestOutcome::Untested);
+    assert_eq!(metadata.stats.unwrap().total_packs, 4);
+}
+
+#[test]
+fn test_sync_carries_node_results_into_detailed_checklist() {
+    let temp_dir = TempDir::new().unwrap();
+    write_workflows(temp_dir.path());
+
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(
+        &checklist_path,
+        "# Node Pack QA Checklist\n\n- [x] pack1 (2)\n- [ ] retired (1)\n",
+    )
+    .unwrap();
+    let detailed_path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(
+        &detailed_path,
+        r#"# Node Pack QA Checklist
+
+## pack1
+
+- [ ] NodeGone
+- [x] NodeB <!-- Both -->
+
+## retired
+
+- [!] OldNode
+"#,
+    )
+    .unwrap();
+
+    let checklist = Checklist::from_file(&checklist_path).unwrap();
+    let workflows = Workflow::load_all(temp_dir.path().join("workflows")).unwrap();
+
+    let merge = ChecklistMerge::reconcile(&checklist, &workflows, None);
+    assert_eq!(
+        merge.render_detailed(
+            Some(&fs::read_to_string(&detailed_path).unwrap()),
+            &workflows
+        ),
+        r#"# Node Pack QA Checklist
+
+## pack1
+
+- [ ] NodeA
+- [x] NodeB <!-- Both -->
+
+## retired
+
+- [!] OldNode
+
+## pack2
+
+- [ ] NodeC
+
+## pack3
+
+- [ ] NodeD
+"#
+    );
+}
+
+#[test]
+fn test_sync_keeps_notes_in_detailed_checklist() {
+    let temp_dir = TempDir::new().unwrap();
+    write_workflows(temp_dir.path());
+
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(
+        &checklist_path,
+        "# Node Pack QA Checklist\n\n- [x] pack1 (2)\n",
+    )
+    .unwrap();
+    let detailed_path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(
+        &detailed_path,
+        r#"# Nodes for the March release
+
+Run against the staging server.
+
+## pack1
+
+- [ ] NodeGone
+  - note: crashed on load
+- [x] NodeB <!-- Both -->
+  - note: slow
+"#,
+    )
+    .unwrap();
+
+    let checklist = Checklist::from_file(&checklist_path).unwrap();
+    let workflows = Workflow::load_all(temp_dir.path().join("workflows")).unwrap();
+    let merge = ChecklistMerge::reconcile(&checklist, &workflows, None);
+
+    assert_eq!(
+        merge.render_detailed(
+            Some(&fs::read_to_string(&detailed_path).unwrap()),
+            &workflows
+        ),
+        r#"# Nodes for the March release
+
+Run against the staging server.
+
+## pack1
+
+- [ ] NodeA
+- [x] NodeB <!-- Both -->
+  - note: slow
+
+## pack2
+
+- [ ] NodeC
+
+## pack3
+
+- [ ] NodeD
+"#
+    );
+}
+
+#[test]
+fn test_merge_export_keeps_results_and_marks_removed_nodes() {
+    let temp_dir = TempDir::new().unwrap();
+    let checklist_path = temp_dir.path().join("checklist.md");
+    fs::write(
+        &checklist_path,
+        "# Node Pack QA Checklist\n\n- [x] pack1 (2)\n- [!] gone (1)\n",
+    )
+    .unwrap();
+    let detailed_path = temp_dir.path().join("checklist-detailed.md");
+    fs::write(
+        &detailed_path,
+        r#"# Node Pack QA Checklist
+
+## pack1
+
+- [x] Node A <!-- Both -->
+- [!] NodeB
+
+## gone
+
+- [!] NodeG
+"#,
+    )
+    .unwrap();
+
+    let export = Export::from_json(
+        &json!({
+            "version": "1.1",
+            "exported_at": "2024-02-01T00:00:00Z",
+            "project_name": "test",
+            "environment": {"url": "http://localhost:8188", "user_agent": null, "hanzo_studio_version": null},
+            "packs": [
+                {"name": "pack1", "node_count": 2, "nodes": [
+                    {"name": "A", "display_name": "Node A", "deprecated": true, "category": null},
+                    {"name": "NodeC", "display_name": null, "deprecated": false, "category": null}
+                ]},
+                {"name": "new", "node_count": 1, "nodes": [
+                    {"name": "NodeN", "display_name": null, "deprecated": false, "category": null}
+                ]}
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let packs: HashMap<String, ExportPack> = export
+        .packs
+        .iter()
+        .map(|p| (p.name.clone(), p.clone()))
+        .collect();
+
+    let checklist = Checklist::from_file(&checklist_path).unwrap();
+    let detailed = DetailedChecklist::from_file(&detailed_path).unwrap();
+    let mut merge = ChecklistMerge::reconcile(&checklist, &packs, None);
+    merge.mark_removed = true;
+    merge.catalog = Catalog::from_export(&export);
+
+    assert_eq!(merge.missing().collect::<Vec<_>>(), vec!["gone"]);
+    assert_eq!(
+        merge.node_changes(&detailed, &packs),
+        vec![NodeChanges {
+            pack: "pack1".to_string(),
+            added: vec!["NodeC".to_string()],
+            removed: vec!["NodeB".to_string()],
+        }]
+    );
+    assert_eq!(
+        checklist.document.render(&merge.packs),
+        "# Node Pack QA Checklist\n\n- [x] pack1 (2)\n- [!] gone (1)\n- [ ] new (1)\n"
+    );
+    assert_eq!(
+        merge.render_detailed(Some(&fs::read_to_string(&detailed_path).unwrap()), &packs),
+        r#"# Node Pack QA Checklist
+
+## pack1
+
+- [x] Node A ~~DEPRECATED~~ <!-- Both -->
+- [!] NodeB ~~REMOVED~~
+- [ ] NodeC
+
+## gone
+
+- [!] NodeG ~~REMOVED~~
+
+## new
+
+- [ ] NodeN
+"#
+    );
+
+    // Sync afterwards: workflows hold class names, the checklist display names
+    let imported =
+        merge.render_detailed(Some(&fs::read_to_string(&detailed_path).unwrap()), &packs);
+    let workflows: HashMap<String, Workflow> = [(
+        "pack1".to_string(),
+        Workflow::from_value(
+            "all-nodes-pack1.json",
+            json!({"id": "a", "nodes": [{"id": 1, "type": "A"}, {"id": 2, "type": "NodeC"}]}),
+        )
+        .unwrap(),
+    )]
+    .into_iter()
+    .collect();
+    let mut sync = ChecklistMerge::reconcile(&checklist, &workflows, None);
+    sync.mark_removed = true;
+    sync.catalog = Catalog::from_export(&export).with_workflows(workflows.values());
+    assert_eq!(
+        sync.node_changes(&DetailedChecklist::parse(&imported), &workflows),
+        Vec::new()
+    );
+    assert_eq!(sync.render_detailed(Some(&imported), &workflows), imported);
+}
+
+fn sync_pack1(types: &[&str], catalog: Catalog) -> (ChecklistMerge, HashMap<String, Workflow>) {
+    let nodes: Vec<_> = types
+        .iter()
+        .enumerate()
+        .map(|(i, node_type)| json!({"id": i + 1, "type": node_type}))
+        .collect();
+    let workflows: HashMap<String, Workflow> = [(
+        "pack1".to_string(),
+        Workflow::from_value("all-nodes-pack1.json", json!({"id": "a", "nodes": nodes})).unwrap(),
+    )]
+    .into_iter()
+    .collect();
+    let document = ChecklistDocument::parse("# Node Pack QA Checklist\n\n- [x] pack1 (2)\n");
+    let checklist = Checklist {
+        file_path: "checklist.md".into(),
+        project_name: "test".to_string(),
+        packs: document.packs(),
+        document,
+    };
+    let mut merge = ChecklistMerge::reconcile(&checklist, &workflows, None);
+    merge.mark_removed = true;
+    merge.catalog = catalog.with_workflows(workflows.values());
+    (merge, workflows)
+}
+
+#[test]
+fn test_sync_marks_tested_nodes_removed_with_their_notes() {
+    let detailed = r#"# Node Pack QA Checklist
+
+## pack1
+
+- [x] NodeA <!-- Both -->
+  - note: needs a 512px image
+- [!] NodeB
+"#;
+    let (merge, workflows) = sync_pack1(&["NodeB"], Catalog::default());
+
+    assert_eq!(
+        merge.node_changes(&DetailedChecklist::parse(detailed), &workflows),
+        vec![NodeChanges {
+            pack: "pack1".to_string(),
+            added: Vec::new(),
+            removed: vec!["NodeA".to_string()],
+        }]
+    );
+    assert_eq!(
+        merge.render_detailed(Some(detailed), &workflows),
+        r#"# Node Pack QA Checklist
+
+## pack1
+
+- [x] NodeA ~~REMOVED~~ <!-- Both -->
+  - note: needs a 512px image
+- [!] NodeB
+"#
+    );
+}
+
+#[test]
+fn test_sync_keeps_results_of_nodes_that_become_deprecated() {
+    let detailed =
+        "# Node Pack QA Checklist\n\n## pack1\n\n- [x] Node A\n  - note: ok\n- [ ] NodeB\n";
+    let export = Export::from_json(
+        &json!({
+            "version": "1.1",
+            "exported_at": "2024-02-01T00:00:00Z",
+            "project_name": "test",
+            "environment": {"url": "http://localhost:8188", "user_agent": null, "hanzo_studio_version": null},
+            "packs": [
+                {"name": "pack1", "node_count": 2, "nodes": [
+                    {"name": "A", "display_name": "Node A", "deprecated": true, "category": null},
+                    {"name": "NodeB", "display_name": null, "deprecated": false, "category": null}
+                ]}
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let (merge, workflows) = sync_pack1(&["A", "NodeB"], Catalog::from_export(&export));
+
+    assert_eq!(
+        merge.node_changes(&DetailedChecklist::parse(detailed), &workflows),
+        Vec::new()
+    );
+    assert_eq!(
+        merge.render_detailed(Some(detailed), &workflows),
+        "# Node Pack QA Checklist\n\n## pack1\n\n- [x] Node A ~~DEPRECATED~~\n  - note: ok\n- [ ] NodeB\n"
+    );
+}
@@ -74,7 +74,7 @@ fn test_checklist_multiple_formats() {
     let checklist = Checklist::from_file(&checklist_path).unwrap();
 
     assert_eq!(checklist.packs.len(), 3);
-    assert!(checklist.packs[0].tested);
-    assert!(!checklist.packs[1].tested);
-    assert!(checklist.packs[2].tested);
+    assert!(checklist.packs[0].tested());
+    assert!(!checklist.packs[1].tested());
+    assert!(checklist.packs[2].tested());
 }
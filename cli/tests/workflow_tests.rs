@@ -1,4 +1,6 @@
-use comfy_qa::models::Workflow;
+use comfy_qa::models::{
+    NodeId, NodeMode, Workflow, WorkflowDiscovery, WorkflowKind, WorkflowScope,
+};
 use serde_json::json;
 use std::fs;
 use tempfile::TempDir;
@@ -107,3 +109,337 @@ fn test_empty_workflow() {
     assert_eq!(workflow.node_count, 0);
     assert_eq!(workflow.get_unique_node_types().len(), 0);
 }
+
+#[test]
+fn test_workflow_full_graph_parsing() {
+    let workflow_json = json!({
+        "id": "graph-workflow",
+        "last_node_id": 2,
+        "last_link_id": 1,
+        "nodes": [
+            {
+                "id": 1,
+                "type": "LoadImage",
+                "mode": 0,
+                "inputs": [],
+                "outputs": [
+                    {"name": "IMAGE", "type": "IMAGE", "links": [1]},
+                    {"name": "MASK", "type": "MASK", "links": null}
+                ],
+                "properties": {"Node name for S&R": "LoadImage"},
+                "widgets_values": ["example.png", "image"]
+            },
+            {
+                "id": 2,
+                "type": "PreviewImage",
+                "mode": 4,
+                "inputs": [{"name": "images", "type": "IMAGE", "link": 1}],
+                "outputs": [],
+                "widgets_values": {"preview": true}
+            }
+        ],
+        "links": [[1, 1, 0, 2, 0, "IMAGE"]],
+        "groups": [{"id": 1, "title": "Inputs", "bounding": [0, 0, 100, 100]}],
+        "config": {},
+        "extra": {"frontendVersion": "1.26.7"},
+        "version": 0.4
+    });
+
+    let temp_dir = TempDir::new().unwrap();
+    let workflow_path = temp_dir.path().join("all-nodes-graph-pack.json");
+    fs::write(&workflow_path, workflow_json.to_string()).unwrap();
+
+    let workflow = Workflow::from_file(&workflow_path).unwrap();
+    let graph = &workflow.graph;
+
+    assert_eq!(graph.version, Some(0.4));
+    assert_eq!(graph.groups[0].title, "Inputs");
+    assert_eq!(graph.extra["frontendVersion"], "1.26.7");
+
+    let link = graph.get_link(1).unwrap();
+    assert_eq!(link.origin_id, NodeId::Number(1));
+    assert_eq!(link.target_slot, 0);
+    assert_eq!(link.link_type, "IMAGE");
+
+    let load_image = graph.get_node(&NodeId::Number(1)).unwrap();
+    assert_eq!(load_image.outputs[0].links, Some(vec![1]));
+    assert_eq!(load_image.widget_list()[0], "example.png");
+
+    let preview = graph.get_node(&NodeId::Number(2)).unwrap();
+    assert_eq!(preview.mode, NodeMode::Bypass);
+    assert_eq!(preview.inputs[0].link, Some(1));
+    assert!(preview.widget_list().is_empty());
+    assert_eq!(graph.active_nodes().count(), 1);
+}
+
+#[test]
+fn test_workflow_object_links() {
+    let workflow_json = json!({
+        "nodes": [
+            {"id": 1, "type": "NodeA", "outputs": [{"name": "out", "type": "LATENT", "links": [7]}]},
+            {"id": 2, "type": "NodeB", "inputs": [{"name": "in", "type": "LATENT", "link": 7}]}
+        ],
+        "links": [{
+            "id": 7,
+            "origin_id": 1,
+            "origin_slot": 0,
+            "target_id": 2,
+            "target_slot": 0,
+            "type": "LATENT"
+        }]
+    });
+
+    let temp_dir = TempDir::new().unwrap();
+    let workflow_path = temp_dir.path().join("all-nodes-object-links.json");
+    fs::write(&workflow_path, workflow_json.to_string()).unwrap();
+
+    let workflow = Workflow::from_file(&workflow_path).unwrap();
+    let links = workflow.graph.links_into(&NodeId::Number(2));
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].id, 7);
+    assert_eq!(links[0].link_type, "LATENT");
+}
+
+#[test]
+fn test_discovery_recurses_and_tags_kinds() {
+    let temp_dir = TempDir::new().unwrap();
+    let workflows_dir = temp_dir.path().join("workflows");
+    for folder in ["non-executable", "executable", "generated-shared"] {
+        fs::create_dir_all(workflows_dir.join(folder)).unwrap();
+    }
+
+    let workflow_json = json!({"nodes": [{"id": 1, "type": "NodeA"}]}).to_string();
+    fs::write(
+        workflows_dir.join("non-executable/all-nodes-pack1.json"),
+        &workflow_json,
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("executable/Pack1 (1).json"),
+        &workflow_json,
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("executable/all-nodes-not-a-pack.json"),
+        &workflow_json,
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("generated-shared/search-load.json"),
+        &workflow_json,
+    )
+    .unwrap();
+
+    let all_nodes = Workflow::load_all(&workflows_dir).unwrap();
+    assert_eq!(all_nodes.len(), 1);
+    assert!(all_nodes.contains_key("pack1"));
+
+    let executable = Workflow::load_kind(&workflows_dir, WorkflowKind::Executable).unwrap();
+    assert_eq!(executable.len(), 2);
+    assert!(executable
+        .iter()
+        .all(|w| w.kind == WorkflowKind::Executable));
+
+    assert_eq!(Workflow::discover(&workflows_dir).unwrap().len(), 4);
+}
+
+#[test]
+fn test_discovery_config_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let workflows_dir = temp_dir.path().join("workflows");
+    fs::create_dir_all(workflows_dir.join("packs/old")).unwrap();
+
+    let workflow_json = json!({"nodes": [{"id": 1, "type": "NodeA"}]}).to_string();
+    fs::write(
+        workflows_dir.join("packs/all-nodes-keep.json"),
+        &workflow_json,
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join("packs/old/all-nodes-skip.json"),
+        &workflow_json,
+    )
+    .unwrap();
+    fs::write(
+        workflows_dir.join(WorkflowDiscovery::CONFIG_FILE),
+        json!({
+            "sources": [{
+                "folder": "packs",
+                "kind": "all-nodes",
+                "include": ["**/all-nodes-*.json"],
+                "exclude": ["old/**"]
+            }]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let workflows = Workflow::load_all(&workflows_dir).unwrap();
+
+    assert_eq!(workflows.len(), 1);
+    assert!(workflows.contains_key("keep"));
+}
+
+#[test]
+fn test_subgraphs_expand_recursively() {
+    let temp_dir = TempDir::new().unwrap();
+    let outer = "a1b2c3d4-0000-4000-8000-000000000001";
+    let inner = "a1b2c3d4-0000-4000-8000-000000000002";
+    let workflow_json = json!({
+        "nodes": [
+            {"id": 1, "type": "LoadImage"},
+            {"id": 2, "type": outer},
+            {"id": 3, "type": outer},
+            {"id": 4, "type": "workflow>Upscale"}
+        ],
+        "links": [],
+        "definitions": {"subgraphs": [
+            {"id": outer, "name": "Outer", "nodes": [
+                {"id": 1, "type": "ImageResizeKJ"},
+                {"id": 2, "type": inner}
+            ], "links": [{"id": 1, "origin_id": -10, "origin_slot": 0,
+                          "target_id": 1, "target_slot": 0, "type": "IMAGE"}]},
+            {"id": inner, "name": "Inner", "nodes": [
+                {"id": 1, "type": "GetImageSizeAndCount"},
+                {"id": 2, "type": outer}
+            ]}
+        ]},
+        "extra": {"groupNodes": {"Upscale": {"nodes": [
+            {"type": "UpscaleModelLoader", "index": 0},
+            {"type": "ImageUpscaleWithModel", "index": 1}
+        ]}}}
+    });
+
+    let path = temp_dir.path().join("subgraphs.json");
+    fs::write(&path, workflow_json.to_string()).unwrap();
+    let workflow = Workflow::from_file(&path).unwrap();
+
+    assert_eq!(workflow.graph.subgraphs().len(), 2);
+    // 1 + 2 * (1 + 1) + 2; the inner -> outer cycle is not followed
+    assert_eq!(workflow.node_count, 7);
+    assert_eq!(
+        workflow.get_unique_node_types(),
+        vec![
+            "GetImageSizeAndCount",
+            "ImageResizeKJ",
+            "ImageUpscaleWithModel",
+            "LoadImage",
+            "UpscaleModelLoader"
+        ]
+    );
+
+    // Deserializing directly expands the graph the same way
+    let reloaded: Workflow =
+        serde_json::from_value(serde_json::to_value(&workflow).unwrap()).unwrap();
+    assert_eq!(reloaded.nodes().len(), 7);
+    assert_eq!(
+        reloaded.get_unique_node_types(),
+        workflow.get_unique_node_types()
+    );
+}
+
+#[test]
+fn test_project_workflow_scope() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_root = temp_dir.path();
+    let pack = |name: &str| json!({"id": name, "nodes": [{"id": 1, "type": "NodeA"}]});
+
+    fs::create_dir_all(repo_root.join("workflows/cloud/executable")).unwrap();
+    fs::write(
+        repo_root.join("workflows/all-nodes-shared.json"),
+        pack("shared").to_string(),
+    )
+    .unwrap();
+    fs::write(
+        repo_root.join("workflows/cloud/all-nodes-cloud-only.json"),
+        pack("cloud").to_string(),
+    )
+    .unwrap();
+    fs::write(
+        repo_root.join("workflows/cloud/executable/demo.json"),
+        pack("demo").to_string(),
+    )
+    .unwrap();
+
+    fs::create_dir_all(repo_root.join("checklists/cloud")).unwrap();
+    fs::create_dir_all(repo_root.join("checklists/local")).unwrap();
+    fs::write(
+        repo_root.join("checklists/cloud/metadata.json"),
+        json!({
+            "project_name": "cloud",
+            "created_at": "2024-01-01T00:00:00Z",
+            "last_updated": null,
+            "environment": {"type": "cloud", "url": "https://example.com"},
+            "packs": {},
+            "stats": null,
+            "workflow_roots": ["workflows/cloud"]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let cloud = WorkflowScope::for_project(repo_root, "cloud").unwrap();
+    assert_eq!(cloud.primary(), repo_root.join("workflows/cloud"));
+    let cloud_packs = cloud.load_all().unwrap();
+    assert_eq!(cloud_packs.keys().collect::<Vec<_>>(), vec!["cloud-only"]);
+    assert_eq!(cloud.load_kind(WorkflowKind::Executable).unwrap().len(), 1);
+
+    // Projects without roots see the shared folder minus claimed ones
+    let local = WorkflowScope::for_project(repo_root, "local").unwrap();
+    let local_packs = local.load_all().unwrap();
+    assert_eq!(local_packs.keys().collect::<Vec<_>>(), vec!["shared"]);
+    assert!(local
+        .load_kind(WorkflowKind::Executable)
+        .unwrap()
+        .is_empty());
+
+    assert_eq!(
+        WorkflowScope::shared(repo_root).load_all().unwrap().len(),
+        2
+    );
+}
+
+#[test]
+fn test_workflow_scope_rejects_bad_roots_and_skips_broken_projects() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_root = temp_dir.path();
+    let metadata = |roots: serde_json::Value| {
+        json!({
+            "project_name": "p",
+            "created_at": "2024-01-01T00:00:00Z",
+            "last_updated": null,
+            "environment": {"type": "local", "url": "http://localhost:8188"},
+            "packs": {},
+            "stats": null,
+            "workflow_roots": roots
+        })
+        .to_string()
+    };
+
+    for project in ["escape", "absolute", "broken", "local"] {
+        fs::create_dir_all(repo_root.join("checklists").join(project)).unwrap();
+    }
+    fs::write(
+        repo_root.join("checklists/escape/metadata.json"),
+        metadata(json!(["workflows/../../elsewhere"])),
+    )
+    .unwrap();
+    fs::write(
+        repo_root.join("checklists/absolute/metadata.json"),
+        metadata(json!(["/etc"])),
+    )
+    .unwrap();
+    fs::write(
+        repo_root.join("checklists/broken/metadata.json"),
+        "{ not json",
+    )
+    .unwrap();
+
+    assert!(WorkflowScope::for_project(repo_root, "escape").is_err());
+    assert!(WorkflowScope::for_project(repo_root, "absolute").is_err());
+    assert!(WorkflowScope::for_project(repo_root, "broken").is_err());
+
+    let local = WorkflowScope::for_project(repo_root, "local").unwrap();
+    assert!(local.excluded.is_empty());
+}